@@ -1,10 +1,18 @@
 use crate::alipay::prelude::*;
+use crate::utils::*;
 use crate::*;
 
 pub trait BillTrait {
     /// 申请交易账单
     /// 帐单下载地址30秒后失效
     fn trade_bill(&self, query: ReqBillQuery) -> BoxFuture<ResBill>;
+    /// 下载账单 zip 并解压,返回 GBK 解码后的各文件内容
+    /// 下载地址有时效且无需签名
+    fn download_bill<'a>(&'a self, bill_download_url: &'a str) -> BoxFuture<'a, Vec<String>>;
+    /// 申请、下载并解析交易账单(bill_type=trade)
+    fn download_trade_bill(&self, bill_date: String) -> BoxFuture<'_, AlipayTradeBill>;
+    /// 申请、下载并解析账务账单(bill_type=signcustomer)
+    fn download_account_bill(&self, bill_date: String) -> BoxFuture<'_, AlipayAccountBill>;
 }
 impl BillTrait for Payment<AlipayConfig> {
     fn trade_bill(&self, query: ReqBillQuery) -> BoxFuture<ResBill> {
@@ -14,6 +22,44 @@ impl BillTrait for Payment<AlipayConfig> {
             self.do_request::<ResBill>(&url, "POST", &query).await
         })
     }
+    fn download_bill<'a>(&'a self, bill_download_url: &'a str) -> BoxFuture<'a, Vec<String>> {
+        Box::pin(async move {
+            let resp = reqwest::Client::new()
+                .get(bill_download_url)
+                .header("User-Agent", SDK_UA)
+                .send()
+                .await?;
+            if !resp.status().is_success() {
+                return Err(e(&resp.text().await?));
+            }
+            let content = resp.bytes().await?;
+            unzip_bill(&content)
+        })
+    }
+    fn download_trade_bill(&self, bill_date: String) -> BoxFuture<'_, AlipayTradeBill> {
+        Box::pin(async move {
+            let query = ReqBillQuery {
+                bill_type: "trade".to_string(),
+                bill_date,
+                ..Default::default()
+            };
+            let bill = self.trade_bill(query).await?;
+            let files = self.download_bill(&bill.bill_download_url).await?;
+            AlipayTradeBill::parse(&files)
+        })
+    }
+    fn download_account_bill(&self, bill_date: String) -> BoxFuture<'_, AlipayAccountBill> {
+        Box::pin(async move {
+            let query = ReqBillQuery {
+                bill_type: "signcustomer".to_string(),
+                bill_date,
+                ..Default::default()
+            };
+            let bill = self.trade_bill(query).await?;
+            let files = self.download_bill(&bill.bill_download_url).await?;
+            AlipayAccountBill::parse(&files)
+        })
+    }
 }
 
 #[cfg(test)]
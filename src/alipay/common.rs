@@ -30,6 +30,9 @@ pub trait BaseTrait {
     /// 预处理异步通知此方法仅针对异步URL通知的数据进行验签
     /// 如当面付的预下单通知，APP支付的异步通知等
     fn notify(&self, query_str: &str) -> WeaResult<NotifyOrderBody>;
+    /// 处理http请求形式的异步通知
+    /// 支付宝以 POST 表单方式发送异步通知,表单为空时使用url中的query
+    fn notify_request<B: AsRef<[u8]>>(&self, req: &http::Request<B>) -> WeaResult<NotifyOrderBody>;
     /// 构建请求client 同时设置好请求头
     /// 如果设置了mch_key 则会对body进行加密
     fn build_request_builder(
@@ -166,6 +169,15 @@ impl BaseTrait for Payment<AlipayConfig> {
         let notify: NotifyOrderBody = serde_json::from_value(hm_value)?;
         Ok(notify)
     }
+    //notify from http request
+    fn notify_request<B: AsRef<[u8]>>(&self, req: &http::Request<B>) -> WeaResult<NotifyOrderBody> {
+        let body =
+            std::str::from_utf8(req.body().as_ref()).map_err(|_e| e("notify body is not utf8"))?;
+        if body.is_empty() {
+            return self.notify(req.uri().query().unwrap_or(""));
+        }
+        self.notify(body)
+    }
     //get uri
     fn get_uri(&self, method: &str) -> String {
         let url = method.replace(".", "/");
@@ -391,7 +403,7 @@ impl BaseTrait for Payment<AlipayConfig> {
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use crate::alipay::prelude::*;
     use crate::*;
     //test aes encrypt and decrypt
@@ -511,6 +523,72 @@ mod tests {
             println!("qr_code==>{:?}", result.qr_code);
         }
     }
+    // test notify from http form body
+    #[test]
+    fn test_notify_request() {
+        let (config, form) = signed_notify_form();
+        let payment = Payment::new(config);
+        let req = http::Request::builder()
+            .method("POST")
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(form.clone())
+            .unwrap();
+        let result = payment.notify_request(&req).unwrap();
+        assert_eq!(result.out_trade_no, "T20240407007");
+        let req = http::Request::builder()
+            .uri(format!("https://example.com/notify?{}", form))
+            .body(vec![])
+            .unwrap();
+        assert!(payment.notify_request(&req).is_ok());
+        let tampered = form.replace("total_amount=0.99", "total_amount=9.99");
+        let req = http::Request::builder().body(tampered).unwrap();
+        assert!(payment.notify_request(&req).is_err());
+    }
+    // 生成签名后的异步通知表单,返回可验签的配置
+    pub(crate) fn signed_notify_form() -> (AlipayConfig, String) {
+        use openssl::{hash::MessageDigest, pkey::PKey, rsa::Rsa, sign::Signer};
+        let rsa = Rsa::generate(2048).unwrap();
+        let public_key = openssl::base64::encode_block(&rsa.public_key_to_der().unwrap());
+        let public_key_file =
+            crate::utils::write_temp_file("alipay_public.txt", public_key.as_bytes());
+        let mut params = vec![
+            ("app_id", "9021000135675809"),
+            ("buyer_id", "2088722032795825"),
+            ("buyer_logon_id", "uyskdk2812@sandbox.com"),
+            ("notify_id", "2024072401222104407195820503475973"),
+            ("notify_time", "2024-07-24 10:44:07"),
+            ("notify_type", "trade_status_sync"),
+            ("out_trade_no", "T20240407007"),
+            ("receipt_amount", "0.99"),
+            ("total_amount", "0.99"),
+            ("trade_no", "2024072422001495820503421248"),
+            ("trade_status", "TRADE_SUCCESS"),
+        ];
+        params.sort();
+        let sign_content = params
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<String>>()
+            .join("&");
+        let pkey = PKey::from_rsa(rsa).unwrap();
+        let mut signer = Signer::new(MessageDigest::sha256(), &pkey).unwrap();
+        signer.update(sign_content.as_bytes()).unwrap();
+        let sign = openssl::base64::encode_block(&signer.sign_to_vec().unwrap());
+        let mut url = reqwest::Url::parse("https://example.com/").unwrap();
+        {
+            let mut pairs = url.query_pairs_mut();
+            for (key, value) in params.iter() {
+                pairs.append_pair(key, value);
+            }
+            pairs.append_pair("sign_type", "RSA2");
+            pairs.append_pair("sign", &sign);
+        }
+        let config = AlipayConfig {
+            alipay_public_cert: public_key_file,
+            ..Default::default()
+        };
+        (config, url.query().unwrap().to_string())
+    }
     // test pre_notify
     #[test]
     fn test_pre_notify() {
@@ -1,4 +1,10 @@
+use crate::utils::*;
+use crate::WeaResult;
+use encoding_rs::GBK;
 use serde::{Deserialize, Serialize};
+use std::io::{Cursor, Read};
+use std::path::Path;
+use zip::ZipArchive;
 //bill请求参数
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct ReqBillQuery {
@@ -18,3 +24,515 @@ pub struct ResBill {
     //账单文件结果说明
     pub bill_file_code: String,
 }
+/// 交易账单(业务明细)明细,金额单位为分
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub struct TradeBillDetail {
+    //支付宝交易号
+    pub trade_no: String,
+    //商户订单号
+    pub out_trade_no: String,
+    //业务类型 交易/退款
+    pub business_type: String,
+    //商品名称
+    pub subject: String,
+    //创建时间
+    pub create_time: String,
+    //完成时间
+    pub finish_time: String,
+    //门店编号
+    pub store_id: String,
+    //门店名称
+    pub store_name: String,
+    //操作员
+    pub operator: String,
+    //终端号
+    pub terminal_id: String,
+    //对方账户
+    pub buyer_account: String,
+    //订单金额
+    pub total_amount: i64,
+    //商家实收
+    pub receipt_amount: i64,
+    //支付宝红包
+    pub alipay_red_packet: i64,
+    //集分宝
+    pub point_amount: i64,
+    //支付宝优惠
+    pub alipay_discount: i64,
+    //商家优惠
+    pub merchant_discount: i64,
+    //券核销金额
+    pub voucher_amount: i64,
+    //券名称
+    pub voucher_name: String,
+    //商家红包消费金额
+    pub merchant_red_packet: i64,
+    //卡消费金额
+    pub card_amount: i64,
+    //退款批次号/请求号
+    pub out_request_no: String,
+    //服务费
+    pub service_fee: i64,
+    //分润
+    pub royalty: i64,
+    //备注
+    pub remark: String,
+}
+/// 交易账单按门店汇总,金额单位为分
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub struct TradeBillStoreSummary {
+    //门店编号
+    pub store_id: String,
+    //门店名称
+    pub store_name: String,
+    //交易订单总笔数
+    pub trade_count: i64,
+    //退款订单总笔数
+    pub refund_count: i64,
+    //订单金额
+    pub total_amount: i64,
+    //商家实收
+    pub receipt_amount: i64,
+    //支付宝优惠
+    pub alipay_discount: i64,
+    //商家优惠
+    pub merchant_discount: i64,
+    //卡消费金额
+    pub card_amount: i64,
+    //服务费
+    pub service_fee: i64,
+    //分润
+    pub royalty: i64,
+    //实收净额
+    pub net_amount: i64,
+}
+/// 交易账单合计,金额单位为分
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub struct TradeBillTotal {
+    //交易笔数
+    pub trade_count: i64,
+    //交易商家实收
+    pub trade_amount: i64,
+    //交易商家优惠
+    pub trade_merchant_discount: i64,
+    //退款笔数
+    pub refund_count: i64,
+    //退款商家实收
+    pub refund_amount: i64,
+    //退款商家优惠
+    pub refund_merchant_discount: i64,
+}
+/// 解析后的交易账单(bill_type=trade)
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub struct AlipayTradeBill {
+    //业务明细
+    pub details: Vec<TradeBillDetail>,
+    //门店汇总,不含合计行
+    pub stores: Vec<TradeBillStoreSummary>,
+    //合计
+    pub total: TradeBillTotal,
+}
+/// 账务账单(账务明细)明细,金额单位为分
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub struct AccountBillDetail {
+    //账务流水号
+    pub account_log_id: String,
+    //业务流水号
+    pub trade_no: String,
+    //商户订单号
+    pub out_trade_no: String,
+    //商品名称
+    pub subject: String,
+    //发生时间
+    pub trans_time: String,
+    //对方账号
+    pub other_account: String,
+    //收入金额
+    pub income: i64,
+    //支出金额,负数
+    pub expense: i64,
+    //账户余额
+    pub balance: i64,
+    //交易渠道
+    pub channel: String,
+    //业务类型
+    pub business_type: String,
+    //备注
+    pub remark: String,
+}
+/// 账务账单合计,金额单位为分
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub struct AccountBillTotal {
+    //收入笔数
+    pub income_count: i64,
+    //收入金额
+    pub income_amount: i64,
+    //支出笔数
+    pub expense_count: i64,
+    //支出金额,负数
+    pub expense_amount: i64,
+}
+/// 解析后的账务账单(bill_type=signcustomer)
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub struct AlipayAccountBill {
+    //账务明细
+    pub details: Vec<AccountBillDetail>,
+    //合计
+    pub total: AccountBillTotal,
+}
+impl AlipayTradeBill {
+    /// 解析解压后的账单文件,files 为 GBK 解码后的文件内容
+    /// 按文件首行区分业务明细及业务汇总
+    pub fn parse(files: &[String]) -> WeaResult<AlipayTradeBill> {
+        let mut bill = AlipayTradeBill::default();
+        let mut found = false;
+        for content in files.iter() {
+            let sections = BillSections::parse(content);
+            if sections.title.contains("业务明细") {
+                found = true;
+                for row in sections.rows.iter() {
+                    bill.details
+                        .push(parse_trade_detail(&sections.header, row)?);
+                }
+                for line in sections.comments.iter() {
+                    let numbers = footer_numbers(line);
+                    if line.starts_with("#交易合计") && numbers.len() >= 3 {
+                        bill.total.trade_count = parse_count(numbers[0])?;
+                        bill.total.trade_amount = yuan_to_fen(numbers[1])?;
+                        bill.total.trade_merchant_discount = yuan_to_fen(numbers[2])?;
+                    } else if line.starts_with("#退款合计") && numbers.len() >= 3 {
+                        bill.total.refund_count = parse_count(numbers[0])?;
+                        bill.total.refund_amount = yuan_to_fen(numbers[1])?;
+                        bill.total.refund_merchant_discount = yuan_to_fen(numbers[2])?;
+                    }
+                }
+            } else if sections.title.contains("业务汇总") {
+                for row in sections.rows.iter() {
+                    if row.first().map(|v| v.as_str()) == Some("合计") {
+                        continue;
+                    }
+                    bill.stores
+                        .push(parse_trade_store_summary(&sections.header, row)?);
+                }
+            }
+        }
+        if !found {
+            return Err(e("trade bill detail not found"));
+        }
+        Ok(bill)
+    }
+    /// 从 zip 内容解析
+    pub fn from_zip(data: &[u8]) -> WeaResult<AlipayTradeBill> {
+        AlipayTradeBill::parse(&unzip_bill(data)?)
+    }
+    /// 从下载的 zip 文件解析
+    pub fn from_file<P: AsRef<Path>>(path: P) -> WeaResult<AlipayTradeBill> {
+        AlipayTradeBill::from_zip(&std::fs::read(path)?)
+    }
+}
+impl AlipayAccountBill {
+    /// 解析解压后的账单文件,files 为 GBK 解码后的文件内容
+    pub fn parse(files: &[String]) -> WeaResult<AlipayAccountBill> {
+        let mut bill = AlipayAccountBill::default();
+        let mut found = false;
+        for content in files.iter() {
+            let sections = BillSections::parse(content);
+            if !sections.title.contains("账务明细") {
+                continue;
+            }
+            found = true;
+            for row in sections.rows.iter() {
+                bill.details
+                    .push(parse_account_detail(&sections.header, row)?);
+            }
+            for line in sections.comments.iter() {
+                let numbers = footer_numbers(line);
+                if line.starts_with("#收入合计") && numbers.len() >= 2 {
+                    bill.total.income_count = parse_count(numbers[0])?;
+                    bill.total.income_amount = yuan_to_fen(numbers[1])?;
+                } else if line.starts_with("#支出合计") && numbers.len() >= 2 {
+                    bill.total.expense_count = parse_count(numbers[0])?;
+                    bill.total.expense_amount = yuan_to_fen(numbers[1])?;
+                }
+            }
+        }
+        if !found {
+            return Err(e("account bill detail not found"));
+        }
+        Ok(bill)
+    }
+    /// 从 zip 内容解析
+    pub fn from_zip(data: &[u8]) -> WeaResult<AlipayAccountBill> {
+        AlipayAccountBill::parse(&unzip_bill(data)?)
+    }
+    /// 从下载的 zip 文件解析
+    pub fn from_file<P: AsRef<Path>>(path: P) -> WeaResult<AlipayAccountBill> {
+        AlipayAccountBill::from_zip(&std::fs::read(path)?)
+    }
+}
+/// 解压账单 zip 并按 GBK 解码每个文件
+pub fn unzip_bill(data: &[u8]) -> WeaResult<Vec<String>> {
+    let mut archive =
+        ZipArchive::new(Cursor::new(data)).map_err(|err| e(&format!("zip error:{}", err)))?;
+    let mut files = Vec::new();
+    for i in 0..archive.len() {
+        let mut file = archive
+            .by_index(i)
+            .map_err(|err| e(&format!("zip error:{}", err)))?;
+        if file.is_dir() {
+            continue;
+        }
+        let mut content = Vec::new();
+        file.read_to_end(&mut content)?;
+        let (content, _, had_errors) = GBK.decode(&content);
+        if had_errors {
+            return Err(e("bill decode error"));
+        }
+        files.push(content.into_owned());
+    }
+    Ok(files)
+}
+/// 账单文件按行拆分,# 开头的为注释行,第一个非注释行为表头
+struct BillSections {
+    title: String,
+    header: Vec<String>,
+    rows: Vec<Vec<String>>,
+    comments: Vec<String>,
+}
+impl BillSections {
+    fn parse(content: &str) -> BillSections {
+        let mut sections = BillSections {
+            title: String::new(),
+            header: Vec::new(),
+            rows: Vec::new(),
+            comments: Vec::new(),
+        };
+        let lines = content
+            .trim_start_matches('\u{feff}')
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty());
+        for line in lines {
+            if line.starts_with('#') {
+                if sections.title.is_empty() {
+                    sections.title = line.to_string();
+                }
+                sections.comments.push(line.to_string());
+            } else if sections.header.is_empty() {
+                sections.header = split_csv_line(line);
+            } else {
+                sections.rows.push(split_csv_line(line));
+            }
+        }
+        sections
+    }
+}
+/// 拆分 CSV 行,支持双引号包裹的字段,去掉字段两端的空白及制表符
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field).trim().to_string()),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field.trim().to_string());
+    fields
+}
+/// 提取合计行中的数字,如 #交易合计：3笔，商家实收共：0.03元
+fn footer_numbers(line: &str) -> Vec<&str> {
+    line.split(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-'))
+        .filter(|s| s.chars().any(|c| c.is_ascii_digit()))
+        .collect()
+}
+fn parse_count(value: &str) -> WeaResult<i64> {
+    value
+        .parse::<i64>()
+        .map_err(|_e| e(&format!("invalid count {}", value)))
+}
+/// 去掉表头中的金额单位,如 订单金额（元） => 订单金额
+fn column_name(name: &str) -> &str {
+    name.split(['（', '(']).next().unwrap_or(name).trim()
+}
+fn parse_trade_detail(header: &[String], row: &[String]) -> WeaResult<TradeBillDetail> {
+    let mut detail = TradeBillDetail::default();
+    for (name, value) in header.iter().zip(row.iter()) {
+        let value = value.to_string();
+        match column_name(name) {
+            "支付宝交易号" => detail.trade_no = value,
+            "商户订单号" => detail.out_trade_no = value,
+            "业务类型" => detail.business_type = value,
+            "商品名称" => detail.subject = value,
+            "创建时间" => detail.create_time = value,
+            "完成时间" => detail.finish_time = value,
+            "门店编号" => detail.store_id = value,
+            "门店名称" => detail.store_name = value,
+            "操作员" => detail.operator = value,
+            "终端号" => detail.terminal_id = value,
+            "对方账户" => detail.buyer_account = value,
+            "订单金额" => detail.total_amount = yuan_to_fen(&value)?,
+            "商家实收" => detail.receipt_amount = yuan_to_fen(&value)?,
+            "支付宝红包" => detail.alipay_red_packet = yuan_to_fen(&value)?,
+            "集分宝" => detail.point_amount = yuan_to_fen(&value)?,
+            "支付宝优惠" => detail.alipay_discount = yuan_to_fen(&value)?,
+            "商家优惠" => detail.merchant_discount = yuan_to_fen(&value)?,
+            "券核销金额" => detail.voucher_amount = yuan_to_fen(&value)?,
+            "券名称" => detail.voucher_name = value,
+            "商家红包消费金额" => detail.merchant_red_packet = yuan_to_fen(&value)?,
+            "卡消费金额" => detail.card_amount = yuan_to_fen(&value)?,
+            "退款批次号/请求号" => detail.out_request_no = value,
+            "服务费" => detail.service_fee = yuan_to_fen(&value)?,
+            "分润" => detail.royalty = yuan_to_fen(&value)?,
+            "备注" => detail.remark = value,
+            _ => {}
+        }
+    }
+    Ok(detail)
+}
+fn parse_trade_store_summary(
+    header: &[String],
+    row: &[String],
+) -> WeaResult<TradeBillStoreSummary> {
+    let mut summary = TradeBillStoreSummary::default();
+    for (name, value) in header.iter().zip(row.iter()) {
+        match column_name(name) {
+            "门店编号" => summary.store_id = value.to_string(),
+            "门店名称" => summary.store_name = value.to_string(),
+            "交易订单总笔数" => summary.trade_count = parse_count(value)?,
+            "退款订单总笔数" => summary.refund_count = parse_count(value)?,
+            "订单金额" => summary.total_amount = yuan_to_fen(value)?,
+            "商家实收" => summary.receipt_amount = yuan_to_fen(value)?,
+            "支付宝优惠" => summary.alipay_discount = yuan_to_fen(value)?,
+            "商家优惠" => summary.merchant_discount = yuan_to_fen(value)?,
+            "卡消费金额" => summary.card_amount = yuan_to_fen(value)?,
+            "服务费" => summary.service_fee = yuan_to_fen(value)?,
+            "分润" => summary.royalty = yuan_to_fen(value)?,
+            "实收净额" => summary.net_amount = yuan_to_fen(value)?,
+            _ => {}
+        }
+    }
+    Ok(summary)
+}
+fn parse_account_detail(header: &[String], row: &[String]) -> WeaResult<AccountBillDetail> {
+    let mut detail = AccountBillDetail::default();
+    for (name, value) in header.iter().zip(row.iter()) {
+        let value = value.to_string();
+        match column_name(name) {
+            "账务流水号" => detail.account_log_id = value,
+            "业务流水号" => detail.trade_no = value,
+            "商户订单号" => detail.out_trade_no = value,
+            "商品名称" => detail.subject = value,
+            "发生时间" => detail.trans_time = value,
+            "对方账号" => detail.other_account = value,
+            "收入金额" => detail.income = yuan_to_fen(&value)?,
+            "支出金额" => detail.expense = yuan_to_fen(&value)?,
+            "账户余额" => detail.balance = yuan_to_fen(&value)?,
+            "交易渠道" => detail.channel = value,
+            "业务类型" => detail.business_type = value,
+            "备注" => detail.remark = value,
+            _ => {}
+        }
+    }
+    Ok(detail)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    const TRADE_DETAIL: &str = "#支付宝业务明细查询
+#账号：[20881234567890120156]
+#起始日期：[2024年07月01日 00:00:00]   终止日期：[2024年07月02日 00:00:00]
+#-----------------------------------------业务明细列表----------------------------------------
+支付宝交易号,商户订单号,业务类型,商品名称,创建时间,完成时间,门店编号,门店名称,操作员,终端号,对方账户,订单金额（元）,商家实收（元）,支付宝红包（元）,集分宝（元）,支付宝优惠（元）,商家优惠（元）,券核销金额（元）,券名称,商家红包消费金额（元）,卡消费金额（元）,退款批次号/请求号,服务费（元）,分润（元）,备注
+2024070122001498971400000001\t,T20240701001\t,交易\t,\"咖啡,大杯\"\t,2024-07-01 10:00:00\t,2024-07-01 10:00:05\t,\t,\t,\t,\t,138****0000\t,10.00\t,10.00\t,0.00\t,0.00\t,0.00\t,0.00\t,0.00\t,\t,0.00\t,0.00\t,\t,-0.06\t,0.00\t,
+2024070122001498971400000001\t,T20240701001\t,退款\t,\"咖啡,大杯\"\t,2024-07-01 12:00:00\t,2024-07-01 12:00:01\t,\t,\t,\t,\t,138****0000\t,-2.00\t,-2.00\t,0.00\t,0.00\t,0.00\t,0.00\t,0.00\t,\t,0.00\t,0.00\t,R20240701001\t,0.01\t,0.00\t,
+#-----------------------------------------业务明细列表结束------------------------------------
+#交易合计：1笔，商家实收共：10.00元，商家优惠共：0.00元
+#退款合计：1笔，商家实收退款共：-2.00元，商家优惠退款共：0.00元
+#导出时间：[2024年07月02日 10:20:21]
+";
+    const TRADE_SUMMARY: &str = "#支付宝业务汇总查询
+#账号：[20881234567890120156]
+#起始日期：[2024年07月01日 00:00:00]   终止日期：[2024年07月02日 00:00:00]
+#-----------------------------------------业务汇总列表----------------------------------------
+门店编号,门店名称,交易订单总笔数,退款订单总笔数,订单金额（元）,商家实收（元）,支付宝优惠（元）,商家优惠（元）,卡消费金额（元）,服务费（元）,分润（元）,实收净额（元）
+S001,总店,1,1,8.00,8.00,0.00,0.00,0.00,-0.05,0.00,7.95
+合计,,1,1,8.00,8.00,0.00,0.00,0.00,-0.05,0.00,7.95
+#-----------------------------------------业务汇总列表结束------------------------------------
+#导出时间：[2024年07月02日 10:20:21]
+";
+    const ACCOUNT_DETAIL: &str = "#支付宝账务明细查询
+#账号：[20881234567890120156]
+#起始日期：[2024年07月01日 00:00:00]   终止日期：[2024年07月02日 00:00:00]
+#-----------------------------------------账务明细列表----------------------------------------
+账务流水号,业务流水号,商户订单号,商品名称,发生时间,对方账号,收入金额（+元）,支出金额（-元）,账户余额（元）,交易渠道,业务类型,备注
+20240701000000000001\t,2024070122001498971400000001\t,T20240701001\t,咖啡\t,2024-07-01 10:00:05\t,138****0000\t,10.00\t,0.00\t,110.00\t,支付宝\t,在线支付\t,
+20240701000000000002\t,2024070122001498971400000001\t,T20240701001\t,咖啡\t,2024-07-01 12:00:01\t,138****0000\t,0.00\t,-2.00\t,108.00\t,支付宝\t,交易退款\t,
+#-----------------------------------------账务明细列表结束------------------------------------
+#支出合计：1笔，共-2.00元
+#收入合计：1笔，共10.00元
+#导出时间：[2024年07月02日 10:20:21]
+";
+    fn gbk_zip(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, content) in files.iter() {
+            zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+            let (content, _, _) = GBK.encode(content);
+            zip.write_all(&content).unwrap();
+        }
+        zip.finish().unwrap().into_inner()
+    }
+    #[test]
+    fn test_parse_trade_bill() {
+        let data = gbk_zip(&[
+            ("20881234567890120156_20240701_业务明细.csv", TRADE_DETAIL),
+            (
+                "20881234567890120156_20240701_业务明细(汇总).csv",
+                TRADE_SUMMARY,
+            ),
+        ]);
+        let path = crate::utils::write_temp_file("alipay_trade_bill.zip", &data);
+        let bill = AlipayTradeBill::from_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(bill.details.len(), 2);
+        let detail = &bill.details[0];
+        assert_eq!(detail.trade_no, "2024070122001498971400000001");
+        assert_eq!(detail.subject, "咖啡,大杯");
+        assert_eq!(detail.total_amount, 1000);
+        assert_eq!(detail.service_fee, -6);
+        assert_eq!(bill.details[1].business_type, "退款");
+        assert_eq!(bill.details[1].out_request_no, "R20240701001");
+        assert_eq!(bill.details[1].receipt_amount, -200);
+        assert_eq!(bill.stores.len(), 1);
+        assert_eq!(bill.stores[0].store_name, "总店");
+        assert_eq!(bill.stores[0].net_amount, 795);
+        assert_eq!(bill.total.trade_count, 1);
+        assert_eq!(bill.total.trade_amount, 1000);
+        assert_eq!(bill.total.refund_amount, -200);
+        assert!(AlipayTradeBill::from_zip(&gbk_zip(&[("a.csv", TRADE_SUMMARY)])).is_err());
+    }
+    #[test]
+    fn test_parse_account_bill() {
+        let data = gbk_zip(&[("20881234567890120156_20240701_账务明细.csv", ACCOUNT_DETAIL)]);
+        let bill = AlipayAccountBill::from_zip(&data).unwrap();
+        assert_eq!(bill.details.len(), 2);
+        assert_eq!(bill.details[0].income, 1000);
+        assert_eq!(bill.details[1].expense, -200);
+        assert_eq!(bill.details[1].balance, 10800);
+        assert_eq!(bill.details[1].business_type, "交易退款");
+        assert_eq!(bill.total.income_amount, 1000);
+        assert_eq!(bill.total.expense_count, 1);
+        assert_eq!(bill.total.expense_amount, -200);
+    }
+}
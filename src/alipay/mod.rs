@@ -1,5 +1,6 @@
 pub mod bill;
 pub mod common;
 pub mod dict;
+pub mod page;
 pub mod prelude;
 pub mod refund;
@@ -0,0 +1,295 @@
+use crate::alipay::prelude::*;
+use crate::utils::*;
+use crate::*;
+use encoding_rs::GBK;
+use std::collections::BTreeMap;
+
+/// 电脑网站支付及手机网站支付
+/// 两者均由浏览器跳转到支付宝网关 gateway.do 完成支付,商户服务端不发起请求
+/// 生成的表单或链接已签名,证书模式下自动带上 app_cert_sn 及 alipay_root_cert_sn
+pub trait PagePayTrait {
+    /// 电脑网站支付 alipay.trade.page.pay,返回自动提交的表单 html
+    /// product_code 为空时使用 FAST_INSTANT_TRADE_PAY
+    /// return_url 为支付完成后浏览器跳转的地址
+    fn page_pay_form(&self, data: ReqOrderBody, return_url: Option<String>) -> WeaResult<String>;
+    /// 电脑网站支付,返回 GET 方式跳转的链接
+    fn page_pay_url(&self, data: ReqOrderBody, return_url: Option<String>) -> WeaResult<String>;
+    /// 手机网站支付 alipay.trade.wap.pay,返回自动提交的表单 html
+    /// product_code 为空时使用 QUICK_WAP_WAY,用户中途退出时跳转到 quit_url
+    fn wap_pay_form(&self, data: ReqOrderBody, return_url: Option<String>) -> WeaResult<String>;
+    /// 手机网站支付,返回 GET 方式跳转的链接
+    fn wap_pay_url(&self, data: ReqOrderBody, return_url: Option<String>) -> WeaResult<String>;
+    /// 生成网关请求的全部参数(含 sign)
+    /// notify_url 为空时使用配置中的 notify_url,并作为公共参数传递而不放入 biz_content
+    /// 配置了 mch_key 时 biz_content 使用 AES 加密
+    fn gateway_params(
+        &self,
+        method: &str,
+        data: ReqOrderBody,
+        return_url: Option<String>,
+    ) -> WeaResult<BTreeMap<String, String>>;
+    /// 网关地址,沙盒模式下为沙盒网关
+    fn gateway_url(&self) -> &'static str;
+}
+impl PagePayTrait for Payment<AlipayConfig> {
+    fn page_pay_form(&self, data: ReqOrderBody, return_url: Option<String>) -> WeaResult<String> {
+        let data = with_product_code(data, "FAST_INSTANT_TRADE_PAY");
+        let params = self.gateway_params("alipay.trade.page.pay", data, return_url)?;
+        Ok(build_form(
+            self.gateway_url(),
+            &charset(&self.config)?,
+            &params,
+        ))
+    }
+    fn page_pay_url(&self, data: ReqOrderBody, return_url: Option<String>) -> WeaResult<String> {
+        let data = with_product_code(data, "FAST_INSTANT_TRADE_PAY");
+        let params = self.gateway_params("alipay.trade.page.pay", data, return_url)?;
+        build_url(self.gateway_url(), &charset(&self.config)?, &params)
+    }
+    fn wap_pay_form(&self, data: ReqOrderBody, return_url: Option<String>) -> WeaResult<String> {
+        let data = with_product_code(data, "QUICK_WAP_WAY");
+        let params = self.gateway_params("alipay.trade.wap.pay", data, return_url)?;
+        Ok(build_form(
+            self.gateway_url(),
+            &charset(&self.config)?,
+            &params,
+        ))
+    }
+    fn wap_pay_url(&self, data: ReqOrderBody, return_url: Option<String>) -> WeaResult<String> {
+        let data = with_product_code(data, "QUICK_WAP_WAY");
+        let params = self.gateway_params("alipay.trade.wap.pay", data, return_url)?;
+        build_url(self.gateway_url(), &charset(&self.config)?, &params)
+    }
+    fn gateway_params(
+        &self,
+        method: &str,
+        data: ReqOrderBody,
+        return_url: Option<String>,
+    ) -> WeaResult<BTreeMap<String, String>> {
+        let charset = charset(&self.config)?;
+        let notify_url = data.notify_url.clone().or(self.config.notify_url.clone());
+        let biz_content = serde_json::to_string(&ReqOrderBody {
+            notify_url: None,
+            ..data
+        })?;
+        let mut params = BTreeMap::new();
+        let mut set = |key: &str, value: String| {
+            params.insert(key.to_string(), value);
+        };
+        set("app_id", self.config.app_id.clone());
+        set("method", method.to_string());
+        set("format", "JSON".to_string());
+        set("charset", charset.clone());
+        set("sign_type", "RSA2".to_string());
+        set("timestamp", beijing_time(get_timestamp()?));
+        set("version", "1.0".to_string());
+        if let Some(notify_url) = notify_url {
+            set("notify_url", notify_url);
+        }
+        if let Some(return_url) = return_url {
+            set("return_url", return_url);
+        }
+        if self.config.mch_key.is_some() {
+            set("encrypt_type", "AES".to_string());
+            set("biz_content", self.encrypt(&biz_content)?);
+        } else {
+            set("biz_content", biz_content);
+        }
+        if let Some(alipay_root_cert) = &self.config.alipay_root_cert {
+            let app_public_cert = self
+                .config
+                .app_public_cert
+                .clone()
+                .ok_or(e("app_public_cert is none"))?;
+            set("app_cert_sn", get_cert_sn(&app_public_cert)?);
+            set("alipay_root_cert_sn", get_root_cert_sn(alipay_root_cert)?);
+        }
+        // 参数按 key 排序后以 key=value& 拼接,空值不参与签名
+        let sign_content = params
+            .iter()
+            .filter(|(_, value)| !value.is_empty())
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<String>>()
+            .join("&");
+        let sign = rsa_sha256_sign(
+            &encode_charset(&sign_content, &charset)?,
+            &self.config.app_private_key,
+        )?;
+        params.insert("sign".to_string(), sign);
+        Ok(params)
+    }
+    fn gateway_url(&self) -> &'static str {
+        if self.config.is_sandbox.unwrap_or(false) {
+            "https://openapi-sandbox.dl.alipaydev.com/gateway.do"
+        } else {
+            "https://openapi.alipay.com/gateway.do"
+        }
+    }
+}
+// set default product_code
+fn with_product_code(data: ReqOrderBody, product_code: &str) -> ReqOrderBody {
+    ReqOrderBody {
+        product_code: data.product_code.clone().or(Some(product_code.to_string())),
+        ..data
+    }
+}
+// charset in lower case, only utf-8 and gbk are supported
+fn charset(config: &AlipayConfig) -> WeaResult<String> {
+    let charset = config
+        .charset
+        .clone()
+        .unwrap_or("utf-8".to_string())
+        .to_lowercase();
+    match charset.as_str() {
+        "utf-8" | "gbk" => Ok(charset),
+        _ => Err(e(&format!("unsupported charset {}", charset))),
+    }
+}
+// encode string with charset
+fn encode_charset(data: &str, charset: &str) -> WeaResult<Vec<u8>> {
+    if charset != "gbk" {
+        return Ok(data.as_bytes().to_vec());
+    }
+    let (bytes, _, had_errors) = GBK.encode(data);
+    if had_errors {
+        return Err(e("content can not be encoded as gbk"));
+    }
+    Ok(bytes.into_owned())
+}
+// percent encode with charset, unreserved characters are kept
+fn url_encode(data: &str, charset: &str) -> WeaResult<String> {
+    Ok(encode_charset(data, charset)?
+        .iter()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (*byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect())
+}
+// gateway url with all params in query
+fn build_url(gateway: &str, charset: &str, params: &BTreeMap<String, String>) -> WeaResult<String> {
+    let query = params
+        .iter()
+        .map(|(key, value)| Ok(format!("{}={}", key, url_encode(value, charset)?)))
+        .collect::<WeaResult<Vec<String>>>()?
+        .join("&");
+    Ok(format!("{}?{}", gateway, query))
+}
+// auto submit form, accept-charset makes browser encode the form with charset
+fn build_form(gateway: &str, charset: &str, params: &BTreeMap<String, String>) -> String {
+    let inputs = params
+        .iter()
+        .map(|(key, value)| {
+            format!(
+                "<input type=\"hidden\" name=\"{}\" value=\"{}\">\n",
+                html_escape(key),
+                html_escape(value)
+            )
+        })
+        .collect::<String>();
+    format!(
+        "<form name=\"punchout_form\" method=\"post\" action=\"{}?charset={}\" accept-charset=\"{}\">\n{}<input type=\"submit\" value=\"立即支付\" style=\"display:none\">\n</form>\n<script>document.forms['punchout_form'].submit();</script>",
+        gateway, charset, charset, inputs
+    )
+}
+// escape html attribute value
+fn html_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('\'', "&#39;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openssl::{hash::MessageDigest, pkey::PKey, rsa::Rsa, sign::Verifier};
+    fn new_payment(
+        charset: Option<String>,
+    ) -> (Payment<AlipayConfig>, PKey<openssl::pkey::Private>) {
+        let pkey = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+        let key_file = crate::utils::write_temp_file(
+            "page_pay_key.pem",
+            &pkey.private_key_to_pem_pkcs8().unwrap(),
+        );
+        let config = AlipayConfig {
+            app_id: "2021000000000000".to_string(),
+            app_private_key: key_file,
+            notify_url: Some("https://example.com/notify".to_string()),
+            charset,
+            ..Default::default()
+        };
+        (Payment::new(config), pkey)
+    }
+    fn order() -> ReqOrderBody {
+        ReqOrderBody {
+            out_trade_no: "T20240407003".to_string(),
+            total_amount: "10.01".to_string(),
+            subject: "旅行卡年卡服务".to_string(),
+            ..Default::default()
+        }
+    }
+    fn verify(
+        pkey: &PKey<openssl::pkey::Private>,
+        params: &BTreeMap<String, String>,
+        charset: &str,
+    ) -> bool {
+        let content = params
+            .iter()
+            .filter(|(key, _)| key.as_str() != "sign")
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<String>>()
+            .join("&");
+        let mut verifier = Verifier::new(MessageDigest::sha256(), pkey).unwrap();
+        verifier
+            .update(&encode_charset(&content, charset).unwrap())
+            .unwrap();
+        let sign = openssl::base64::decode_block(&params["sign"]).unwrap();
+        verifier.verify(&sign).unwrap()
+    }
+    #[test]
+    fn test_gateway_params() {
+        let (payment, pkey) = new_payment(None);
+        let data = with_product_code(order(), "FAST_INSTANT_TRADE_PAY");
+        let params = payment
+            .gateway_params(
+                "alipay.trade.page.pay",
+                data,
+                Some("https://example.com/return".to_string()),
+            )
+            .unwrap();
+        assert_eq!(params["notify_url"], "https://example.com/notify");
+        assert_eq!(params["return_url"], "https://example.com/return");
+        assert_eq!(params["charset"], "utf-8");
+        let biz_content: serde_json::Value = serde_json::from_str(&params["biz_content"]).unwrap();
+        assert_eq!(biz_content["product_code"], "FAST_INSTANT_TRADE_PAY");
+        assert!(biz_content.get("notify_url").is_none());
+        assert!(verify(&pkey, &params, "utf-8"));
+        let form = payment.wap_pay_form(order(), None).unwrap();
+        assert!(form.contains("action=\"https://openapi.alipay.com/gateway.do?charset=utf-8\""));
+        assert!(form.contains("name=\"method\" value=\"alipay.trade.wap.pay\""));
+        assert!(form.contains("QUICK_WAP_WAY"));
+        assert!(form.contains("&quot;out_trade_no&quot;"));
+        std::fs::remove_file(&payment.config.app_private_key).unwrap();
+    }
+    #[test]
+    fn test_gateway_url_gbk() {
+        let (payment, pkey) = new_payment(Some("GBK".to_string()));
+        let url = payment.page_pay_url(order(), None).unwrap();
+        assert!(url.starts_with("https://openapi.alipay.com/gateway.do?app_id=2021000000000000&"));
+        // "旅" 的 GBK 编码为 C2C3
+        assert!(url.contains("%C2%C3"));
+        let params = payment
+            .gateway_params("alipay.trade.page.pay", order(), None)
+            .unwrap();
+        assert!(verify(&pkey, &params, "gbk"));
+        std::fs::remove_file(&payment.config.app_private_key).unwrap();
+        let (payment, _) = new_payment(Some("big5".to_string()));
+        assert!(payment.page_pay_url(order(), None).is_err());
+        std::fs::remove_file(&payment.config.app_private_key).unwrap();
+    }
+}
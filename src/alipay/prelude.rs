@@ -50,9 +50,18 @@ pub use super::dict::refund::ReqRefundQuery;
 pub use super::dict::refund::ResRefundQuery;
 pub use super::dict::refund::ResRefundRoyalty;
 //帐单
+pub use super::dict::bill::unzip_bill;
+pub use super::dict::bill::AccountBillDetail;
+pub use super::dict::bill::AccountBillTotal;
+pub use super::dict::bill::AlipayAccountBill;
+pub use super::dict::bill::AlipayTradeBill;
 pub use super::dict::bill::ReqBillQuery;
 pub use super::dict::bill::ResBill;
+pub use super::dict::bill::TradeBillDetail;
+pub use super::dict::bill::TradeBillStoreSummary;
+pub use super::dict::bill::TradeBillTotal;
 
 pub use super::bill::BillTrait;
 pub use super::common::BaseTrait;
+pub use super::page::PagePayTrait;
 pub use super::refund::RefundTrait;
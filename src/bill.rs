@@ -0,0 +1,410 @@
+//! 统一账单,将微信支付及支付宝解析后的交易账单转换为相同的结构
+//! 金额单位统一为分且均为正数,手续费正数为商户支出、负数为退回的手续费
+//! # Examples
+//! ```rust,ignore
+//! use weapay::bill::*;
+//! let mut records = BillRecord::from_wechat(&wechat_bill);
+//! records.extend(BillRecord::from_alipay(&alipay_bill));
+//! let settlement = DailySettlement::summarize("2024-07-01", &records);
+//! std::fs::write("bill.csv", records_to_csv(&records))?;
+//! ```
+use crate::alipay::prelude::{AlipayTradeBill, TradeBillDetail};
+use crate::utils::*;
+use crate::wechat::prelude::{TradeBill, TradeBillRecord};
+use serde::{Deserialize, Serialize};
+
+/// 支付渠道
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq, Eq, Hash)]
+pub enum PayChannel {
+    //微信支付
+    #[default]
+    Wechat,
+    //支付宝
+    Alipay,
+}
+impl PayChannel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PayChannel::Wechat => "WECHAT",
+            PayChannel::Alipay => "ALIPAY",
+        }
+    }
+}
+/// 账单业务类型
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub enum BillKind {
+    //支付
+    #[default]
+    Payment,
+    //退款
+    Refund,
+}
+impl BillKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BillKind::Payment => "PAYMENT",
+            BillKind::Refund => "REFUND",
+        }
+    }
+}
+/// 统一的账单状态
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub enum BillStatus {
+    //成功
+    #[default]
+    Success,
+    //处理中
+    Processing,
+    //已关闭或已撤销
+    Closed,
+    //失败或异常
+    Failed,
+}
+impl BillStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BillStatus::Success => "SUCCESS",
+            BillStatus::Processing => "PROCESSING",
+            BillStatus::Closed => "CLOSED",
+            BillStatus::Failed => "FAILED",
+        }
+    }
+}
+/// 统一的账单记录,金额单位为分
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub struct BillRecord {
+    //支付渠道
+    pub channel: PayChannel,
+    //业务类型
+    pub kind: BillKind,
+    //交易时间,退款为退款时间
+    pub trade_time: String,
+    //商户订单号
+    pub out_trade_no: String,
+    //渠道交易号
+    pub trade_no: String,
+    //商户退款单号,支付记录为空
+    pub out_refund_no: String,
+    //渠道退款单号,支付记录为空
+    pub refund_no: String,
+    //订单金额,退款为退款金额
+    pub amount: i64,
+    //商户实收金额,退款为商户实际退出的金额
+    pub settlement_amount: i64,
+    //手续费,正数为商户支出,负数为退回的手续费
+    pub fee: i64,
+    //状态
+    pub status: BillStatus,
+    //渠道原始状态
+    pub raw_status: String,
+    //支付方式,如微信支付的 JSAPI
+    pub trade_type: String,
+    //商品名称
+    pub subject: String,
+    //备注,微信支付为商户数据包
+    pub remark: String,
+}
+impl BillRecord {
+    /// 转换微信支付交易账单
+    pub fn from_wechat(bill: &TradeBill) -> Vec<BillRecord> {
+        bill.records.iter().map(BillRecord::from).collect()
+    }
+    /// 转换支付宝交易账单
+    pub fn from_alipay(bill: &AlipayTradeBill) -> Vec<BillRecord> {
+        bill.details.iter().map(BillRecord::from).collect()
+    }
+}
+/// 交易状态为 REFUND 的记录转换为退款,其余转换为支付
+impl From<&TradeBillRecord> for BillRecord {
+    fn from(record: &TradeBillRecord) -> Self {
+        let base = BillRecord {
+            channel: PayChannel::Wechat,
+            trade_time: record.trade_time.clone(),
+            out_trade_no: record.out_trade_no.clone(),
+            trade_no: record.transaction_id.clone(),
+            fee: record.fee,
+            trade_type: record.trade_type.clone(),
+            subject: record.goods_name.clone(),
+            remark: record.attach.clone(),
+            ..Default::default()
+        };
+        if record.trade_state == "REFUND" {
+            let status = match record.refund_status.as_str() {
+                "SUCCESS" => BillStatus::Success,
+                "PROCESSING" => BillStatus::Processing,
+                "CLOSED" => BillStatus::Closed,
+                _ => BillStatus::Failed,
+            };
+            BillRecord {
+                kind: BillKind::Refund,
+                trade_time: if record.refund_success_time.is_empty() {
+                    base.trade_time.clone()
+                } else {
+                    record.refund_success_time.clone()
+                },
+                out_refund_no: record.out_refund_no.clone(),
+                refund_no: record.refund_id.clone(),
+                amount: record.refund_amount,
+                settlement_amount: record.refund_amount,
+                status,
+                raw_status: record.refund_status.clone(),
+                ..base
+            }
+        } else {
+            let status = match record.trade_state.as_str() {
+                "SUCCESS" => BillStatus::Success,
+                "NOTPAY" | "USERPAYING" => BillStatus::Processing,
+                "CLOSED" | "REVOKED" => BillStatus::Closed,
+                _ => BillStatus::Failed,
+            };
+            BillRecord {
+                kind: BillKind::Payment,
+                amount: record.total_amount,
+                settlement_amount: record.settlement_total,
+                status,
+                raw_status: record.trade_state.clone(),
+                ..base
+            }
+        }
+    }
+}
+/// 支付宝账单只包含成功的交易及退款,退款金额及服务费为负数,转换后取反
+impl From<&TradeBillDetail> for BillRecord {
+    fn from(detail: &TradeBillDetail) -> Self {
+        let refund = detail.business_type == "退款";
+        BillRecord {
+            channel: PayChannel::Alipay,
+            kind: if refund {
+                BillKind::Refund
+            } else {
+                BillKind::Payment
+            },
+            trade_time: detail.finish_time.clone(),
+            out_trade_no: detail.out_trade_no.clone(),
+            trade_no: detail.trade_no.clone(),
+            out_refund_no: if refund {
+                detail.out_request_no.clone()
+            } else {
+                String::new()
+            },
+            refund_no: String::new(),
+            amount: detail.total_amount.abs(),
+            settlement_amount: detail.receipt_amount.abs(),
+            fee: -detail.service_fee,
+            status: BillStatus::Success,
+            raw_status: detail.business_type.clone(),
+            trade_type: String::new(),
+            subject: detail.subject.clone(),
+            remark: detail.remark.clone(),
+        }
+    }
+}
+/// 导出账单记录为 CSV,金额单位为分
+pub fn records_to_csv(records: &[BillRecord]) -> String {
+    let mut csv = String::from("渠道,业务类型,交易时间,商户订单号,渠道交易号,商户退款单号,渠道退款单号,订单金额,实收金额,手续费,状态,渠道状态,支付方式,商品名称,备注\n");
+    for record in records.iter() {
+        let row = [
+            record.channel.as_str().to_string(),
+            record.kind.as_str().to_string(),
+            record.trade_time.clone(),
+            record.out_trade_no.clone(),
+            record.trade_no.clone(),
+            record.out_refund_no.clone(),
+            record.refund_no.clone(),
+            record.amount.to_string(),
+            record.settlement_amount.to_string(),
+            record.fee.to_string(),
+            record.status.as_str().to_string(),
+            record.raw_status.clone(),
+            record.trade_type.clone(),
+            record.subject.clone(),
+            record.remark.clone(),
+        ];
+        let row = row.iter().map(|value| csv_field(value)).collect::<Vec<_>>();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+/// 按渠道汇总的结算数据,金额单位为分
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub struct ChannelSettlement {
+    //支付渠道
+    pub channel: PayChannel,
+    //成功支付笔数
+    pub payment_count: i64,
+    //实收总额
+    pub payment_amount: i64,
+    //成功退款笔数
+    pub refund_count: i64,
+    //退款总额
+    pub refund_amount: i64,
+    //手续费总额
+    pub fee_amount: i64,
+    //结算净额 = 实收总额 - 退款总额 - 手续费总额
+    pub net_amount: i64,
+}
+impl ChannelSettlement {
+    fn add(&mut self, record: &BillRecord) {
+        match record.kind {
+            BillKind::Payment => {
+                self.payment_count += 1;
+                self.payment_amount += record.settlement_amount;
+            }
+            BillKind::Refund => {
+                self.refund_count += 1;
+                self.refund_amount += record.settlement_amount;
+            }
+        }
+        self.fee_amount += record.fee;
+        self.net_amount = self.payment_amount - self.refund_amount - self.fee_amount;
+    }
+}
+/// 日结算汇总,只统计成功的记录
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub struct DailySettlement {
+    //账单日期
+    pub bill_date: String,
+    //按渠道汇总
+    pub channels: Vec<ChannelSettlement>,
+    //全部渠道合计,channel 字段无意义
+    pub total: ChannelSettlement,
+}
+impl DailySettlement {
+    /// 汇总账单记录
+    pub fn summarize(bill_date: &str, records: &[BillRecord]) -> DailySettlement {
+        let mut settlement = DailySettlement {
+            bill_date: bill_date.to_string(),
+            ..Default::default()
+        };
+        for record in records.iter() {
+            if record.status != BillStatus::Success {
+                continue;
+            }
+            let index = match settlement
+                .channels
+                .iter()
+                .position(|item| item.channel == record.channel)
+            {
+                Some(index) => index,
+                None => {
+                    settlement.channels.push(ChannelSettlement {
+                        channel: record.channel.clone(),
+                        ..Default::default()
+                    });
+                    settlement.channels.len() - 1
+                }
+            };
+            settlement.channels[index].add(record);
+            settlement.total.add(record);
+        }
+        settlement
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wechat_bill() -> TradeBill {
+        TradeBill {
+            records: vec![
+                TradeBillRecord {
+                    trade_time: "2024-07-01 10:00:00".to_string(),
+                    out_trade_no: "T001".to_string(),
+                    trade_type: "JSAPI".to_string(),
+                    trade_state: "SUCCESS".to_string(),
+                    settlement_total: 1000,
+                    total_amount: 1000,
+                    fee: 6,
+                    ..Default::default()
+                },
+                TradeBillRecord {
+                    trade_time: "2024-07-01 12:00:00".to_string(),
+                    out_trade_no: "T001".to_string(),
+                    trade_state: "REFUND".to_string(),
+                    out_refund_no: "R001".to_string(),
+                    refund_amount: 200,
+                    refund_status: "SUCCESS".to_string(),
+                    fee: -1,
+                    ..Default::default()
+                },
+                TradeBillRecord {
+                    out_trade_no: "T002".to_string(),
+                    trade_state: "REVOKED".to_string(),
+                    total_amount: 500,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+    fn alipay_bill() -> AlipayTradeBill {
+        AlipayTradeBill {
+            details: vec![
+                TradeBillDetail {
+                    out_trade_no: "A001".to_string(),
+                    business_type: "交易".to_string(),
+                    subject: "咖啡,大杯".to_string(),
+                    total_amount: 2000,
+                    receipt_amount: 1900,
+                    service_fee: -11,
+                    ..Default::default()
+                },
+                TradeBillDetail {
+                    out_trade_no: "A001".to_string(),
+                    business_type: "退款".to_string(),
+                    out_request_no: "AR001".to_string(),
+                    total_amount: -500,
+                    receipt_amount: -500,
+                    service_fee: 3,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+    #[test]
+    fn test_convert_records() {
+        let records = BillRecord::from_wechat(&wechat_bill());
+        assert_eq!(records[0].kind, BillKind::Payment);
+        assert_eq!(records[1].kind, BillKind::Refund);
+        assert_eq!(records[1].amount, 200);
+        assert_eq!(records[2].status, BillStatus::Closed);
+        let records = BillRecord::from_alipay(&alipay_bill());
+        assert_eq!(records[0].fee, 11);
+        assert_eq!(records[0].settlement_amount, 1900);
+        assert_eq!(records[1].kind, BillKind::Refund);
+        assert_eq!(records[1].out_refund_no, "AR001");
+        assert_eq!(records[1].amount, 500);
+        assert_eq!(records[1].fee, -3);
+    }
+    #[test]
+    fn test_daily_settlement() {
+        let mut records = BillRecord::from_wechat(&wechat_bill());
+        records.extend(BillRecord::from_alipay(&alipay_bill()));
+        let settlement = DailySettlement::summarize("2024-07-01", &records);
+        assert_eq!(settlement.channels.len(), 2);
+        let wechat = &settlement.channels[0];
+        assert_eq!(wechat.payment_count, 1);
+        assert_eq!(wechat.refund_count, 1);
+        assert_eq!(wechat.fee_amount, 5);
+        assert_eq!(wechat.net_amount, 1000 - 200 - 5);
+        let alipay = &settlement.channels[1];
+        assert_eq!(alipay.channel, PayChannel::Alipay);
+        assert_eq!(alipay.net_amount, 1900 - 500 - 8);
+        assert_eq!(settlement.total.payment_amount, 2900);
+        assert_eq!(settlement.total.net_amount, 795 + 1392);
+    }
+    #[test]
+    fn test_records_to_csv() {
+        let records = BillRecord::from_alipay(&alipay_bill());
+        let csv = records_to_csv(&records);
+        let mut lines = csv.lines().skip(1);
+        assert_eq!(
+            lines.next().unwrap(),
+            "ALIPAY,PAYMENT,,A001,,,,2000,1900,11,SUCCESS,交易,,\"咖啡,大杯\","
+        );
+        assert!(lines.next().unwrap().starts_with("ALIPAY,REFUND,"));
+    }
+}
@@ -16,6 +16,21 @@ impl WeaError {
     pub fn new(kind: impl Into<String>, message: String) -> WeaError {
         WeaError(kind.into(), message)
     }
+    /// 错误来源
+    pub fn kind(&self) -> &str {
+        &self.0
+    }
+    /// 文件摘要校验失败
+    pub fn hash_mismatch(expected: &str, actual: &str) -> WeaError {
+        WeaError::new(
+            "HashMismatch",
+            format!("hash mismatch, expected {} actual {}", expected, actual),
+        )
+    }
+    /// 是否为文件摘要校验失败
+    pub fn is_hash_mismatch(&self) -> bool {
+        self.0 == "HashMismatch"
+    }
 }
 impl fmt::Display for WeaError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
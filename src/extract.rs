@@ -0,0 +1,318 @@
+//! axum 异步通知提取器,需要开启 axum feature
+//! 提取器会完成验签、解密,失败时按各支付渠道要求的格式应答
+//! # Examples
+//! ```rust,ignore
+//! use std::sync::Arc;
+//! use axum::Router;
+//! use weapay::extract::{wechat_notify_route, alipay_notify_route};
+//! let wechat = Arc::new(Payment::new(wechat_config));
+//! let alipay = Arc::new(Payment::new(alipay_config));
+//! let app: Router = Router::new()
+//!     .route("/notify/wechat", wechat_notify_route(wechat, |notify| async move {
+//!         println!("{:?}", notify.event);
+//!         Ok(())
+//!     }))
+//!     .route("/notify/alipay", alipay_notify_route(alipay, |notify| async move {
+//!         println!("{}", notify.out_trade_no);
+//!         Ok(())
+//!     }));
+//! ```
+use crate::alipay::prelude::{BaseTrait as AlipayBaseTrait, NotifyOrderBody};
+use crate::notify::NotifyAck;
+use crate::wechat::prelude::{BaseTrait as WechatBaseTrait, NotifyEnvelope};
+use crate::{AlipayConfig, Payment, WeaResult, WechatConfig};
+use axum::extract::{FromRef, FromRequest, Request};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{post, MethodRouter};
+use std::future::Future;
+use std::sync::Arc;
+
+/// 通知请求体的最大长度
+const NOTIFY_BODY_LIMIT: usize = 1024 * 1024;
+
+impl IntoResponse for NotifyAck {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        (
+            status,
+            [(header::CONTENT_TYPE, self.content_type)],
+            self.body,
+        )
+            .into_response()
+    }
+}
+
+/// 微信支付通知提取器,state 中需要能取到 `Arc<Payment<WechatConfig>>`
+/// 验签或解密失败时返回 FAIL 应答
+#[derive(Clone, Debug)]
+pub struct WechatNotify(pub NotifyEnvelope);
+
+impl<S> FromRequest<S> for WechatNotify
+where
+    Arc<Payment<WechatConfig>>: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = NotifyAck;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let payment = Arc::<Payment<WechatConfig>>::from_ref(state);
+        let (parts, body) = req.into_parts();
+        let body = axum::body::to_bytes(body, NOTIFY_BODY_LIMIT)
+            .await
+            .map_err(|err| NotifyAck::wechat_fail(&err.to_string()))?;
+        let notify = payment
+            .notify_http(&parts.headers, &body)
+            .await
+            .map_err(|err| NotifyAck::wechat_fail(&err.to_string()))?;
+        Ok(WechatNotify(notify))
+    }
+}
+
+/// 支付宝通知提取器,state 中需要能取到 `Arc<Payment<AlipayConfig>>`
+/// 验签失败时返回 fail 应答
+#[derive(Clone, Debug)]
+pub struct AlipayNotify(pub NotifyOrderBody);
+
+impl<S> FromRequest<S> for AlipayNotify
+where
+    Arc<Payment<AlipayConfig>>: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = NotifyAck;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let payment = Arc::<Payment<AlipayConfig>>::from_ref(state);
+        let (parts, body) = req.into_parts();
+        let body = axum::body::to_bytes(body, NOTIFY_BODY_LIMIT)
+            .await
+            .map_err(|_err| NotifyAck::alipay_fail())?;
+        let req = Request::from_parts(parts, body);
+        let notify = payment
+            .notify_request(&req)
+            .map_err(|_err| NotifyAck::alipay_fail())?;
+        Ok(AlipayNotify(notify))
+    }
+}
+
+/// 微信支付通知路由,验签解密后将通知交给 handler 处理
+/// handler 返回 Ok 时应答 SUCCESS,返回 Err 时应答 FAIL 由微信支付重新发送通知
+pub fn wechat_notify_route<S, F, Fut>(
+    payment: Arc<Payment<WechatConfig>>,
+    handler: F,
+) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+    F: Fn(NotifyEnvelope) -> Fut + Clone + Send + Sync + 'static,
+    Fut: Future<Output = WeaResult<()>> + Send + 'static,
+{
+    let route = post(move |WechatNotify(notify): WechatNotify| async move {
+        match handler(notify).await {
+            Ok(()) => NotifyAck::wechat_success(),
+            Err(err) => NotifyAck::wechat_fail(&err.to_string()),
+        }
+    });
+    route.with_state(payment)
+}
+
+/// 支付宝通知路由,验签后将通知交给 handler 处理
+/// handler 返回 Ok 时应答 success,返回 Err 时应答 fail 由支付宝重新发送通知
+pub fn alipay_notify_route<S, F, Fut>(
+    payment: Arc<Payment<AlipayConfig>>,
+    handler: F,
+) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+    F: Fn(NotifyOrderBody) -> Fut + Clone + Send + Sync + 'static,
+    Fut: Future<Output = WeaResult<()>> + Send + 'static,
+{
+    let route = post(move |AlipayNotify(notify): AlipayNotify| async move {
+        match handler(notify).await {
+            Ok(()) => NotifyAck::alipay_success(),
+            Err(_err) => NotifyAck::alipay_fail(),
+        }
+    });
+    route.with_state(payment)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{e, TempDir};
+    use axum::body::Body;
+    use axum::Router;
+    use tower::ServiceExt;
+
+    async fn read_body(resp: Response) -> String {
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+    #[tokio::test]
+    async fn test_alipay_notify_route() {
+        let (config, form) = crate::alipay::common::tests::signed_notify_form();
+        let payment = Arc::new(Payment::new(config));
+        let app: Router = Router::new().route(
+            "/notify/alipay",
+            alipay_notify_route(payment.clone(), |notify| async move {
+                assert_eq!(notify.out_trade_no, "T20240407007");
+                Ok(())
+            }),
+        );
+        let req = Request::builder()
+            .method("POST")
+            .uri("/notify/alipay")
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(Body::from(form.clone()))
+            .unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(read_body(resp).await, "success");
+
+        let tampered = form.replace("total_amount=0.99", "total_amount=9.99");
+        let req = Request::builder()
+            .method("POST")
+            .uri("/notify/alipay")
+            .body(Body::from(tampered))
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(read_body(resp).await, "fail");
+
+        let app: Router = Router::new().route(
+            "/notify/alipay",
+            alipay_notify_route(payment, |_notify| async move { Err(e("handle error")) }),
+        );
+        let req = Request::builder()
+            .method("POST")
+            .uri("/notify/alipay")
+            .body(Body::from(form))
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(read_body(resp).await, "fail");
+    }
+    // 生成平台证书签名、APIv3密钥加密的支付成功通知,平台证书保存到临时证书目录
+    // 返回 (配置, 通知请求头, 通知报文, 临时证书目录)
+    fn signed_wechat_notify() -> (WechatConfig, Vec<(&'static str, String)>, String, TempDir) {
+        use crate::utils::{generate_random_string, get_timestamp, write_test_cert};
+        use crate::wechat::prelude::{ResourceOrderBody, RespBody, RespResource};
+        use aes_gcm::{
+            aead::{Aead, Payload},
+            Aes256Gcm, KeyInit, Nonce,
+        };
+        use openssl::{base64::encode_block, hash::MessageDigest, sign::Signer};
+        let mch_key = generate_random_string(32);
+        let order = ResourceOrderBody {
+            out_trade_no: "T20240407003".to_string(),
+            transaction_id: "4200002236202407193283044366".to_string(),
+            ..Default::default()
+        };
+        let nonce = generate_random_string(12);
+        let cipher = Aes256Gcm::new_from_slice(mch_key.as_bytes()).unwrap();
+        let ciphertext = cipher
+            .encrypt(
+                Nonce::from_slice(nonce.as_bytes()),
+                Payload {
+                    msg: serde_json::to_string(&order).unwrap().as_bytes(),
+                    aad: b"transaction",
+                },
+            )
+            .unwrap();
+        let body = serde_json::to_string(&RespBody {
+            id: generate_random_string(16),
+            create_time: "2024-07-19T09:21:31+08:00".to_string(),
+            resource_type: "encrypt-resource".to_string(),
+            event_type: "TRANSACTION.SUCCESS".to_string(),
+            summary: "支付成功".to_string(),
+            resource: RespResource {
+                original_type: "transaction".to_string(),
+                algorithm: "AEAD_AES_256_GCM".to_string(),
+                ciphertext: encode_block(&ciphertext),
+                associated_data: Some("transaction".to_string()),
+                nonce,
+            },
+        })
+        .unwrap();
+
+        let (pkey, key_file, cert_file) = write_test_cert("wechatpay_platform", 1);
+        let serial = format!("WEAPAYTEST{}", generate_random_string(16).to_uppercase());
+        let cert_dir = TempDir::new("wechatpay_certs");
+        std::fs::copy(&cert_file, format!("{}/{}.pem", cert_dir.0, serial)).unwrap();
+        std::fs::remove_file(key_file).unwrap();
+        std::fs::remove_file(cert_file).unwrap();
+
+        let timestamp = get_timestamp().unwrap().to_string();
+        let notify_nonce = generate_random_string(32);
+        let mut signer = Signer::new(MessageDigest::sha256(), &pkey).unwrap();
+        signer
+            .update(format!("{}\n{}\n{}\n", timestamp, notify_nonce, body).as_bytes())
+            .unwrap();
+        let signature = encode_block(&signer.sign_to_vec().unwrap());
+        let headers = vec![
+            ("Wechatpay-Nonce", notify_nonce),
+            ("Wechatpay-Timestamp", timestamp),
+            ("Wechatpay-Signature", signature),
+            ("Wechatpay-Serial", serial),
+        ];
+        let config = WechatConfig {
+            mch_key,
+            notify_store: Some(Arc::new(crate::notify::MemoryNotifyStore::new())),
+            cert_dir: Some(cert_dir.0.clone()),
+            ..Default::default()
+        };
+        (config, headers, body, cert_dir)
+    }
+    #[tokio::test]
+    async fn test_wechat_notify_route() {
+        use crate::wechat::prelude::NotifyEvent;
+        let (config, headers, body, _cert_dir) = signed_wechat_notify();
+        let payment = Arc::new(Payment::new(config));
+        let app: Router = Router::new().route(
+            "/notify/wechat",
+            wechat_notify_route(payment, |notify| async move {
+                match notify.event {
+                    NotifyEvent::TransactionSuccess(order) => {
+                        assert_eq!(order.out_trade_no, "T20240407003");
+                        Ok(())
+                    }
+                    _ => Err(e("event type error")),
+                }
+            }),
+        );
+        let request = || {
+            let mut builder = Request::builder().method("POST").uri("/notify/wechat");
+            for (name, value) in headers.iter() {
+                builder = builder.header(*name, value);
+            }
+            builder.body(Body::from(body.clone())).unwrap()
+        };
+        let resp = app.clone().oneshot(request()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let ack: serde_json::Value = serde_json::from_str(&read_body(resp).await).unwrap();
+        assert_eq!(ack["code"], "SUCCESS");
+
+        // 相同的通知再次发送时被拒绝
+        let resp = app.oneshot(request()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        let ack: serde_json::Value = serde_json::from_str(&read_body(resp).await).unwrap();
+        assert_eq!(ack["code"], "FAIL");
+        assert!(ack["message"].as_str().unwrap().contains("notify replayed"));
+    }
+    #[tokio::test]
+    async fn test_wechat_notify_route_rejects_unsigned() {
+        let payment = Arc::new(Payment::new(WechatConfig::default()));
+        let app: Router = Router::new().route(
+            "/notify/wechat",
+            wechat_notify_route(payment, |_notify| async move { Ok(()) }),
+        );
+        let req = Request::builder()
+            .method("POST")
+            .uri("/notify/wechat")
+            .body(Body::from("{}"))
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        let body: serde_json::Value = serde_json::from_str(&read_body(resp).await).unwrap();
+        assert_eq!(body["code"], "FAIL");
+    }
+}
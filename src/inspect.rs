@@ -0,0 +1,344 @@
+//! 证书有效期检查,读取配置中的全部证书及本地保存的微信支付平台证书
+//! 返回证书主题、序列号、有效期及剩余天数,并检查证书与私钥是否匹配
+//! # Examples
+//! ```rust,ignore
+//! use weapay::inspect::*;
+//! let report = inspect_wechat(&payment)?;
+//! // 支付宝使用 inspect_alipay
+//! for cert in report.expiring_within(30) {
+//!     println!("{} {} 剩余 {} 天", cert.kind.as_str(), cert.path, cert.days_remaining);
+//! }
+//! if report.has_problems() {
+//!     println!("{:?}", report.errors);
+//! }
+//! ```
+use crate::utils::*;
+use crate::wechat::prelude::{BaseTrait, WechatSignType};
+use crate::{AlipayConfig, Payment, WeaResult, WechatConfig};
+use openssl::asn1::{Asn1Time, Asn1TimeRef};
+use openssl::x509::{X509NameRef, X509};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// 证书类型
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CertKind {
+    //微信支付商户API证书
+    #[default]
+    WechatMerchant,
+    //微信支付平台证书
+    WechatPlatform,
+    //支付宝应用公钥证书
+    AlipayAppPublic,
+    //支付宝公钥证书
+    AlipayPublic,
+    //支付宝根证书
+    AlipayRoot,
+}
+impl CertKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CertKind::WechatMerchant => "WECHAT_MERCHANT",
+            CertKind::WechatPlatform => "WECHAT_PLATFORM",
+            CertKind::AlipayAppPublic => "ALIPAY_APP_PUBLIC",
+            CertKind::AlipayPublic => "ALIPAY_PUBLIC",
+            CertKind::AlipayRoot => "ALIPAY_ROOT",
+        }
+    }
+}
+/// 证书信息
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub struct CertInfo {
+    //证书类型
+    pub kind: CertKind,
+    //证书文件路径
+    pub path: String,
+    //证书主题
+    pub subject: String,
+    //颁发者
+    pub issuer: String,
+    //证书序列号,微信为十六进制序列号,支付宝为证书SN
+    pub serial_no: String,
+    //生效时间
+    pub not_before: String,
+    //过期时间
+    pub not_after: String,
+    //剩余天数,已过期时为负数
+    pub days_remaining: i64,
+    //检查发现的问题,如证书与私钥不匹配、已过期
+    pub problems: Vec<String>,
+}
+impl CertInfo {
+    pub fn is_expired(&self) -> bool {
+        self.days_remaining < 0
+    }
+}
+/// 证书检查结果
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub struct CertReport {
+    //证书列表
+    pub certs: Vec<CertInfo>,
+    //无法读取或解析的证书,格式为 "类型 路径: 错误"
+    pub errors: Vec<String>,
+}
+impl CertReport {
+    /// 剩余天数不超过 days 的证书,包括已过期的证书
+    pub fn expiring_within(&self, days: i64) -> Vec<&CertInfo> {
+        self.certs
+            .iter()
+            .filter(|cert| cert.days_remaining <= days)
+            .collect()
+    }
+    /// 是否存在读取错误或证书问题
+    pub fn has_problems(&self) -> bool {
+        !self.errors.is_empty() || self.certs.iter().any(|cert| !cert.problems.is_empty())
+    }
+    /// 合并另一份检查结果,用于同时检查微信支付和支付宝
+    pub fn merge(&mut self, other: CertReport) {
+        self.certs.extend(other.certs);
+        self.errors.extend(other.errors);
+    }
+    fn push(&mut self, kind: CertKind, path: &str, result: WeaResult<Vec<CertInfo>>) {
+        match result {
+            Ok(certs) => self.certs.extend(certs),
+            Err(error) => self
+                .errors
+                .push(format!("{} {}: {}", kind.as_str(), path, error)),
+        }
+    }
+}
+/// 检查微信支付商户API证书及本地保存的平台证书
+/// 商户证书会检查是否与商户私钥匹配,以及私钥类型是否与 sign_type 一致
+pub fn inspect_wechat(payment: &Payment<WechatConfig>) -> WeaResult<CertReport> {
+    let mut report = CertReport::default();
+    let (apiclient_key, apiclient_cert) = payment.merchant_cert();
+    let sign_type = payment.config.sign_type.clone().unwrap_or_default();
+    let result = load_certs(CertKind::WechatMerchant, &apiclient_cert).and_then(|mut certs| {
+        for cert in certs.iter_mut() {
+            cert.serial_no = cert.serial_no.to_uppercase();
+        }
+        let pkey = read_pkey(&apiclient_key)?;
+        let is_sm2 = is_sm2_key(&pkey)?;
+        if let Some(info) = certs.first_mut() {
+            let cert = X509::from_pem(&std::fs::read(&apiclient_cert)?)?;
+            if !cert.public_key()?.public_eq(&pkey) {
+                info.problems
+                    .push("certificate does not match apiclient_key".to_string());
+            }
+            if is_sm2 != (sign_type == WechatSignType::SM2) {
+                info.problems.push(format!(
+                    "apiclient_key type does not match sign_type {}",
+                    sign_type.as_str()
+                ));
+            }
+        }
+        Ok(certs)
+    });
+    report.push(CertKind::WechatMerchant, &apiclient_cert, result);
+    for cert_file in wechat_cert_files(&payment.config.cert_dir())? {
+        let result = load_certs(CertKind::WechatPlatform, &cert_file).map(|mut certs| {
+            let file_serial = Path::new(&cert_file)
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .unwrap_or_default()
+                .to_string();
+            for info in certs.iter_mut() {
+                info.serial_no = info.serial_no.to_uppercase();
+                if !info.serial_no.eq_ignore_ascii_case(&file_serial) {
+                    info.problems.push(format!(
+                        "file name does not match serial_no {}",
+                        info.serial_no
+                    ));
+                }
+            }
+            certs
+        });
+        report.push(CertKind::WechatPlatform, &cert_file, result);
+    }
+    Ok(report)
+}
+/// 检查支付宝证书,普通密钥模式下没有证书,返回空结果
+/// 应用公钥证书会检查是否与应用私钥匹配,证书序列号为支付宝使用的证书SN
+pub fn inspect_alipay(payment: &Payment<AlipayConfig>) -> WeaResult<CertReport> {
+    let mut report = CertReport::default();
+    let config = &payment.config;
+    let root_cert = match &config.alipay_root_cert {
+        Some(root_cert) => root_cert,
+        None => return Ok(report),
+    };
+    if let Some(app_public_cert) = &config.app_public_cert {
+        let result =
+            load_alipay_certs(CertKind::AlipayAppPublic, app_public_cert).and_then(|mut certs| {
+                let pkey = read_pkey(&config.app_private_key)?;
+                let cert = X509::from_pem(&std::fs::read(app_public_cert)?)?;
+                if let Some(info) = certs.first_mut() {
+                    if !cert.public_key()?.public_eq(&pkey) {
+                        info.problems
+                            .push("certificate does not match app_private_key".to_string());
+                    }
+                }
+                Ok(certs)
+            });
+        report.push(CertKind::AlipayAppPublic, app_public_cert, result);
+    }
+    let result = load_alipay_certs(CertKind::AlipayPublic, &config.alipay_public_cert);
+    report.push(CertKind::AlipayPublic, &config.alipay_public_cert, result);
+    let result = load_alipay_certs(CertKind::AlipayRoot, root_cert);
+    report.push(CertKind::AlipayRoot, root_cert, result);
+    Ok(report)
+}
+// load all certs in a pem file, serial_no is the hex serial number
+fn load_certs(kind: CertKind, path: &str) -> WeaResult<Vec<CertInfo>> {
+    let content = std::fs::read(path)?;
+    let certs = X509::stack_from_pem(&content)?;
+    if certs.is_empty() {
+        return Err(e("certificate not found"));
+    }
+    let now = Asn1Time::days_from_now(0)?;
+    certs
+        .iter()
+        .map(|cert| {
+            let days_remaining = days_between(&now, cert.not_after())?;
+            let mut problems = vec![];
+            if days_remaining < 0 {
+                problems.push("certificate expired".to_string());
+            } else if days_between(&now, cert.not_before())? > 0 {
+                problems.push("certificate not yet valid".to_string());
+            }
+            Ok(CertInfo {
+                kind: kind.clone(),
+                path: path.to_string(),
+                subject: name_to_string(cert.subject_name()),
+                issuer: name_to_string(cert.issuer_name()),
+                serial_no: cert.serial_number().to_bn()?.to_hex_str()?.to_string(),
+                not_before: cert.not_before().to_string(),
+                not_after: cert.not_after().to_string(),
+                days_remaining,
+                problems,
+            })
+        })
+        .collect()
+}
+// load alipay certs, serial_no is the cert sn used by alipay
+fn load_alipay_certs(kind: CertKind, path: &str) -> WeaResult<Vec<CertInfo>> {
+    let mut certs = load_certs(kind, path)?;
+    let stack = X509::stack_from_pem(&std::fs::read(path)?)?;
+    for (info, cert) in certs.iter_mut().zip(stack.iter()) {
+        info.serial_no = get_cert_sn_by_content(&cert.to_pem()?)?;
+    }
+    Ok(certs)
+}
+// whole days from `from` to `to`, negative when `to` is earlier
+fn days_between(from: &Asn1TimeRef, to: &Asn1TimeRef) -> WeaResult<i64> {
+    let diff = from.diff(to)?;
+    let days = diff.days as i64;
+    // diff 的 days 与 secs 同号,不足一天的负数部分按一整天计
+    Ok(if days <= 0 && diff.secs < 0 {
+        days - 1
+    } else {
+        days
+    })
+}
+// format x509 name as CN=xx,O=xx
+fn name_to_string(name: &X509NameRef) -> String {
+    name.entries()
+        .map(|entry| {
+            let key = entry.object().nid().short_name().unwrap_or("UNKNOWN");
+            let value = entry
+                .data()
+                .to_string()
+                .unwrap_or_else(|_| String::from_utf8_lossy(entry.data().as_slice()).to_string());
+            format!("{}={}", key, value)
+        })
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::write_test_cert;
+    #[test]
+    fn test_inspect_alipay() {
+        let (_, app_key, app_cert) = write_test_cert("app", 30);
+        let (_, other_key, other_cert) = write_test_cert("other", 30);
+        let (_, public_key, public_cert) = write_test_cert("public", 365);
+        let files = vec![
+            app_key.clone(),
+            app_cert.clone(),
+            other_key.clone(),
+            other_cert,
+            public_key,
+            public_cert.clone(),
+        ];
+        let config = AlipayConfig {
+            app_private_key: app_key,
+            app_public_cert: Some(app_cert.clone()),
+            alipay_public_cert: public_cert,
+            alipay_root_cert: Some("/nonexistent/alipay_root_cert.crt".to_string()),
+            ..Default::default()
+        };
+        let report = inspect_alipay(&Payment::new(config.clone())).unwrap();
+        assert_eq!(report.certs.len(), 2);
+        assert_eq!(report.errors.len(), 1);
+        let app = &report.certs[0];
+        assert_eq!(app.kind, CertKind::AlipayAppPublic);
+        assert_eq!(app.subject, "CN=app");
+        assert_eq!(app.serial_no.len(), 32);
+        assert!(app.days_remaining == 29 || app.days_remaining == 30);
+        assert!(app.problems.is_empty());
+        assert_eq!(report.expiring_within(60).len(), 1);
+        let config = AlipayConfig {
+            app_private_key: other_key,
+            ..config
+        };
+        let report = inspect_alipay(&Payment::new(config)).unwrap();
+        assert_eq!(
+            report.certs[0].problems,
+            vec!["certificate does not match app_private_key".to_string()]
+        );
+        assert!(report.has_problems());
+        for file in files {
+            std::fs::remove_file(file).unwrap();
+        }
+    }
+    #[test]
+    fn test_inspect_wechat() {
+        let (_, merchant_key, apiclient_cert) = write_test_cert("merchant", 10);
+        let (_, apiclient_key, other_cert) = write_test_cert("merchant_other", 10);
+        let files = vec![
+            merchant_key,
+            apiclient_cert.clone(),
+            apiclient_key.clone(),
+            other_cert,
+        ];
+        let config = WechatConfig {
+            apiclient_key,
+            apiclient_cert,
+            ..Default::default()
+        };
+        let report = inspect_wechat(&Payment::new(config)).unwrap();
+        let merchant = report
+            .certs
+            .iter()
+            .find(|cert| cert.kind == CertKind::WechatMerchant)
+            .unwrap();
+        assert_eq!(merchant.serial_no, merchant.serial_no.to_uppercase());
+        assert_eq!(
+            merchant.problems,
+            vec!["certificate does not match apiclient_key".to_string()]
+        );
+        for file in files {
+            std::fs::remove_file(file).unwrap();
+        }
+    }
+    #[test]
+    fn test_days_between() {
+        let now = Asn1Time::from_unix(1_700_000_000).unwrap();
+        let later = Asn1Time::from_unix(1_700_000_000 + 86400 * 3 + 10).unwrap();
+        let earlier = Asn1Time::from_unix(1_700_000_000 - 10).unwrap();
+        assert_eq!(days_between(&now, &later).unwrap(), 3);
+        assert_eq!(days_between(&now, &earlier).unwrap(), -1);
+    }
+}
@@ -4,7 +4,7 @@
 //! 1. 关于签名：微信支付签名规则参考[微信支付签名生成算法](https://pay.weixin.qq.com/wiki/doc/apiv3/wechatpay/wechatpay4_1.shtml)
 //! 2. 关于验签：微信支付验签规则参考[微信支付验签](https://pay.weixin.qq.com/wiki/doc/apiv3/wechatpay/wechatpay4_2.shtml)
 //! 注意微信支付验签时需要平台证书，平台证书下载地址[微信支付平台证书下载](https://pay.weixin.qq.com/wiki/doc/apiv3/wechatpay/wechatpay4_3.shtml)
-//! 目前只能通过 api接口的方式下载平台证书，不支持手动下载，请保持 CARGO_MANIFEST_DIR/payment/certs/download 目录可写系统会每隔12小时自动下载平台证书
+//! 目前只能通过 api接口的方式下载平台证书，不支持手动下载，请保持 CARGO_MANIFEST_DIR/payment/certs/download 目录(可通过 cert_dir 配置)可写系统会每隔12小时自动下载平台证书
 //! 3. 如果无法接收到异步通知请检查是否配置了正确的异步通知地址及设置了APIv3 密钥
 //! # 支付宝签名验签
 //! 1. 关于签名：[支付宝支付签名生成算法](https://opendocs.alipay.com/open-v3/05419m?pathHash=a5cb620e)
@@ -19,12 +19,21 @@
 //! 支付宝根证书：用来验证支付宝消息，包含了根 CA 名称、根 CA 的公钥、证书有效期等内容。
 
 use crate::error::WeaError;
+use crate::notify::NotifyStore;
+use crate::wechat::dict::cert::{MerchantCert, WechatSignType};
 use std::fmt::Debug;
 use std::future::Future;
 use std::pin::Pin;
+use std::sync::Arc;
 
 pub mod alipay;
+pub mod bill;
 pub mod error;
+#[cfg(feature = "axum")]
+pub mod extract;
+pub mod inspect;
+pub mod notify;
+pub mod reconcile;
 pub mod utils;
 pub mod wechat;
 // reqwest 请求 user-agent
@@ -83,6 +92,35 @@ pub struct WechatConfig {
     pub apiclient_cert: String,
     // 异步通知地址
     pub notify_url: String,
+    // 通知时间戳允许的最大偏差(秒),默认300秒
+    pub notify_max_skew: Option<u64>,
+    // 已处理通知存储,用于拒绝重放的通知,为空时不检查
+    pub notify_store: Option<Arc<dyn NotifyStore>>,
+    // 境外(香港)商户模式,启用后请求发送到 apihk.mch.weixin.qq.com
+    pub is_global: Option<bool>,
+    // 签名及加密算法,默认 RSA,国密商户设置为 SM2
+    pub sign_type: Option<WechatSignType>,
+    // APIv2密钥,用于 v2 接口签名,为空时使用 mch_key
+    pub api_v2_key: Option<String>,
+    // 轮换中的其他APIv3密钥,解密时先使用 mch_key,失败后按顺序尝试
+    pub api_v3_keys: Option<Vec<String>>,
+    // 可在运行时替换的商户私钥及证书,设置后优先于 apiclient_key/apiclient_cert
+    pub merchant_cert: Option<MerchantCert>,
+    // 平台证书保存目录,为空时使用 CARGO_MANIFEST_DIR/certs/download
+    pub cert_dir: Option<String>,
+}
+impl WechatConfig {
+    /// 平台证书保存目录,以 / 结尾
+    pub(crate) fn cert_dir(&self) -> String {
+        match &self.cert_dir {
+            Some(cert_dir) => format!("{}/", cert_dir.trim_end_matches('/')),
+            None => format!("{}/certs/download/", env!("CARGO_MANIFEST_DIR")),
+        }
+    }
+    /// APIv2密钥,用于 v2 接口及小程序插件等签名,未配置 api_v2_key 时使用 mch_key
+    pub(crate) fn v2_key(&self) -> &str {
+        self.api_v2_key.as_deref().unwrap_or(&self.mch_key)
+    }
 }
 /// 支付宝支付配置
 /// 支付宝配置分为普通密钥模式和证书模式
@@ -123,6 +161,8 @@ pub struct AlipayConfig {
     pub notify_url: Option<String>,
     // 沙盒模式
     pub is_sandbox: Option<bool>,
+    // 电脑网站、手机网站支付网关请求的字符集,支持 utf-8、gbk,默认 utf-8
+    pub charset: Option<String>,
 }
 
 // 支付配置
@@ -224,6 +264,7 @@ pub mod tests {
             mch_key,
             notify_url,
             is_sandbox,
+            ..Default::default()
         };
         (wechat_cfg, alipay_cfg)
     }
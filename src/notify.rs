@@ -0,0 +1,141 @@
+//! 异步通知的防重放及应答
+//! 微信支付通知会校验 Wechatpay-Timestamp 与当前时间的偏差,默认不超过5分钟
+//! 配置 notify_store 后会记录已处理的通知,相同的通知ID与随机串再次出现时会被拒绝
+use crate::utils::*;
+use crate::{BoxFuture, WeaResult};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Mutex;
+
+/// 通知时间戳默认允许的最大偏差,单位为秒
+pub const DEFAULT_NOTIFY_MAX_SKEW: u64 = 300;
+
+/// 已处理通知存储,多实例部署时可使用 redis 等共享存储实现
+pub trait NotifyStore: Debug + Send + Sync {
+    /// 记录通知,ttl 为记录保留的秒数
+    /// 通知未出现过时返回 true,已出现过返回 false
+    fn check_and_record<'a>(&'a self, key: &'a str, ttl: u64) -> BoxFuture<'a, bool>;
+}
+
+/// 基于内存的已处理通知存储,仅适用于单实例部署
+#[derive(Debug, Default)]
+pub struct MemoryNotifyStore {
+    // 通知key => 过期时间戳
+    seen: Mutex<HashMap<String, u64>>,
+}
+impl MemoryNotifyStore {
+    pub fn new() -> Self {
+        MemoryNotifyStore::default()
+    }
+}
+impl NotifyStore for MemoryNotifyStore {
+    fn check_and_record<'a>(&'a self, key: &'a str, ttl: u64) -> BoxFuture<'a, bool> {
+        Box::pin(async move {
+            let now = get_timestamp()?;
+            let mut seen = self
+                .seen
+                .lock()
+                .map_err(|_e| e("notify store lock error"))?;
+            seen.retain(|_, expire_at| *expire_at > now);
+            if seen.contains_key(key) {
+                return Ok(false);
+            }
+            seen.insert(key.to_string(), now + ttl);
+            Ok(true)
+        })
+    }
+}
+
+/// 校验通知时间戳与当前时间的偏差,timestamp 为秒级时间戳
+pub fn check_timestamp(timestamp: &str, max_skew: u64) -> WeaResult<()> {
+    let timestamp = timestamp
+        .parse::<u64>()
+        .map_err(|_e| e("notify timestamp error"))?;
+    let now = get_timestamp()?;
+    if now.abs_diff(timestamp) > max_skew {
+        return Err(e("notify timestamp expired"));
+    }
+    Ok(())
+}
+
+/// 通知应答,status 为 HTTP 状态码,body 为应答内容
+#[derive(Clone, Debug, PartialEq)]
+pub struct NotifyAck {
+    //HTTP 状态码
+    pub status: u16,
+    //Content-Type
+    pub content_type: &'static str,
+    //应答内容
+    pub body: String,
+}
+impl NotifyAck {
+    /// 微信支付通知处理成功
+    pub fn wechat_success() -> Self {
+        NotifyAck {
+            status: 200,
+            content_type: "application/json",
+            body: r#"{"code":"SUCCESS","message":"成功"}"#.to_string(),
+        }
+    }
+    /// 微信支付通知处理失败,微信支付会按策略重新发送通知
+    pub fn wechat_fail(message: &str) -> Self {
+        let body = serde_json::json!({"code": "FAIL", "message": message});
+        NotifyAck {
+            status: 500,
+            content_type: "application/json",
+            body: body.to_string(),
+        }
+    }
+    /// 支付宝通知处理成功
+    pub fn alipay_success() -> Self {
+        NotifyAck {
+            status: 200,
+            content_type: "text/plain",
+            body: "success".to_string(),
+        }
+    }
+    /// 支付宝通知处理失败,支付宝会按策略重新发送通知
+    pub fn alipay_fail() -> Self {
+        NotifyAck {
+            status: 200,
+            content_type: "text/plain",
+            body: "fail".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[tokio::test]
+    async fn test_memory_notify_store() {
+        let store = MemoryNotifyStore::new();
+        assert!(store.check_and_record("id:nonce", 600).await.unwrap());
+        assert!(!store.check_and_record("id:nonce", 600).await.unwrap());
+        assert!(store.check_and_record("id:nonce2", 600).await.unwrap());
+        // ttl 为0的记录会在下次记录时被清理
+        assert!(store.check_and_record("expired", 0).await.unwrap());
+        assert!(store.check_and_record("expired", 0).await.unwrap());
+    }
+    #[test]
+    fn test_check_timestamp() {
+        let now = get_timestamp().unwrap();
+        assert!(check_timestamp(&now.to_string(), DEFAULT_NOTIFY_MAX_SKEW).is_ok());
+        let expired = (now - DEFAULT_NOTIFY_MAX_SKEW - 1).to_string();
+        assert!(check_timestamp(&expired, DEFAULT_NOTIFY_MAX_SKEW).is_err());
+        assert!(check_timestamp("abc", DEFAULT_NOTIFY_MAX_SKEW).is_err());
+    }
+    #[test]
+    fn test_notify_ack() {
+        assert_eq!(NotifyAck::alipay_success().body, "success");
+        assert_eq!(NotifyAck::alipay_fail().body, "fail");
+        let ack = NotifyAck::wechat_success();
+        assert_eq!(ack.status, 200);
+        let body: serde_json::Value = serde_json::from_str(&ack.body).unwrap();
+        assert_eq!(body["code"], "SUCCESS");
+        let ack = NotifyAck::wechat_fail("验签失败");
+        assert_eq!(ack.status, 500);
+        let body: serde_json::Value = serde_json::from_str(&ack.body).unwrap();
+        assert_eq!(body["code"], "FAIL");
+    }
+}
@@ -0,0 +1,562 @@
+//! 对账,将本地订单、退款与支付渠道账单逐笔核对
+//! 金额单位统一为分,差异分为长款、短款、金额不符、状态不符、手续费不符及退款不符
+//! # Examples
+//! ```rust,ignore
+//! use weapay::reconcile::*;
+//! let report = reconcile_wechat(&payment, &ledger, "2024-07-01").await?;
+//! // 支付宝使用 reconcile_alipay
+//! if !report.is_balanced() {
+//!     std::fs::write("diff.csv", report.to_csv())?;
+//! }
+//! ```
+use crate::alipay::prelude::{AlipayTradeBill, BillTrait as AlipayBillTrait};
+pub use crate::bill::{BillKind as ReconKind, PayChannel};
+use crate::bill::{BillRecord, BillStatus};
+use crate::utils::*;
+use crate::wechat::prelude::{BillTrait, TradeBill, TradeBillType};
+use crate::{AlipayConfig, BoxFuture, Payment, WeaResult, WechatConfig};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// 本地订单,amount 为订单金额
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub struct LedgerOrder {
+    //商户订单号
+    pub out_trade_no: String,
+    //订单金额
+    pub amount: i64,
+    //是否已支付
+    pub paid: bool,
+    //手续费,为空时不核对
+    pub fee: Option<i64>,
+}
+/// 本地退款单
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub struct LedgerRefund {
+    //商户订单号
+    pub out_trade_no: String,
+    //商户退款单号
+    pub out_refund_no: String,
+    //退款金额
+    pub amount: i64,
+    //是否已退款成功
+    pub success: bool,
+}
+/// 本地账本,对账时按账单日期读取订单及退款
+pub trait Ledger: Send + Sync {
+    /// 账单日期内的订单
+    fn orders<'a>(&'a self, bill_date: &'a str) -> BoxFuture<'a, Vec<LedgerOrder>>;
+    /// 账单日期内的退款
+    fn refunds<'a>(&'a self, bill_date: &'a str) -> BoxFuture<'a, Vec<LedgerRefund>>;
+}
+/// 渠道账单中参与对账的一笔记录
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub struct ReconBillItem {
+    //支付渠道
+    pub channel: PayChannel,
+    //业务类型
+    pub kind: ReconKind,
+    //商户订单号
+    pub out_trade_no: String,
+    //渠道交易号
+    pub trade_no: String,
+    //商户退款单号,支付记录为空
+    pub out_refund_no: String,
+    //金额,支付为订单金额,退款为退款金额
+    pub amount: i64,
+    //渠道状态
+    pub status: String,
+    //是否成功
+    pub success: bool,
+    //手续费,正数为商户支出
+    pub fee: i64,
+}
+impl ReconBillItem {
+    /// 将微信支付交易账单转换为对账记录
+    pub fn from_wechat(bill: &TradeBill) -> Vec<ReconBillItem> {
+        BillRecord::from_wechat(bill)
+            .iter()
+            .map(ReconBillItem::from)
+            .collect()
+    }
+    /// 将支付宝交易账单转换为对账记录
+    pub fn from_alipay(bill: &AlipayTradeBill) -> Vec<ReconBillItem> {
+        BillRecord::from_alipay(bill)
+            .iter()
+            .map(ReconBillItem::from)
+            .collect()
+    }
+}
+impl From<&BillRecord> for ReconBillItem {
+    fn from(record: &BillRecord) -> Self {
+        ReconBillItem {
+            channel: record.channel.clone(),
+            kind: record.kind.clone(),
+            out_trade_no: record.out_trade_no.clone(),
+            trade_no: record.trade_no.clone(),
+            out_refund_no: record.out_refund_no.clone(),
+            amount: record.amount,
+            status: record.raw_status.clone(),
+            success: record.status == BillStatus::Success,
+            fee: record.fee,
+        }
+    }
+}
+/// 差异类型
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DiffType {
+    //长款,渠道有记录本地没有
+    Long,
+    //短款,本地有记录渠道没有
+    Short,
+    //订单金额不符
+    AmountMismatch,
+    //状态不符
+    StatusMismatch,
+    //手续费不符
+    FeeMismatch,
+    //退款金额不符
+    RefundMismatch,
+}
+impl DiffType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiffType::Long => "LONG",
+            DiffType::Short => "SHORT",
+            DiffType::AmountMismatch => "AMOUNT_MISMATCH",
+            DiffType::StatusMismatch => "STATUS_MISMATCH",
+            DiffType::FeeMismatch => "FEE_MISMATCH",
+            DiffType::RefundMismatch => "REFUND_MISMATCH",
+        }
+    }
+}
+/// 一笔对账差异
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ReconDiff {
+    //差异类型
+    pub diff_type: DiffType,
+    //业务类型
+    pub kind: ReconKind,
+    //商户订单号
+    pub out_trade_no: String,
+    //商户退款单号
+    pub out_refund_no: String,
+    //本地金额,长款时为空
+    pub local_amount: Option<i64>,
+    //账单金额,短款时为空
+    pub bill_amount: Option<i64>,
+    //渠道状态
+    pub bill_status: String,
+    //说明
+    pub message: String,
+}
+/// 对账结果
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub struct ReconReport {
+    //账单日期
+    pub bill_date: String,
+    //核对一致的笔数
+    pub matched_count: usize,
+    //差异明细
+    pub diffs: Vec<ReconDiff>,
+}
+impl ReconReport {
+    /// 是否无差异
+    pub fn is_balanced(&self) -> bool {
+        self.diffs.is_empty()
+    }
+    /// 按差异类型筛选
+    pub fn diffs_of(&self, diff_type: DiffType) -> Vec<&ReconDiff> {
+        self.diffs
+            .iter()
+            .filter(|diff| diff.diff_type == diff_type)
+            .collect()
+    }
+    /// 导出为 JSON
+    pub fn to_json(&self) -> WeaResult<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+    /// 导出差异明细为 CSV,金额单位为分
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "差异类型,业务类型,商户订单号,商户退款单号,本地金额,账单金额,账单状态,说明\n",
+        );
+        for diff in self.diffs.iter() {
+            let amount = |amount: Option<i64>| amount.map(|v| v.to_string()).unwrap_or_default();
+            let row = [
+                diff.diff_type.as_str().to_string(),
+                diff.kind.as_str().to_string(),
+                diff.out_trade_no.clone(),
+                diff.out_refund_no.clone(),
+                amount(diff.local_amount),
+                amount(diff.bill_amount),
+                diff.bill_status.clone(),
+                diff.message.clone(),
+            ];
+            let row = row.iter().map(|value| csv_field(value)).collect::<Vec<_>>();
+            csv.push_str(&row.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+/// 核对本地订单、退款与渠道账单
+pub fn reconcile(
+    bill_date: &str,
+    orders: &[LedgerOrder],
+    refunds: &[LedgerRefund],
+    items: &[ReconBillItem],
+) -> ReconReport {
+    let mut report = ReconReport {
+        bill_date: bill_date.to_string(),
+        ..Default::default()
+    };
+    let orders_map: HashMap<&str, &LedgerOrder> = orders
+        .iter()
+        .map(|order| (order.out_trade_no.as_str(), order))
+        .collect();
+    let refunds_map: HashMap<&str, &LedgerRefund> = refunds
+        .iter()
+        .map(|refund| (refund.out_refund_no.as_str(), refund))
+        .collect();
+    let mut seen_orders = HashSet::new();
+    let mut seen_refunds = HashSet::new();
+    for item in items.iter() {
+        let diff = |diff_type: DiffType, local_amount: Option<i64>, message: String| ReconDiff {
+            diff_type,
+            kind: item.kind.clone(),
+            out_trade_no: item.out_trade_no.clone(),
+            out_refund_no: item.out_refund_no.clone(),
+            local_amount,
+            bill_amount: Some(item.amount),
+            bill_status: item.status.clone(),
+            message,
+        };
+        let diffs_before = report.diffs.len();
+        match item.kind {
+            ReconKind::Payment => {
+                let Some(order) = orders_map.get(item.out_trade_no.as_str()) else {
+                    if item.success {
+                        report
+                            .diffs
+                            .push(diff(DiffType::Long, None, "本地无此订单".to_string()));
+                    }
+                    continue;
+                };
+                seen_orders.insert(order.out_trade_no.as_str());
+                if order.paid != item.success {
+                    report.diffs.push(diff(
+                        DiffType::StatusMismatch,
+                        Some(order.amount),
+                        format!("本地支付状态 {}", order.paid),
+                    ));
+                }
+                if order.amount != item.amount {
+                    report.diffs.push(diff(
+                        DiffType::AmountMismatch,
+                        Some(order.amount),
+                        "订单金额不符".to_string(),
+                    ));
+                }
+                if let Some(fee) = order.fee {
+                    if fee != item.fee {
+                        report.diffs.push(diff(
+                            DiffType::FeeMismatch,
+                            Some(order.amount),
+                            format!("本地手续费 {} 账单手续费 {}", fee, item.fee),
+                        ));
+                    }
+                }
+            }
+            ReconKind::Refund => {
+                let Some(refund) = refunds_map.get(item.out_refund_no.as_str()) else {
+                    if item.success {
+                        report
+                            .diffs
+                            .push(diff(DiffType::Long, None, "本地无此退款".to_string()));
+                    }
+                    continue;
+                };
+                seen_refunds.insert(refund.out_refund_no.as_str());
+                if refund.success != item.success {
+                    report.diffs.push(diff(
+                        DiffType::StatusMismatch,
+                        Some(refund.amount),
+                        format!("本地退款状态 {}", refund.success),
+                    ));
+                }
+                if refund.amount != item.amount {
+                    report.diffs.push(diff(
+                        DiffType::RefundMismatch,
+                        Some(refund.amount),
+                        "退款金额不符".to_string(),
+                    ));
+                }
+            }
+        }
+        if report.diffs.len() == diffs_before {
+            report.matched_count += 1;
+        }
+    }
+    for order in orders.iter() {
+        if order.paid && !seen_orders.contains(order.out_trade_no.as_str()) {
+            report.diffs.push(ReconDiff {
+                diff_type: DiffType::Short,
+                kind: ReconKind::Payment,
+                out_trade_no: order.out_trade_no.clone(),
+                out_refund_no: String::new(),
+                local_amount: Some(order.amount),
+                bill_amount: None,
+                bill_status: String::new(),
+                message: "账单无此订单".to_string(),
+            });
+        }
+    }
+    for refund in refunds.iter() {
+        if refund.success && !seen_refunds.contains(refund.out_refund_no.as_str()) {
+            report.diffs.push(ReconDiff {
+                diff_type: DiffType::Short,
+                kind: ReconKind::Refund,
+                out_trade_no: refund.out_trade_no.clone(),
+                out_refund_no: refund.out_refund_no.clone(),
+                local_amount: Some(refund.amount),
+                bill_amount: None,
+                bill_status: String::new(),
+                message: "账单无此退款".to_string(),
+            });
+        }
+    }
+    report
+}
+/// 从本地账本读取订单及退款后与渠道账单核对
+pub fn reconcile_ledger<'a, L: Ledger>(
+    ledger: &'a L,
+    bill_date: &'a str,
+    items: Vec<ReconBillItem>,
+) -> BoxFuture<'a, ReconReport> {
+    Box::pin(async move {
+        let orders = ledger.orders(bill_date).await?;
+        let refunds = ledger.refunds(bill_date).await?;
+        Ok(reconcile(bill_date, &orders, &refunds, &items))
+    })
+}
+/// 下载微信支付全部交易账单并与本地账本核对
+pub fn reconcile_wechat<'a, L: Ledger>(
+    payment: &'a Payment<WechatConfig>,
+    ledger: &'a L,
+    bill_date: &'a str,
+) -> BoxFuture<'a, ReconReport> {
+    Box::pin(async move {
+        let bill = payment
+            .download_trade_bill(bill_date.to_string(), TradeBillType::ALL, false)
+            .await?;
+        let items = ReconBillItem::from_wechat(&bill);
+        reconcile_ledger(ledger, bill_date, items).await
+    })
+}
+/// 下载支付宝交易账单并与本地账本核对
+pub fn reconcile_alipay<'a, L: Ledger>(
+    payment: &'a Payment<AlipayConfig>,
+    ledger: &'a L,
+    bill_date: &'a str,
+) -> BoxFuture<'a, ReconReport> {
+    Box::pin(async move {
+        let bill = payment.download_trade_bill(bill_date.to_string()).await?;
+        let items = ReconBillItem::from_alipay(&bill);
+        reconcile_ledger(ledger, bill_date, items).await
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wechat::prelude::TradeBillRecord;
+
+    fn order(out_trade_no: &str, amount: i64, fee: Option<i64>) -> LedgerOrder {
+        LedgerOrder {
+            out_trade_no: out_trade_no.to_string(),
+            amount,
+            paid: true,
+            fee,
+        }
+    }
+    fn payment_item(out_trade_no: &str, amount: i64, fee: i64) -> ReconBillItem {
+        ReconBillItem {
+            out_trade_no: out_trade_no.to_string(),
+            amount,
+            status: "SUCCESS".to_string(),
+            success: true,
+            fee,
+            ..Default::default()
+        }
+    }
+    #[test]
+    fn test_reconcile() {
+        let orders = vec![
+            order("T001", 100, Some(1)),
+            order("T002", 200, None),
+            order("T003", 300, Some(2)),
+            order("T004", 400, None),
+            LedgerOrder {
+                paid: false,
+                ..order("T005", 500, None)
+            },
+        ];
+        let refunds = vec![
+            LedgerRefund {
+                out_trade_no: "T001".to_string(),
+                out_refund_no: "R001".to_string(),
+                amount: 50,
+                success: true,
+            },
+            LedgerRefund {
+                out_trade_no: "T002".to_string(),
+                out_refund_no: "R002".to_string(),
+                amount: 20,
+                success: true,
+            },
+        ];
+        let items = vec![
+            payment_item("T001", 100, 1),
+            payment_item("T002", 210, 1),
+            payment_item("T003", 300, 3),
+            payment_item("T005", 500, 3),
+            payment_item("T006", 600, 4),
+            ReconBillItem {
+                kind: ReconKind::Refund,
+                out_refund_no: "R001".to_string(),
+                status: "SUCCESS".to_string(),
+                success: true,
+                amount: 40,
+                ..Default::default()
+            },
+        ];
+        let report = reconcile("2024-07-01", &orders, &refunds, &items);
+        assert!(!report.is_balanced());
+        assert_eq!(report.matched_count, 1);
+        let types = report
+            .diffs
+            .iter()
+            .map(|diff| (diff.diff_type.as_str(), diff.out_trade_no.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            types,
+            vec![
+                ("AMOUNT_MISMATCH", "T002"),
+                ("FEE_MISMATCH", "T003"),
+                ("STATUS_MISMATCH", "T005"),
+                ("LONG", "T006"),
+                ("REFUND_MISMATCH", ""),
+                ("SHORT", "T004"),
+                ("SHORT", "T002"),
+            ]
+        );
+        assert_eq!(report.diffs_of(DiffType::Short)[1].out_refund_no, "R002");
+    }
+    #[test]
+    fn test_report_export() {
+        let report = ReconReport {
+            bill_date: "2024-07-01".to_string(),
+            matched_count: 0,
+            diffs: vec![ReconDiff {
+                diff_type: DiffType::Long,
+                kind: ReconKind::Payment,
+                out_trade_no: "T001".to_string(),
+                out_refund_no: String::new(),
+                local_amount: None,
+                bill_amount: Some(100),
+                bill_status: "SUCCESS".to_string(),
+                message: "本地无此订单,请核实".to_string(),
+            }],
+        };
+        let csv = report.to_csv();
+        let row = csv.lines().nth(1).unwrap();
+        assert_eq!(
+            row,
+            "LONG,PAYMENT,T001,,,100,SUCCESS,\"本地无此订单,请核实\""
+        );
+        let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+        assert_eq!(json["diffs"][0]["diff_type"], "LONG");
+    }
+    #[test]
+    fn test_from_wechat() {
+        let bill = TradeBill {
+            records: vec![
+                TradeBillRecord {
+                    out_trade_no: "T001".to_string(),
+                    trade_state: "SUCCESS".to_string(),
+                    total_amount: 100,
+                    fee: 1,
+                    ..Default::default()
+                },
+                TradeBillRecord {
+                    out_trade_no: "T001".to_string(),
+                    trade_state: "REFUND".to_string(),
+                    out_refund_no: "R001".to_string(),
+                    refund_amount: 100,
+                    refund_status: "SUCCESS".to_string(),
+                    fee: -1,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let items = ReconBillItem::from_wechat(&bill);
+        assert_eq!(items[0].kind, ReconKind::Payment);
+        assert!(items[0].success);
+        assert_eq!(items[1].kind, ReconKind::Refund);
+        assert_eq!(items[1].amount, 100);
+        assert_eq!(items[1].out_refund_no, "R001");
+    }
+    #[test]
+    fn test_from_alipay() {
+        use crate::alipay::prelude::TradeBillDetail;
+        let bill = AlipayTradeBill {
+            details: vec![
+                TradeBillDetail {
+                    out_trade_no: "T001".to_string(),
+                    business_type: "交易".to_string(),
+                    total_amount: 1000,
+                    service_fee: -6,
+                    ..Default::default()
+                },
+                TradeBillDetail {
+                    out_trade_no: "T001".to_string(),
+                    business_type: "退款".to_string(),
+                    out_request_no: "R001".to_string(),
+                    total_amount: -200,
+                    service_fee: 1,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let items = ReconBillItem::from_alipay(&bill);
+        assert_eq!(items[0].kind, ReconKind::Payment);
+        assert_eq!(items[0].fee, 6);
+        assert_eq!(items[1].kind, ReconKind::Refund);
+        assert_eq!(items[1].amount, 200);
+        assert_eq!(items[1].out_refund_no, "R001");
+    }
+    struct MemoryLedger(Vec<LedgerOrder>);
+    impl Ledger for MemoryLedger {
+        fn orders<'a>(&'a self, _bill_date: &'a str) -> BoxFuture<'a, Vec<LedgerOrder>> {
+            Box::pin(async move { Ok(self.0.clone()) })
+        }
+        fn refunds<'a>(&'a self, _bill_date: &'a str) -> BoxFuture<'a, Vec<LedgerRefund>> {
+            Box::pin(async move { Ok(Vec::new()) })
+        }
+    }
+    #[tokio::test]
+    async fn test_reconcile_ledger() {
+        let ledger = MemoryLedger(vec![order("T001", 100, None)]);
+        let items = vec![payment_item("T001", 100, 0)];
+        let report = reconcile_ledger(&ledger, "2024-07-01", items)
+            .await
+            .unwrap();
+        assert!(report.is_balanced());
+        assert_eq!(report.matched_count, 1);
+    }
+}
@@ -1,43 +1,141 @@
 use crate::error::WeaError;
 use crate::WeaResult;
+use aes_gcm::{
+    aead::{Aead, Payload},
+    Aes256Gcm, KeyInit, Nonce,
+};
+use flate2::read::GzDecoder;
 use openssl::{
     base64::{decode_block, encode_block},
+    bn::{BigNum, BigNumContext},
+    ec::EcGroup,
     hash::{hash, MessageDigest},
+    memcmp,
     //OpenSSLString,
     nid::Nid,
-    pkey::PKey,
+    pkey::{HasPublic, PKey, PKeyRef, Private, Public},
+    pkey_ctx::PkeyCtx,
     rand::rand_bytes,
-    rsa::Rsa,
-    sign::Signer,
+    rsa::{Padding, Rsa},
+    sign::{Signer, Verifier},
+    symm::{Cipher, Crypter, Mode},
     x509::X509,
 };
+use reqwest::Url;
+use std::io::Read;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use std::{fs, path::Path};
 
-/// 生成签名 data: vec!['GET', 'https://xxx', '1395712654', 'nonce_str', 'body']
-/// private_key: 商户私钥,支付宝提供的私钥可能没有 begin-- end 手动加上。注意两端不要有空格
-pub(crate) fn generate_signature(data: Vec<&str>, private_key: &str) -> WeaResult<String> {
-    let data = data.join("\n");
-    let data = data + "\n";
+/// 读取私钥文件,支持 PEM 格式及 base64 编码的 DER 格式
+pub(crate) fn read_private_key(private_key: &str) -> WeaResult<Rsa<Private>> {
     let private_key_content = std::fs::read_to_string(private_key)?;
     //private_key_content = prepair_cert(private_key_content, true);
     //print!("RSA PRIVATE, {}", private_key_content);
-    let pkey = if private_key_content.contains("-----BEGIN") {
-        let rsa = Rsa::private_key_from_pem(&private_key_content.as_bytes())?;
-        PKey::from_rsa(rsa)?
+    let rsa = if private_key_content.contains("-----BEGIN") {
+        Rsa::private_key_from_pem(private_key_content.as_bytes())?
     } else {
         let private_u8 = decode_block(&private_key_content)?;
-        let rsa = Rsa::private_key_from_der(private_u8.as_slice())?;
-        PKey::from_rsa(rsa)?
+        Rsa::private_key_from_der(private_u8.as_slice())?
     };
+    Ok(rsa)
+}
+/// 生成签名 data: vec!['GET', 'https://xxx', '1395712654', 'nonce_str', 'body']
+/// private_key: 商户私钥,支付宝提供的私钥可能没有 begin-- end 手动加上。注意两端不要有空格
+pub(crate) fn generate_signature(data: Vec<&str>, private_key: &str) -> WeaResult<String> {
+    let data = data.join("\n");
+    let data = data + "\n";
+    rsa_sha256_sign(data.as_bytes(), private_key)
+}
+/// 对原文直接进行 SHA256withRSA 签名并返回 base64 编码的签名,用于支付宝网关接口
+pub(crate) fn rsa_sha256_sign(data: &[u8], private_key: &str) -> WeaResult<String> {
+    let pkey = PKey::from_rsa(read_private_key(private_key)?)?;
 
     let mut signer = Signer::new(MessageDigest::sha256(), &pkey)?;
     //signer.set_rsa_padding(Padding::PKCS1).unwrap();
-    signer.update(data.as_bytes())?;
+    signer.update(data)?;
     let sign = signer.sign_to_vec()?;
 
     Ok(encode_block(&sign))
 }
+/// 读取私钥文件,不限定密钥类型,支持 PEM 格式及 base64 编码的 DER 格式
+pub(crate) fn read_pkey(private_key: &str) -> WeaResult<PKey<Private>> {
+    let private_key_content = std::fs::read_to_string(private_key)?;
+    let pkey = if private_key_content.contains("-----BEGIN") {
+        PKey::private_key_from_pem(private_key_content.as_bytes())?
+    } else {
+        let private_u8 = decode_block(&private_key_content)?;
+        PKey::private_key_from_der(private_u8.as_slice())?
+    };
+    Ok(pkey)
+}
+/// SM2 曲线 OID 1.2.156.10197.1.301 的 DER 编码
+const SM2_CURVE_OID: [u8; 10] = [0x06, 0x08, 0x2a, 0x81, 0x1c, 0xcf, 0x55, 0x01, 0x82, 0x2d];
+/// SM2 签名使用的默认用户ID
+const SM2_USER_ID: &[u8] = b"1234567812345678";
+/// 判断是否为 SM2 密钥
+pub(crate) fn is_sm2_key<T: HasPublic>(pkey: &PKeyRef<T>) -> WeaResult<bool> {
+    let der = pkey.public_key_to_der()?;
+    Ok(der
+        .windows(SM2_CURVE_OID.len())
+        .any(|item| item == SM2_CURVE_OID))
+}
+/// 计算 SM2 签名的摘要 e = SM3(ZA || M)
+/// openssl 未提供设置用户ID的接口,这里按默认用户ID自行计算 ZA
+fn sm2_digest<T: HasPublic>(pkey: &PKeyRef<T>, data: &[u8]) -> WeaResult<Vec<u8>> {
+    let group = EcGroup::from_curve_name(Nid::SM2)?;
+    let mut ctx = BigNumContext::new()?;
+    let (mut p, mut a, mut b) = (BigNum::new()?, BigNum::new()?, BigNum::new()?);
+    group.components_gfp(&mut p, &mut a, &mut b, &mut ctx)?;
+    let (mut x, mut y) = (BigNum::new()?, BigNum::new()?);
+    group
+        .generator_opt()
+        .ok_or(e("sm2 generator not found"))?
+        .affine_coordinates(&group, &mut x, &mut y, &mut ctx)?;
+    // 公钥 DER 末尾64字节为未压缩公钥的 x、y 坐标
+    let public_key = pkey.public_key_to_der()?;
+    let mut za_data = ((SM2_USER_ID.len() * 8) as u16).to_be_bytes().to_vec();
+    za_data.extend_from_slice(SM2_USER_ID);
+    for item in [&a, &b, &x, &y] {
+        za_data.extend(item.to_vec_padded(32)?);
+    }
+    za_data.extend_from_slice(&public_key[public_key.len() - 64..]);
+    let mut digest_data = hash(MessageDigest::sm3(), &za_data)?.to_vec();
+    digest_data.extend_from_slice(data);
+    Ok(hash(MessageDigest::sm3(), &digest_data)?.to_vec())
+}
+/// 国密 SM2 签名,摘要算法为 SM3,用户ID为默认的 1234567812345678
+/// data 与 generate_signature 相同,private_key 为 SM2 私钥文件路径
+pub(crate) fn generate_sm2_signature(data: Vec<&str>, private_key: &str) -> WeaResult<String> {
+    let data = data.join("\n");
+    let data = data + "\n";
+    let pkey = read_pkey(private_key)?;
+    if !is_sm2_key(&pkey)? {
+        return Err(e("private key is not a SM2 key"));
+    }
+    let digest = sm2_digest(&pkey, data.as_bytes())?;
+    let mut ctx = PkeyCtx::new(&pkey)?;
+    ctx.sign_init()?;
+    let mut sign = vec![];
+    ctx.sign_to_vec(&digest, &mut sign)?;
+    Ok(encode_block(&sign))
+}
+/// 验证签名,根据公钥类型选择算法,SM2 公钥使用 SM2-WITH-SM3,其他使用 SHA256-RSA
+pub(crate) fn verify_with_public_key(
+    data: &[u8],
+    signature: &str,
+    pkey: &PKey<Public>,
+) -> WeaResult<bool> {
+    let sign_u8 = decode_block(signature)?;
+    if is_sm2_key(pkey)? {
+        let digest = sm2_digest(pkey, data)?;
+        let mut ctx = PkeyCtx::new(pkey)?;
+        ctx.verify_init()?;
+        return Ok(ctx.verify(&digest, &sign_u8).unwrap_or(false));
+    }
+    let mut verifier = Verifier::new(MessageDigest::sha256(), pkey)?;
+    verifier.update(data)?;
+    Ok(verifier.verify(&sign_u8)?)
+}
 // generate a random string
 pub(crate) fn generate_random_string(len: usize) -> String {
     let num_bytes = (len + 1) / 2;
@@ -68,6 +166,30 @@ pub(crate) fn get_timestamp_millis() -> WeaResult<u128> {
     let timestamp_millis = since_the_epoch.as_millis();
     Ok(timestamp_millis)
 }
+/// 格式化为北京时间 yyyy-MM-dd HH:mm:ss
+pub(crate) fn beijing_time(timestamp: u64) -> String {
+    let secs = timestamp + 8 * 3600;
+    let (days, rem) = ((secs / 86400) as i64, secs % 86400);
+    // 由 1970-01-01 起的天数换算公历日期
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
 // short for payerror
 pub(crate) fn e(message: &str) -> WeaError {
     WeaError::new("", message.to_string())
@@ -138,10 +260,221 @@ pub(crate) fn get_root_cert_sn(cert_content: &str) -> Result<String, WeaError> {
         .join("_");
     Ok(root_cert_sn)
 }
-/// list all wechat platform certs,if sn is not none,return only return
+/// 使用平台证书公钥对敏感信息加密,RSA/ECB/OAEPWithSHA-1AndMGF1Padding
+/// cert: 平台证书文件路径
+pub(crate) fn rsa_encrypt_oaep(data: &str, cert: &str) -> WeaResult<String> {
+    let cert_content = fs::read(cert)?;
+    let cert = X509::from_pem(&cert_content)?;
+    let rsa = cert.public_key()?.rsa()?;
+    let mut buf = vec![0u8; rsa.size() as usize];
+    let len = rsa.public_encrypt(data.as_bytes(), &mut buf, Padding::PKCS1_OAEP)?;
+    Ok(encode_block(&buf[..len]))
+}
+/// 计算摘要并返回小写十六进制字符串,hash_type 支持 MD5、SHA1、SHA256、SM3
+pub(crate) fn hex_digest(hash_type: &str, data: &[u8]) -> WeaResult<String> {
+    let result = hash(message_digest(hash_type)?, data)?;
+    Ok(to_hex(&result))
+}
+/// 根据 hash_type 获取摘要算法,支持 MD5、SHA1、SHA256、SM3
+pub(crate) fn message_digest(hash_type: &str) -> WeaResult<MessageDigest> {
+    match hash_type.to_uppercase().as_str() {
+        "MD5" => Ok(MessageDigest::md5()),
+        "SHA1" => Ok(MessageDigest::sha1()),
+        "SHA256" => Ok(MessageDigest::sha256()),
+        "SM3" => Ok(MessageDigest::sm3()),
+        _ => Err(e(&format!("unsupported hash type {}", hash_type))),
+    }
+}
+/// 转换为小写十六进制字符串
+pub(crate) fn to_hex(data: &[u8]) -> String {
+    data.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+/// HMAC-SHA256 签名并返回大写十六进制字符串,用于使用商户支付密钥(APIv2密钥)的签名
+pub(crate) fn hmac_sha256(key: &str, data: &str) -> WeaResult<String> {
+    let pkey = PKey::hmac(key.as_bytes())?;
+    let mut signer = Signer::new(MessageDigest::sha256(), &pkey)?;
+    signer.update(data.as_bytes())?;
+    Ok(to_hex(&signer.sign_to_vec()?).to_uppercase())
+}
+/// 校验摘要,不一致时返回 HashMismatch 错误
+pub(crate) fn check_digest(digest: &str, hash_value: &str) -> WeaResult<()> {
+    if !digest.eq_ignore_ascii_case(hash_value) {
+        return Err(WeaError::hash_mismatch(hash_value, digest));
+    }
+    Ok(())
+}
+/// 拼接并编码查询参数,返回 path?query
+/// 加密后的 base64 串、带时区的时间等含有 + / = 的值需要编码
+pub(crate) fn with_query(path: &str, params: &[(&str, &str)]) -> WeaResult<String> {
+    let mut url = Url::parse("https://api.mch.weixin.qq.com/")
+        .and_then(|base| base.join(path))
+        .map_err(|_e| e("parse url error"))?;
+    url.query_pairs_mut().extend_pairs(params);
+    Ok(format!(
+        "{}?{}",
+        url.path(),
+        url.query().unwrap_or_default()
+    ))
+}
+/// 解压 gzip 数据
+pub(crate) fn gunzip(data: &[u8]) -> WeaResult<Vec<u8>> {
+    let mut decoder = GzDecoder::new(data);
+    let mut content = Vec::new();
+    decoder.read_to_end(&mut content)?;
+    Ok(content)
+}
+/// 将以元为单位的金额字符串转换为分,超过两位的小数四舍五入
+/// 空字符串视为0
+pub(crate) fn yuan_to_fen(amount: &str) -> WeaResult<i64> {
+    let amount = amount.trim();
+    if amount.is_empty() {
+        return Ok(0);
+    }
+    let err = || e(&format!("invalid amount {}", amount));
+    let (negative, amount) = match amount.strip_prefix('-') {
+        Some(amount) => (true, amount),
+        None => (false, amount),
+    };
+    let (int_part, frac_part) = amount.split_once('.').unwrap_or((amount, ""));
+    if int_part.is_empty() && frac_part.is_empty() {
+        return Err(err());
+    }
+    let digits_only = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if !digits_only(int_part) || !digits_only(frac_part) {
+        return Err(err());
+    }
+    let yuan = if int_part.is_empty() {
+        0
+    } else {
+        int_part.parse::<i64>().map_err(|_e| err())?
+    };
+    let frac = frac_part.as_bytes();
+    let digit = |i: usize| frac.get(i).map(|b| (b - b'0') as i64).unwrap_or(0);
+    let mut fen = yuan * 100 + digit(0) * 10 + digit(1);
+    if digit(2) >= 5 {
+        fen += 1;
+    }
+    Ok(if negative { -fen } else { fen })
+}
+/// 使用商户私钥解密,RSA/ECB/OAEPWithSHA-1AndMGF1Padding
+/// ciphertext: base64 编码的密文,private_key: 商户私钥文件路径
+pub(crate) fn rsa_decrypt_oaep(ciphertext: &str, private_key: &str) -> WeaResult<Vec<u8>> {
+    let rsa = read_private_key(private_key)?;
+    let ciphertext = decode_block(ciphertext)?;
+    let mut buf = vec![0u8; rsa.size() as usize];
+    let len = rsa.private_decrypt(&ciphertext, &mut buf, Padding::PKCS1_OAEP)?;
+    buf.truncate(len);
+    Ok(buf)
+}
+/// AEAD_AES_256_GCM 解密,ciphertext 末尾16字节为认证标签
+pub(crate) fn aes_gcm_decrypt(
+    key: &[u8],
+    nonce: &[u8],
+    associated_data: &[u8],
+    ciphertext: &[u8],
+) -> WeaResult<Vec<u8>> {
+    if nonce.len() != 12 {
+        return Err(e("nonce length error"));
+    }
+    let cipher =
+        Aes256Gcm::new_from_slice(key).map_err(|error| e(&format!("cipher error:{}", error)))?;
+    let payload = Payload {
+        msg: ciphertext,
+        aad: associated_data,
+    };
+    Ok(cipher.decrypt(Nonce::from_slice(nonce), payload)?)
+}
+/// AEAD_SM4_GCM 解密,ciphertext 末尾16字节为认证标签
+/// key 为 16 字节的 SM4 密钥,可通过 sm4_key 由 APIv3 密钥派生
+pub(crate) fn sm4_gcm_decrypt(
+    key: &[u8],
+    nonce: &[u8],
+    associated_data: &[u8],
+    ciphertext: &[u8],
+) -> WeaResult<Vec<u8>> {
+    if ciphertext.len() < 16 {
+        return Err(e("ciphertext length error"));
+    }
+    if nonce.len() != 12 {
+        return Err(e("nonce length error"));
+    }
+    // OpenSSL 3.2 之前没有 SM4-GCM,这里基于 SM4-ECB 实现 GCM
+    let (ciphertext, tag) = ciphertext.split_at(ciphertext.len() - 16);
+    let h = u128::from_be_bytes(sm4_encrypt_block(key, &[0u8; 16])?);
+    let mut j0 = [0u8; 16];
+    j0[..12].copy_from_slice(nonce);
+    j0[15] = 1;
+    let mut expected_tag = ghash(h, associated_data, ciphertext);
+    expected_tag ^= u128::from_be_bytes(sm4_encrypt_block(key, &j0)?);
+    if !memcmp::eq(&expected_tag.to_be_bytes(), tag) {
+        return Err(e("sm4 gcm tag verify error"));
+    }
+    let mut counter = u32::from_be_bytes([j0[12], j0[13], j0[14], j0[15]]);
+    let mut plain = Vec::with_capacity(ciphertext.len());
+    for chunk in ciphertext.chunks(16) {
+        counter = counter.wrapping_add(1);
+        j0[12..].copy_from_slice(&counter.to_be_bytes());
+        let stream = sm4_encrypt_block(key, &j0)?;
+        plain.extend(chunk.iter().zip(stream.iter()).map(|(c, s)| c ^ s));
+    }
+    Ok(plain)
+}
+/// SM4 加密单个分组
+fn sm4_encrypt_block(key: &[u8], block: &[u8; 16]) -> WeaResult<[u8; 16]> {
+    let mut crypter = Crypter::new(Cipher::sm4_ecb(), Mode::Encrypt, key, None)?;
+    crypter.pad(false);
+    let mut out = [0u8; 32];
+    let len = crypter.update(block, &mut out)?;
+    let len = len + crypter.finalize(&mut out[len..])?;
+    if len != 16 {
+        return Err(e("sm4 encrypt error"));
+    }
+    let mut result = [0u8; 16];
+    result.copy_from_slice(&out[..16]);
+    Ok(result)
+}
+/// GCM 的 GHASH 计算
+fn ghash(h: u128, associated_data: &[u8], ciphertext: &[u8]) -> u128 {
+    let mut y = 0u128;
+    for data in [associated_data, ciphertext] {
+        for chunk in data.chunks(16) {
+            let mut block = [0u8; 16];
+            block[..chunk.len()].copy_from_slice(chunk);
+            y = gf128_mul(y ^ u128::from_be_bytes(block), h);
+        }
+    }
+    let len_block = ((associated_data.len() as u128 * 8) << 64) | (ciphertext.len() as u128 * 8);
+    gf128_mul(y ^ len_block, h)
+}
+/// GF(2^128) 乘法
+fn gf128_mul(x: u128, y: u128) -> u128 {
+    let r = 0xe1u128 << 120;
+    let mut z = 0u128;
+    let mut v = y;
+    for i in 0..128 {
+        if (x >> (127 - i)) & 1 == 1 {
+            z ^= v;
+        }
+        v = if v & 1 == 1 { (v >> 1) ^ r } else { v >> 1 };
+    }
+    z
+}
+/// 由 APIv3 密钥派生 SM4 密钥,取 APIv3 密钥 SM3 摘要的前16字节
+pub(crate) fn sm4_key(api_v3_key: &str) -> WeaResult<Vec<u8>> {
+    let digest = hash(MessageDigest::sm3(), api_v3_key.as_bytes())?;
+    Ok(digest[..16].to_vec())
+}
+/// CSV 字段转义,包含逗号、引号或换行时使用双引号包裹
+pub(crate) fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+/// list all wechat platform certs in cert_dir,if sn is not none,return only return
 /// if sn is not none,return only return the match cert
-pub(crate) fn list_wechat_certs(sn: &str) -> WeaResult<Option<String>> {
-    let cert_dir = format!("{}/certs/download/", env!("CARGO_MANIFEST_DIR"));
+pub(crate) fn list_wechat_certs(cert_dir: &str, sn: &str) -> WeaResult<Option<String>> {
     //println!("cert_dir==={}", cert_dir);
     if !Path::exists(Path::new(&cert_dir)) {
         return Ok(None);
@@ -158,11 +491,152 @@ pub(crate) fn list_wechat_certs(sn: &str) -> WeaResult<Option<String>> {
     }
     Ok(None)
 }
+/// 列出 cert_dir 中保存的全部微信支付平台证书文件,不检查是否过期
+pub(crate) fn wechat_cert_files(cert_dir: &str) -> WeaResult<Vec<String>> {
+    if !Path::exists(Path::new(&cert_dir)) {
+        return Ok(vec![]);
+    }
+    let mut files = vec![];
+    for cert_file in std::fs::read_dir(cert_dir)? {
+        let path = cert_file?.path();
+        if path.extension().and_then(|ext| ext.to_str()) == Some("pem") {
+            files.push(path.to_string_lossy().to_string());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+/// 测试用临时文件,文件名带随机前缀,避免并发运行的测试互相覆盖,返回文件路径
+#[cfg(test)]
+pub(crate) fn write_temp_file(name: &str, content: &[u8]) -> String {
+    let path = std::env::temp_dir().join(format!("weapay_{}_{}", generate_random_string(8), name));
+    fs::write(&path, content).unwrap();
+    path.to_string_lossy().to_string()
+}
+/// 测试用临时目录,目录名带随机前缀,离开作用域时删除整个目录
+#[cfg(test)]
+pub(crate) struct TempDir(pub(crate) String);
+#[cfg(test)]
+impl TempDir {
+    pub(crate) fn new(name: &str) -> Self {
+        let path =
+            std::env::temp_dir().join(format!("weapay_{}_{}", generate_random_string(8), name));
+        fs::create_dir_all(&path).unwrap();
+        TempDir(path.to_string_lossy().to_string())
+    }
+}
+#[cfg(test)]
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+/// 生成测试用 RSA 私钥及自签名证书并写入临时文件,返回 (私钥, 私钥文件路径, 证书文件路径)
+#[cfg(test)]
+pub(crate) fn write_test_cert(cn: &str, days: u32) -> (PKey<Private>, String, String) {
+    use openssl::{asn1::Asn1Time, x509::X509Name};
+    let pkey = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+    let mut name_builder = X509Name::builder().unwrap();
+    name_builder.append_entry_by_text("CN", cn).unwrap();
+    let name = name_builder.build();
+    let mut builder = X509::builder().unwrap();
+    builder.set_subject_name(&name).unwrap();
+    builder.set_issuer_name(&name).unwrap();
+    builder.set_pubkey(&pkey).unwrap();
+    builder
+        .set_not_before(&Asn1Time::days_from_now(0).unwrap())
+        .unwrap();
+    builder
+        .set_not_after(&Asn1Time::days_from_now(days).unwrap())
+        .unwrap();
+    builder.sign(&pkey, MessageDigest::sha256()).unwrap();
+    let key_file = write_temp_file(
+        &format!("{}_key.pem", cn),
+        &pkey.private_key_to_pem_pkcs8().unwrap(),
+    );
+    let cert_file = write_temp_file(
+        &format!("{}_cert.pem", cn),
+        &builder.build().to_pem().unwrap(),
+    );
+    (pkey, key_file, cert_file)
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
     #[test]
+    fn test_with_query() {
+        let url = with_query(
+            "/v3/capital/capitallhh/banks/search-banks-by-bank-account",
+            &[("account_number", "ab+c/d==")],
+        )
+        .unwrap();
+        assert_eq!(
+            url,
+            "/v3/capital/capitallhh/banks/search-banks-by-bank-account?account_number=ab%2Bc%2Fd%3D%3D"
+        );
+    }
+    #[test]
+    fn test_beijing_time() {
+        assert_eq!(beijing_time(1_700_000_000), "2023-11-15 06:13:20");
+        assert_eq!(beijing_time(951_753_600), "2000-02-29 00:00:00");
+    }
+    #[test]
+    fn test_hmac_sha256() {
+        let sign = hmac_sha256("Jefe", "what do ya want for nothing?").unwrap();
+        assert_eq!(
+            sign,
+            "5BDCC146BF60754E6A042426089575C75A003F089D2739839DEC58B964EC3843"
+        );
+    }
+    #[test]
+    fn test_sm2_signature() {
+        use openssl::ec::{EcGroup, EcKey};
+        let group = EcGroup::from_curve_name(Nid::SM2).unwrap();
+        let ec_key = EcKey::generate(&group).unwrap();
+        let pkey = PKey::from_ec_key(ec_key).unwrap();
+        let key_file = write_temp_file("sm2_key.pem", &pkey.private_key_to_pem_pkcs8().unwrap());
+        let key_path = key_file.as_str();
+        let sign = generate_sm2_signature(vec!["GET", "/v3/certificates", "1554208460"], key_path)
+            .unwrap();
+        let public_pem = read_pkey(key_path).unwrap().public_key_to_pem().unwrap();
+        let pkey = PKey::public_key_from_pem(&public_pem).unwrap();
+        let data = b"GET\n/v3/certificates\n1554208460\n";
+        assert!(verify_with_public_key(data, &sign, &pkey).unwrap());
+        assert!(!verify_with_public_key(b"tampered", &sign, &pkey).unwrap());
+        fs::remove_file(key_file).unwrap();
+    }
+    // RFC 8998 A.1 SM4-GCM 测试向量
+    #[test]
+    fn test_sm4_gcm_decrypt() {
+        let from_hex = |s: &str| {
+            (0..s.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+                .collect::<Vec<u8>>()
+        };
+        let key = from_hex("0123456789ABCDEFFEDCBA9876543210");
+        let nonce = from_hex("00001234567800000000ABCD");
+        let aad = from_hex("FEEDFACEDEADBEEFFEEDFACEDEADBEEFABADDAD2");
+        let plain = "AAAAAAAAAAAAAAAABBBBBBBBBBBBBBBBCCCCCCCCCCCCCCCCDDDDDDDDDDDDDDDDEEEEEEEEEEEEEEEEFFFFFFFFFFFFFFFFEEEEEEEEEEEEEEEEAAAAAAAAAAAAAAAA";
+        let ciphertext = from_hex(concat!(
+            "17F399F08C67D5EE19D0DC9969C4BB7D5FD46FD3756489069157B282BB200735",
+            "D82710CA5C22F0CCFA7CBF93D496AC15A56834CBCF98C397B4024A2691233B8D",
+            "83DE3541E4C2B58177E065A9BF7B62EC"
+        ));
+        let decrypted = sm4_gcm_decrypt(&key, &nonce, &aad, &ciphertext).unwrap();
+        assert_eq!(decrypted, from_hex(plain));
+        assert!(sm4_gcm_decrypt(&key, &nonce, b"tampered", &ciphertext).is_err());
+    }
+    #[test]
+    fn test_hex_digest_sm3() {
+        let digest = hex_digest("SM3", b"abc").unwrap();
+        assert_eq!(
+            digest,
+            "66c7f0f462eeedd9d1f2d46bdc10e4e24167c4875cf2f7a2297da02b8f4ba8e0"
+        );
+    }
+    #[test]
     fn test_serial() {
         let cert_file = "E:\\work\\code\\lifebank\\backend\\weapay\\certs\\appPublicCert.crt";
         let sn = get_cert_sn(cert_file).unwrap();
@@ -182,10 +656,23 @@ mod tests {
         println!("random_string==={}", random_string);
         assert_eq!(random_string.len(), 32);
     }
+    #[test]
+    fn test_yuan_to_fen() {
+        assert_eq!(yuan_to_fen("0.01").unwrap(), 1);
+        assert_eq!(yuan_to_fen("12").unwrap(), 1200);
+        assert_eq!(yuan_to_fen("1.5").unwrap(), 150);
+        assert_eq!(yuan_to_fen("-3.20").unwrap(), -320);
+        assert_eq!(yuan_to_fen("0.00600").unwrap(), 1);
+        assert_eq!(yuan_to_fen("").unwrap(), 0);
+        assert!(yuan_to_fen("1.2a").is_err());
+        assert!(yuan_to_fen("-").is_err());
+    }
     // test list wechat certs
     #[test]
     fn test_list_wechat_certs() {
-        let certs = list_wechat_certs("5AD141C1086A7945A1394A8AEAA9EB0619751859").unwrap();
+        let cert_dir = crate::WechatConfig::default().cert_dir();
+        let certs =
+            list_wechat_certs(&cert_dir, "5AD141C1086A7945A1394A8AEAA9EB0619751859").unwrap();
         println!("certs==={:?}", certs);
         // assert_eq!(certs.len(), 0);
     }
@@ -0,0 +1,183 @@
+use crate::utils::*;
+use crate::wechat::prelude::*;
+use crate::*;
+/// 特约商户进件
+/// 需为服务商模式,图片资料先通过 MediaTrait::upload_image 上传获取 media_id
+pub trait ApplymentTrait {
+    /// 提交申请单,身份证件、联系方式、银行账户等敏感信息会自动使用平台证书加密
+    fn submit_applyment(&self, data: ReqApplyment) -> BoxFuture<'_, ApplymentResponse>;
+    /// 通过业务申请编号查询申请状态
+    fn query_applyment_by_business_code(
+        &self,
+        business_code: &str,
+    ) -> BoxFuture<'_, ApplymentQueryResponse>;
+    /// 通过申请单号查询申请状态
+    fn query_applyment(&self, applyment_id: u64) -> BoxFuture<'_, ApplymentQueryResponse>;
+    /// 获取对私银行卡号开户银行,银行卡号会自动加密
+    fn search_banks_by_account(&self, account_number: &str) -> BoxFuture<'_, BankListResponse>;
+    /// 查询支持个人业务的银行列表
+    fn personal_banks(&self, offset: i32, limit: i32) -> BoxFuture<'_, BankListResponse>;
+    /// 查询支持对公业务的银行列表
+    fn corporate_banks(&self, offset: i32, limit: i32) -> BoxFuture<'_, BankListResponse>;
+    /// 查询省份列表
+    fn provinces(&self) -> BoxFuture<'_, ProvinceListResponse>;
+    /// 查询城市列表
+    fn cities(&self, province_code: i32) -> BoxFuture<'_, CityListResponse>;
+    /// 查询支行列表,bank_alias_code 为银行列表中的银行别名编码
+    fn bank_branches(
+        &self,
+        bank_alias_code: &str,
+        city_code: i32,
+        offset: i32,
+        limit: i32,
+    ) -> BoxFuture<'_, BankBranchListResponse>;
+}
+impl ApplymentTrait for Payment<WechatConfig> {
+    fn submit_applyment(&self, data: ReqApplyment) -> BoxFuture<'_, ApplymentResponse> {
+        let mut new_data = data;
+        Box::pin(async move {
+            let (serial_no, cert_file) = self.get_platform_cert().await?;
+            encrypt_applyment(&mut new_data, &cert_file)?;
+            let body = serde_json::to_string(&new_data)?;
+            let req_builder = self
+                .build_request_builder("/v3/applyment4sub/applyment/", "POST", &body)?
+                .header("Wechatpay-Serial", serial_no);
+            self.send_request::<ApplymentResponse>(req_builder).await
+        })
+    }
+    fn query_applyment_by_business_code(
+        &self,
+        business_code: &str,
+    ) -> BoxFuture<'_, ApplymentQueryResponse> {
+        let url = format!(
+            "/v3/applyment4sub/applyment/business_code/{}",
+            business_code
+        );
+        Box::pin(async move {
+            self.do_request::<ApplymentQueryResponse>(&url, "GET", "")
+                .await
+        })
+    }
+    fn query_applyment(&self, applyment_id: u64) -> BoxFuture<'_, ApplymentQueryResponse> {
+        let url = format!("/v3/applyment4sub/applyment/applyment_id/{}", applyment_id);
+        Box::pin(async move {
+            self.do_request::<ApplymentQueryResponse>(&url, "GET", "")
+                .await
+        })
+    }
+    fn search_banks_by_account(&self, account_number: &str) -> BoxFuture<'_, BankListResponse> {
+        let account_number = account_number.to_string();
+        Box::pin(async move {
+            let (serial_no, cert_file) = self.get_platform_cert().await?;
+            let account_number = rsa_encrypt_oaep(&account_number, &cert_file)?;
+            let url = with_query(
+                "/v3/capital/capitallhh/banks/search-banks-by-bank-account",
+                &[("account_number", &account_number)],
+            )?;
+            let req_builder = self
+                .build_request_builder(&url, "GET", "")?
+                .header("Wechatpay-Serial", serial_no);
+            self.send_request::<BankListResponse>(req_builder).await
+        })
+    }
+    fn personal_banks(&self, offset: i32, limit: i32) -> BoxFuture<'_, BankListResponse> {
+        let url = format!(
+            "/v3/capital/capitallhh/banks/personal-banking?offset={}&limit={}",
+            offset, limit
+        );
+        Box::pin(async move { self.do_request::<BankListResponse>(&url, "GET", "").await })
+    }
+    fn corporate_banks(&self, offset: i32, limit: i32) -> BoxFuture<'_, BankListResponse> {
+        let url = format!(
+            "/v3/capital/capitallhh/banks/corporate-banking?offset={}&limit={}",
+            offset, limit
+        );
+        Box::pin(async move { self.do_request::<BankListResponse>(&url, "GET", "").await })
+    }
+    fn provinces(&self) -> BoxFuture<'_, ProvinceListResponse> {
+        Box::pin(async move {
+            self.do_request::<ProvinceListResponse>(
+                "/v3/capital/capitallhh/areas/provinces",
+                "GET",
+                "",
+            )
+            .await
+        })
+    }
+    fn cities(&self, province_code: i32) -> BoxFuture<'_, CityListResponse> {
+        let url = format!(
+            "/v3/capital/capitallhh/areas/provinces/{}/cities",
+            province_code
+        );
+        Box::pin(async move { self.do_request::<CityListResponse>(&url, "GET", "").await })
+    }
+    fn bank_branches(
+        &self,
+        bank_alias_code: &str,
+        city_code: i32,
+        offset: i32,
+        limit: i32,
+    ) -> BoxFuture<'_, BankBranchListResponse> {
+        let url = format!(
+            "/v3/capital/capitallhh/banks/{}/branches?city_code={}&offset={}&limit={}",
+            bank_alias_code, city_code, offset, limit
+        );
+        Box::pin(async move {
+            self.do_request::<BankBranchListResponse>(&url, "GET", "")
+                .await
+        })
+    }
+}
+/// 使用平台证书加密申请单中的敏感信息
+fn encrypt_applyment(data: &mut ReqApplyment, cert_file: &str) -> WeaResult<()> {
+    let encrypt = |value: &mut String| -> WeaResult<()> {
+        *value = rsa_encrypt_oaep(value, cert_file)?;
+        Ok(())
+    };
+    let encrypt_option = |value: &mut Option<String>| -> WeaResult<()> {
+        if let Some(v) = value {
+            *v = rsa_encrypt_oaep(v, cert_file)?;
+        }
+        Ok(())
+    };
+    let contact = &mut data.contact_info;
+    encrypt(&mut contact.contact_name)?;
+    encrypt_option(&mut contact.contact_id_number)?;
+    encrypt_option(&mut contact.openid)?;
+    encrypt(&mut contact.mobile_phone)?;
+    encrypt(&mut contact.contact_email)?;
+    let identity = &mut data.subject_info.identity_info;
+    if let Some(card) = identity.id_card_info.as_mut() {
+        encrypt(&mut card.id_card_name)?;
+        encrypt(&mut card.id_card_number)?;
+        encrypt_option(&mut card.id_card_address)?;
+    }
+    if let Some(doc) = identity.id_doc_info.as_mut() {
+        encrypt(&mut doc.id_doc_name)?;
+        encrypt(&mut doc.id_doc_number)?;
+        encrypt_option(&mut doc.id_doc_address)?;
+    }
+    for ubo in data.subject_info.ubo_info_list.iter_mut().flatten() {
+        encrypt(&mut ubo.ubo_id_doc_name)?;
+        encrypt(&mut ubo.ubo_id_doc_number)?;
+        encrypt(&mut ubo.ubo_id_doc_address)?;
+    }
+    if let Some(bank) = data.bank_account_info.as_mut() {
+        encrypt(&mut bank.account_name)?;
+        encrypt(&mut bank.account_number)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_applyment_query_response() {
+        let content = r#"{"business_code":"1900013511_10000","applyment_id":2000002124775691,"sign_url":"https://pay.weixin.qq.com/public/apply4ec_sign/s?applymentId=2000002124775691","applyment_state":"APPLYMENT_STATE_REJECTED","applyment_state_msg":"请修改申请单","audit_detail":[{"field":"id_card_number","field_name":"身份证号码","reject_reason":"身份证号码与身份证照片不一致"}]}"#;
+        let resp: ApplymentQueryResponse = serde_json::from_str(content).unwrap();
+        assert_eq!(resp.applyment_state, ApplymentState::ApplymentStateRejected);
+        assert!(resp.sub_mchid.is_none());
+        assert_eq!(resp.audit_detail.unwrap()[0].field, "id_card_number");
+    }
+}
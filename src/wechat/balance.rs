@@ -0,0 +1,117 @@
+use crate::utils::*;
+use crate::wechat::prelude::*;
+use crate::*;
+/// 账户余额查询及特约商户提现
+/// 直连模式查询本商户余额,服务商模式查询配置中 mchid 对应的特约商户余额
+pub trait BalanceTrait {
+    /// 查询账户实时余额
+    fn query_balance(
+        &self,
+        account_type: FundBillAccountType,
+    ) -> BoxFuture<'_, FundBalanceResponse>;
+    /// 查询账户日终余额,date 格式为 YYYY-MM-DD
+    /// 服务商模式下日终余额接口不区分账户类型,account_type 被忽略
+    fn query_day_end_balance<'a>(
+        &'a self,
+        account_type: FundBillAccountType,
+        date: &'a str,
+    ) -> BoxFuture<'a, FundBalanceResponse>;
+    /// 特约商户余额提现,仅服务商模式可用
+    fn sub_merchant_withdraw(
+        &self,
+        data: ReqEcommerceWithdraw,
+    ) -> BoxFuture<'_, EcommerceWithdrawResponse>;
+    /// 通过微信支付提现单号查询特约商户提现状态
+    fn query_sub_merchant_withdraw(
+        &self,
+        withdraw_id: &str,
+    ) -> BoxFuture<'_, EcommerceWithdrawQueryResponse>;
+    /// 通过商户提现单号查询特约商户提现状态
+    fn query_sub_merchant_withdraw_by_out_no(
+        &self,
+        out_request_no: &str,
+    ) -> BoxFuture<'_, EcommerceWithdrawQueryResponse>;
+}
+impl BalanceTrait for Payment<WechatConfig> {
+    fn query_balance(
+        &self,
+        account_type: FundBillAccountType,
+    ) -> BoxFuture<'_, FundBalanceResponse> {
+        Box::pin(async move {
+            if self.is_sp() {
+                let resp = self.query_ecommerce_balance(Some(account_type)).await?;
+                return Ok(resp.into());
+            }
+            let url = format!("/v3/merchant/fund/balance/{}", account_type.as_str());
+            self.do_request::<FundBalanceResponse>(&url, "GET", "")
+                .await
+        })
+    }
+    fn query_day_end_balance<'a>(
+        &'a self,
+        account_type: FundBillAccountType,
+        date: &'a str,
+    ) -> BoxFuture<'a, FundBalanceResponse> {
+        Box::pin(async move {
+            if self.is_sp() {
+                let resp = self.query_ecommerce_end_day_balance(date).await?;
+                return Ok(resp.into());
+            }
+            let url = format!(
+                "/v3/merchant/fund/dayendbalance/{}?date={}",
+                account_type.as_str(),
+                date
+            );
+            self.do_request::<FundBalanceResponse>(&url, "GET", "")
+                .await
+        })
+    }
+    fn sub_merchant_withdraw(
+        &self,
+        data: ReqEcommerceWithdraw,
+    ) -> BoxFuture<'_, EcommerceWithdrawResponse> {
+        Box::pin(async move {
+            if !self.is_sp() {
+                return Err(e("sub merchant withdraw requires sp mode"));
+            }
+            self.ecommerce_withdraw(data).await
+        })
+    }
+    fn query_sub_merchant_withdraw(
+        &self,
+        withdraw_id: &str,
+    ) -> BoxFuture<'_, EcommerceWithdrawQueryResponse> {
+        self.query_ecommerce_withdraw(withdraw_id)
+    }
+    fn query_sub_merchant_withdraw_by_out_no(
+        &self,
+        out_request_no: &str,
+    ) -> BoxFuture<'_, EcommerceWithdrawQueryResponse> {
+        self.query_ecommerce_withdraw_by_out_no(out_request_no)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_fund_balance_response() {
+        let content = r#"{"available_amount":10000,"pending_amount":500}"#;
+        let resp: FundBalanceResponse = serde_json::from_str(content).unwrap();
+        assert!(resp.sub_mchid.is_none());
+        assert!(resp.is_sufficient(10000));
+        assert!(!resp.is_sufficient(10001));
+        let content = r#"{"sub_mchid":"1900000109","account_type":"OPERATION","available_amount":100,"pending_amount":0}"#;
+        let resp: FundBalanceResponse = serde_json::from_str(content).unwrap();
+        assert_eq!(resp.account_type, Some(FundBillAccountType::OPERATION));
+    }
+    #[test]
+    fn test_from_ecommerce_balance() {
+        let content = r#"{"sub_mchid":"1900000109","available_amount":100,"pending_amount":20}"#;
+        let resp: EcommerceEndDayBalanceResponse = serde_json::from_str(content).unwrap();
+        let resp: FundBalanceResponse = resp.into();
+        assert_eq!(resp.sub_mchid.as_deref(), Some("1900000109"));
+        assert_eq!(resp.available_amount, 100);
+        assert_eq!(resp.pending_amount, Some(20));
+    }
+}
@@ -1,6 +1,12 @@
 use crate::utils::*;
+use crate::wechat::dict::bill::append_bill_part;
 use crate::wechat::prelude::*;
 use crate::*;
+use flate2::write::GzDecoder;
+use openssl::hash::Hasher;
+use std::io::Write;
+use std::path::Path;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 pub trait BillTrait {
     /// 申请交易账单
     #[allow(dead_code)]
@@ -23,6 +29,57 @@ pub trait BillTrait {
     #[allow(dead_code)]
     //fn download(&self,download_url: &str) -> WeaResult<Bytes>;
     fn download(&self, download_url: &str) -> BoxFuture<reqwest::Response>;
+    /// 下载申请到的账单,gzip 为 true 时先解压,然后按 hash_type 校验摘要
+    /// 返回解压后的账单内容
+    fn download_bill<'a>(&'a self, bill: &'a BillResponse, gzip: bool) -> BoxFuture<'a, Vec<u8>>;
+    /// 流式下载申请到的账单并写入 writer,边下载边计算摘要,内存占用与账单大小无关
+    /// gzip 为 true 时边下载边解压,写入的是解压后的内容
+    /// 摘要不一致时返回 HashMismatch 错误,此时 writer 中已写入的内容不可信
+    fn download_bill_to_writer<'a, W>(
+        &'a self,
+        bill: &'a BillResponse,
+        gzip: bool,
+        writer: &'a mut W,
+    ) -> BoxFuture<'a, u64>
+    where
+        W: AsyncWrite + Unpin + Send + 'a;
+    /// 流式下载申请到的账单并保存到文件,返回写入的字节数
+    /// 下载失败或摘要不一致时删除文件
+    fn download_bill_to_file<'a>(
+        &'a self,
+        bill: &'a BillResponse,
+        gzip: bool,
+        path: &'a Path,
+    ) -> BoxFuture<'a, u64>;
+    /// 申请、下载并解析交易账单
+    fn download_trade_bill(
+        &self,
+        bill_date: String,
+        bill_type: TradeBillType,
+        with_mchid: bool,
+    ) -> BoxFuture<'_, TradeBill>;
+    /// 申请、下载并解析资金账单
+    fn download_fund_flow_bill(
+        &self,
+        bill_date: String,
+        account_type: FundBillAccountType,
+    ) -> BoxFuture<'_, FundFlowBill>;
+    /// 申请二级商户资金账单,账单文件按分片加密
+    fn sub_merchant_fund_bill(
+        &self,
+        sub_mchid: String,
+        bill_date: String,
+        account_type: FundBillAccountType,
+        tar_type: Option<String>,
+    ) -> BoxFuture<'_, SubMerchantFundBillResponse>;
+    /// 申请、下载、解密并解析二级商户资金账单
+    /// 分片按 bill_sequence 顺序解密合并,每个分片均会校验摘要
+    fn download_sub_merchant_fund_bill(
+        &self,
+        sub_mchid: String,
+        bill_date: String,
+        account_type: FundBillAccountType,
+    ) -> BoxFuture<'_, FundFlowBill>;
 }
 impl BillTrait for Payment<WechatConfig> {
     fn trade_bill(
@@ -58,7 +115,7 @@ impl BillTrait for Payment<WechatConfig> {
         Box::pin(async move { self.do_request::<BillResponse>(&url, "GET", "").await })
     }
     fn download(&self, download_url: &str) -> BoxFuture<reqwest::Response> {
-        let download_url = download_url.replace("https://api.mch.weixin.qq.com", "");
+        let download_url = download_url.replace(self.base_url(), "");
         Box::pin(async move {
             let req_builder = self.build_request_builder(&download_url, "GET", "")?;
             let resp = req_builder.send().await?;
@@ -71,6 +128,189 @@ impl BillTrait for Payment<WechatConfig> {
             }
         })
     }
+    fn download_bill<'a>(&'a self, bill: &'a BillResponse, gzip: bool) -> BoxFuture<'a, Vec<u8>> {
+        Box::pin(async move {
+            let resp = self.download(&bill.download_url).await?;
+            let content = resp.bytes().await?;
+            let content = if gzip {
+                gunzip(&content)?
+            } else {
+                content.to_vec()
+            };
+            verify_bill_hash(&content, &bill.hash_type, &bill.hash_value)?;
+            Ok(content)
+        })
+    }
+    fn download_bill_to_writer<'a, W>(
+        &'a self,
+        bill: &'a BillResponse,
+        gzip: bool,
+        writer: &'a mut W,
+    ) -> BoxFuture<'a, u64>
+    where
+        W: AsyncWrite + Unpin + Send + 'a,
+    {
+        Box::pin(async move {
+            let mut resp = self.download(&bill.download_url).await?;
+            let mut stream = BillStreamWriter::new(writer, &bill.hash_type, gzip)?;
+            while let Some(chunk) = resp.chunk().await? {
+                stream.write_chunk(&chunk).await?;
+            }
+            stream.finish(&bill.hash_value).await
+        })
+    }
+    fn download_bill_to_file<'a>(
+        &'a self,
+        bill: &'a BillResponse,
+        gzip: bool,
+        path: &'a Path,
+    ) -> BoxFuture<'a, u64> {
+        Box::pin(async move {
+            let mut file = tokio::fs::File::create(path).await?;
+            let result = self.download_bill_to_writer(bill, gzip, &mut file).await;
+            if result.is_err() {
+                drop(file);
+                // 清理失败时仍返回下载或校验的错误
+                let _ = tokio::fs::remove_file(path).await;
+            }
+            result
+        })
+    }
+    fn download_trade_bill(
+        &self,
+        bill_date: String,
+        bill_type: TradeBillType,
+        with_mchid: bool,
+    ) -> BoxFuture<'_, TradeBill> {
+        Box::pin(async move {
+            let bill = self
+                .trade_bill(
+                    bill_date,
+                    Some(bill_type.as_str().to_string()),
+                    Some("GZIP".to_string()),
+                    with_mchid,
+                )
+                .await?;
+            let content = self.download_bill(&bill, true).await?;
+            TradeBill::parse(&String::from_utf8(content)?)
+        })
+    }
+    fn download_fund_flow_bill(
+        &self,
+        bill_date: String,
+        account_type: FundBillAccountType,
+    ) -> BoxFuture<'_, FundFlowBill> {
+        Box::pin(async move {
+            let bill = self
+                .fund_bill(
+                    bill_date,
+                    Some(account_type.as_str().to_string()),
+                    Some("GZIP".to_string()),
+                )
+                .await?;
+            let content = self.download_bill(&bill, true).await?;
+            FundFlowBill::parse(&String::from_utf8(content)?)
+        })
+    }
+    fn sub_merchant_fund_bill(
+        &self,
+        sub_mchid: String,
+        bill_date: String,
+        account_type: FundBillAccountType,
+        tar_type: Option<String>,
+    ) -> BoxFuture<'_, SubMerchantFundBillResponse> {
+        let url = format!(
+            "/v3/bill/sub-merchant-fundflowbill?sub_mchid={}&bill_date={}&account_type={}&algorithm=AEAD_AES_256_GCM&tar_type={}",
+            sub_mchid,
+            bill_date,
+            account_type.as_str(),
+            tar_type.unwrap_or("GZIP".to_string())
+        );
+        Box::pin(async move {
+            self.do_request::<SubMerchantFundBillResponse>(&url, "GET", "")
+                .await
+        })
+    }
+    fn download_sub_merchant_fund_bill(
+        &self,
+        sub_mchid: String,
+        bill_date: String,
+        account_type: FundBillAccountType,
+    ) -> BoxFuture<'_, FundFlowBill> {
+        Box::pin(async move {
+            let mut bill = self
+                .sub_merchant_fund_bill(sub_mchid, bill_date, account_type, None)
+                .await?;
+            bill.download_bill_list
+                .sort_by_key(|item| item.bill_sequence);
+            let apiclient_key = self.merchant_cert().0;
+            let mut merged = Vec::new();
+            for item in bill.download_bill_list.iter() {
+                let resp = self.download(&item.download_url).await?;
+                let content = resp.bytes().await?;
+                append_bill_part(&mut merged, item.decrypt(&content, &apiclient_key, true)?);
+            }
+            FundFlowBill::parse(&String::from_utf8(merged)?)
+        })
+    }
+}
+/// 账单流式写入器,边写入边计算摘要,gzip 为 true 时边写入边解压
+/// 可用于自行下载的账单数据
+pub struct BillStreamWriter<'a, W> {
+    writer: &'a mut W,
+    hasher: Hasher,
+    decoder: Option<GzDecoder<Vec<u8>>>,
+    written: u64,
+}
+impl<'a, W: AsyncWrite + Unpin> BillStreamWriter<'a, W> {
+    pub fn new(writer: &'a mut W, hash_type: &str, gzip: bool) -> WeaResult<Self> {
+        Ok(BillStreamWriter {
+            writer,
+            hasher: Hasher::new(message_digest(hash_type)?)?,
+            decoder: if gzip {
+                Some(GzDecoder::new(Vec::new()))
+            } else {
+                None
+            },
+            written: 0,
+        })
+    }
+    /// 写入下载的数据块
+    pub async fn write_chunk(&mut self, chunk: &[u8]) -> WeaResult<()> {
+        match self.decoder.as_mut() {
+            Some(decoder) => {
+                decoder.write_all(chunk)?;
+                let data = std::mem::take(decoder.get_mut());
+                self.write_plain(&data).await
+            }
+            None => self.write_plain(chunk).await,
+        }
+    }
+    /// 写入完成,校验摘要并返回写入的字节数
+    pub async fn finish(mut self, hash_value: &str) -> WeaResult<u64> {
+        if let Some(decoder) = self.decoder.take() {
+            let data = decoder.finish()?;
+            self.write_plain(&data).await?;
+        }
+        self.writer.flush().await?;
+        let digest = to_hex(&self.hasher.finish()?);
+        check_digest(&digest, hash_value)?;
+        Ok(self.written)
+    }
+    async fn write_plain(&mut self, data: &[u8]) -> WeaResult<()> {
+        if data.is_empty() {
+            return Ok(());
+        }
+        self.hasher.update(data)?;
+        self.writer.write_all(data).await?;
+        self.written += data.len() as u64;
+        Ok(())
+    }
+}
+/// 校验账单摘要,摘要按解压后的账单内容计算
+pub fn verify_bill_hash(content: &[u8], hash_type: &str, hash_value: &str) -> WeaResult<()> {
+    let digest = hex_digest(hash_type, content)?;
+    check_digest(&digest, hash_value)
 }
 
 #[cfg(test)]
@@ -114,6 +354,41 @@ mod tests {
             println!("{:?}", result);
         }
     }
+    #[test]
+    fn test_verify_bill_hash() {
+        let content = "总交易单数,订单总金额\n`1,`0.01\n".as_bytes();
+        let hash_value = hex_digest("SHA1", content).unwrap();
+        assert!(verify_bill_hash(content, "SHA1", &hash_value).is_ok());
+        assert!(verify_bill_hash(content, "SHA1", &hash_value.to_uppercase()).is_ok());
+        assert!(verify_bill_hash(b"tampered", "SHA1", &hash_value).is_err());
+        assert!(verify_bill_hash(content, "MD5", &hash_value).is_err());
+    }
+    #[tokio::test]
+    async fn test_bill_stream_writer() {
+        use flate2::{write::GzEncoder, Compression};
+        let content = "交易时间,订单金额\n`2024-07-01 10:00:00,`0.01\n".repeat(1000);
+        let hash_value = hex_digest("SHA1", content.as_bytes()).unwrap();
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(content.as_bytes()).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let mut output = Vec::new();
+        let mut stream = BillStreamWriter::new(&mut output, "SHA1", true).unwrap();
+        for chunk in compressed.chunks(64) {
+            stream.write_chunk(chunk).await.unwrap();
+        }
+        let written = stream.finish(&hash_value).await.unwrap();
+        assert_eq!(written, content.len() as u64);
+        assert_eq!(output, content.as_bytes());
+
+        let mut output = Vec::new();
+        let mut stream = BillStreamWriter::new(&mut output, "SHA1", false).unwrap();
+        for chunk in content.as_bytes().chunks(100) {
+            stream.write_chunk(chunk).await.unwrap();
+        }
+        let err = stream.finish(&"0".repeat(40)).await.unwrap_err();
+        assert!(err.is_hash_mismatch());
+    }
     #[tokio::test]
     async fn test_download() {
         let config = crate::tests::get_config().0;
@@ -1,6 +1,6 @@
 use std::{fs,path};
 use openssl::{
-    base64::decode_block, hash::MessageDigest, sign::Verifier, x509::X509
+    base64::decode_block, x509::X509
 };
 use aes_gcm::{
     Aes256Gcm,KeyInit,Nonce,
@@ -10,6 +10,7 @@ use reqwest::Url;
 use serde::de::DeserializeOwned;
 use serde_json;
 use crate::*;
+use crate::notify::*;
 use crate::utils::*;
 use crate::wechat::prelude::*;
 //微信支付trait
@@ -27,6 +28,25 @@ pub trait BaseTrait {
     /// let notify = self.notify::<ResourceRefundBody>(nonce_str, timestamp, body, signature,serial).await?;
     /// ```
     fn notify<'a,U:DeserializeOwned>(&'a self,nonce_str: &'a str,timestamp: &'a str,body: &'a str,signature:&'a str,serial:&'a str) -> BoxFuture<U>;
+    /// 支付通知数据验证签名并解密,根据 event_type 返回对应类型的通知内容
+    /// 无需预先指定解密后的类型,未识别的通知类型返回 NotifyEvent::Unknown
+    /// #Example
+    /// ```rust
+    /// let notify = payment.notify_event(nonce_str, timestamp, body, signature,serial).await?;
+    /// match notify.event {
+    ///     NotifyEvent::TransactionSuccess(order) => {},
+    ///     NotifyEvent::RefundAbnormal(refund) => {},
+    ///     _ => {},
+    /// }
+    /// ```
+    fn notify_event<'a>(&'a self,nonce_str: &'a str,timestamp: &'a str,body: &'a str,signature:&'a str,serial:&'a str) -> BoxFuture<'a, NotifyEnvelope>;
+    /// 从http请求头中读取 Wechatpay-Nonce,Wechatpay-Timestamp,Wechatpay-Signature,Wechatpay-Serial
+    /// 验签解密后根据 event_type 返回对应类型的通知内容
+    fn notify_http<'a>(&'a self,headers: &'a http::HeaderMap,body: &'a [u8]) -> BoxFuture<'a, NotifyEnvelope>;
+    /// 同 notify_http,直接传入 http::Request
+    fn notify_request<'a, B: AsRef<[u8]> + Sync>(&'a self,req: &'a http::Request<B>) -> BoxFuture<'a, NotifyEnvelope>;
+    /// 校验通知的时间戳、签名,配置了 notify_store 时拒绝重放的通知,返回通知数据体
+    fn verify_notify<'a>(&'a self,nonce_str: &'a str,timestamp: &'a str,body: &'a str,signature:&'a str,serial:&'a str) -> BoxFuture<'a, RespBody>;
     /// 根据商家订单号查询订单
     fn query_order(&self,out_trade_no: &str) -> BoxFuture<ResourceOrderBody>;
     /// 根据微信支付订单号查询订单
@@ -37,10 +57,22 @@ pub trait BaseTrait {
     fn download_cert(&self) -> BoxFuture<Vec<String>>;
     /// 构建请求client 同时设置好请求头
     fn build_request_builder(&self,url: &str,method: &str,body: &str) -> WeaResult<reqwest::RequestBuilder>;
+    /// 生成请求头 Authorization,body 为参与签名的报文,图片/视频上传时为 meta 的 json 串
+    fn build_authorization(&self,method: &str,url: &str,body: &str) -> WeaResult<String>;
+    /// 使用配置的签名算法(RSA/SM2)及商户私钥签名,data 按行拼接后签名
+    fn sign(&self,data: Vec<&str>) -> WeaResult<String>;
     /// 发起请求同时会根据传入的类型返回对应的结果
     fn do_request<'a, U:DeserializeOwned>(&'a self,url: &'a str,method: &'a str,body: &'a str) -> BoxFuture<U>;
+    /// 发送已构建好的请求,验证应答签名后返回对应的结果
+    /// 需要额外请求头(如 Wechatpay-Serial)时先调用 build_request_builder 再调用此方法
+    fn send_request<'a, U:DeserializeOwned>(&'a self,req_builder: reqwest::RequestBuilder) -> BoxFuture<'a, U>;
+    /// 获取一个有效的平台证书,返回 (证书序列号,证书文件路径)
+    /// 用于敏感信息加密,本地没有有效证书时会自动下载
+    fn get_platform_cert(&self) -> BoxFuture<'_, (String,String)>;
     /// 判断是否是服务商模式
     fn is_sp(&self) -> bool;
+    /// 接口域名,境外模式下为 https://apihk.mch.weixin.qq.com
+    fn base_url(&self) -> &'static str;
     /// 获取请求uri服务商模式下uri前缀为/v3/pay/partner
     /// widh_mchid 是否带mchid,服务商模式下为 sub_mchid
     /// widh_sp 是否带sp_mchid,服务商模式下为 sp_mchid,默认为false
@@ -50,6 +82,14 @@ pub trait BaseTrait {
     fn verify_signature<'a>(&'a self,data: Vec<&'a str>,signature:&'a str,serial:&'a str) -> BoxFuture<bool>;
     /// 解密内容
     fn decrypt_content(&self,nonce: &str,ciphertext: &str,associated_data: &str) -> WeaResult<String>;
+    /// 依次使用 mch_key 及 api_v3_keys 解密,返回 (解密成功的密钥序号,解密内容)
+    /// 序号 0 为 mch_key,1 起为 api_v3_keys 中的顺序,全部失败时返回 mch_key 的解密错误
+    fn decrypt_content_with_index(&self,nonce: &str,ciphertext: &str,associated_data: &str) -> WeaResult<(usize,String)>;
+    /// 按通知或证书中的 algorithm (AEAD_AES_256_GCM 或 AEAD_SM4_GCM) 选择解密算法,返回值同 decrypt_content_with_index
+    /// algorithm 为空时按配置的 sign_type 选择,便于迁移国密期间同时处理两种加密方式
+    fn decrypt_resource(&self,algorithm: &str,nonce: &str,ciphertext: &str,associated_data: &str) -> WeaResult<(usize,String)>;
+    /// 当前使用的 (商户私钥路径,商户证书路径),配置了 merchant_cert 时使用其中的值
+    fn merchant_cert(&self) -> (String,String);
 }
 
 impl BaseTrait for Payment<WechatConfig> {
@@ -97,13 +137,13 @@ impl BaseTrait for Payment<WechatConfig> {
             
             match trade_type {
                 TradeType::JSAPI => {
-                    let pay_sign = generate_signature(vec![&app_id, &time_stamp, &nonce_str, &package],&self.config.apiclient_key).unwrap();
+                    let pay_sign = self.sign(vec![&app_id, &time_stamp, &nonce_str, &package])?;
                     let sign_package = JsapiSignPackage{
                         app_id,
                         time_stamp,
                         nonce_str,
                         package,
-                        sign_type: "RSA".to_string(),
+                        sign_type: self.config.sign_type.clone().unwrap_or_default().as_str().to_string(),
                         pay_sign
                     };
                     Ok(CreateOrderResult::JSAPI(sign_package))
@@ -111,7 +151,7 @@ impl BaseTrait for Payment<WechatConfig> {
               
                 TradeType::App => {
                     
-                    let pay_sign = generate_signature(vec![&app_id, &time_stamp, &nonce_str, &prepay_id],&self.config.apiclient_key).unwrap();
+                    let pay_sign = self.sign(vec![&app_id, &time_stamp, &nonce_str, &prepay_id])?;
                     let sign_package = AppSignPackage{
                         app_id,
                         partner_id: self.config.mchid.clone(),
@@ -133,20 +173,75 @@ impl BaseTrait for Payment<WechatConfig> {
     }
     fn notify<'a,U: DeserializeOwned>(&'a self,nonce_str: &'a str,timestamp: &'a str,body: &'a str,signature:&'a str,serial:&'a str) -> BoxFuture<U> {
         let fut = async move {
-            let is_valid = self.verify_signature(vec![timestamp, nonce_str,  body], signature,serial).await?;
-            if !is_valid {
-                return Err(e("signature verify error"));
-            }
-            let notify_content = serde_json::from_str::<RespBody>(body)?;
+            let notify_content = self.verify_notify(nonce_str, timestamp, body, signature, serial).await?;
             let nonce = notify_content.resource.nonce;
             let ciphertext = notify_content.resource.ciphertext;
             let associated_data = notify_content.resource.associated_data.unwrap_or("".to_string());
-            let content = self.decrypt_content(&nonce,&ciphertext,&associated_data)?;
+            let (_,content) = self.decrypt_resource(&notify_content.resource.algorithm,&nonce,&ciphertext,&associated_data)?;
             let notify_resource = serde_json::from_str::<U>(&content)?;
             Ok(notify_resource)            
         };
         Box::pin(fut)
     }
+    fn notify_event<'a>(&'a self,nonce_str: &'a str,timestamp: &'a str,body: &'a str,signature:&'a str,serial:&'a str) -> BoxFuture<'a, NotifyEnvelope> {
+        let fut = async move {
+            let notify_content = self.verify_notify(nonce_str, timestamp, body, signature, serial).await?;
+            let resource = notify_content.resource;
+            let associated_data = resource.associated_data.unwrap_or("".to_string());
+            let (api_v3_key_index,content) = self.decrypt_resource(&resource.algorithm,&resource.nonce,&resource.ciphertext,&associated_data)?;
+            let event = NotifyEvent::from_content(&notify_content.event_type, &content)?;
+            Ok(NotifyEnvelope {
+                id: notify_content.id,
+                create_time: notify_content.create_time,
+                resource_type: notify_content.resource_type,
+                event_type: notify_content.event_type,
+                summary: notify_content.summary,
+                event,
+                api_v3_key_index,
+            })
+        };
+        Box::pin(fut)
+    }
+    fn notify_http<'a>(&'a self,headers: &'a http::HeaderMap,body: &'a [u8]) -> BoxFuture<'a, NotifyEnvelope> {
+        let fut = async move {
+            let header = |name: &str| -> WeaResult<&'a str> {
+                match headers.get(name) {
+                    Some(value) => Ok(value.to_str()?),
+                    None => Err(e(&format!("missing header {}", name))),
+                }
+            };
+            let nonce_str = header("Wechatpay-Nonce")?;
+            let timestamp = header("Wechatpay-Timestamp")?;
+            let signature = header("Wechatpay-Signature")?;
+            let serial = header("Wechatpay-Serial")?;
+            let body = std::str::from_utf8(body).map_err(|_e| e("notify body is not utf8"))?;
+            self.notify_event(nonce_str, timestamp, body, signature, serial).await
+        };
+        Box::pin(fut)
+    }
+    fn notify_request<'a, B: AsRef<[u8]> + Sync>(&'a self,req: &'a http::Request<B>) -> BoxFuture<'a, NotifyEnvelope> {
+        self.notify_http(req.headers(), req.body().as_ref())
+    }
+    fn verify_notify<'a>(&'a self,nonce_str: &'a str,timestamp: &'a str,body: &'a str,signature:&'a str,serial:&'a str) -> BoxFuture<'a, RespBody> {
+        let fut = async move {
+            let max_skew = self.config.notify_max_skew.unwrap_or(DEFAULT_NOTIFY_MAX_SKEW);
+            check_timestamp(timestamp, max_skew)?;
+            let is_valid = self.verify_signature(vec![timestamp, nonce_str,  body], signature,serial).await?;
+            if !is_valid {
+                return Err(e("signature verify error"));
+            }
+            let notify_content = serde_json::from_str::<RespBody>(body)?;
+            if let Some(store) = &self.config.notify_store {
+                // 重试的通知会使用新的随机串,只有完全相同的通知才会被拒绝
+                let key = format!("{}:{}", notify_content.id, nonce_str);
+                if !store.check_and_record(&key, max_skew * 2).await? {
+                    return Err(e("notify replayed"));
+                }
+            }
+            Ok(notify_content)
+        };
+        Box::pin(fut)
+    }
     fn query_order(&self,out_trade_no: &str) -> BoxFuture<ResourceOrderBody> {
         let url = format!("/v3/pay/transactions/out-trade-no/{}", out_trade_no);
         let url = self.get_uri(&url,true,true);
@@ -180,7 +275,10 @@ impl BaseTrait for Payment<WechatConfig> {
 
     }
     fn download_cert(&self) -> BoxFuture<Vec<String>> {
-        let url = "/v3/certificates";
+        let url = match self.config.sign_type.clone().unwrap_or_default() {
+            WechatSignType::SM2 => "/v3/certificates?algorithm_type=SM2",
+            WechatSignType::RSA => "/v3/certificates",
+        };
         let url = self.get_uri(url,false,false);
         let fut = async move {
             let req_builder = self.build_request_builder(&url, "GET", "")?;
@@ -206,8 +304,7 @@ impl BaseTrait for Payment<WechatConfig> {
             for item in data {
                 //println!("{:?}", item);
                 let serial_no = item.serial_no;
-                let dir = env!("CARGO_MANIFEST_DIR");
-                let save_path = format!("{}/certs/download/",dir);
+                let save_path = self.config.cert_dir();
                 let cert_path = format!("{}{}.pem",save_path,serial_no);
                 //let cert_file  = path::Path::new(&cert_path);
                 // if  cert_file.is_file()  {
@@ -228,7 +325,7 @@ impl BaseTrait for Payment<WechatConfig> {
                 let nonce = encrypt_certificate.nonce;
                 let associated_data =  encrypt_certificate.associated_data;
 
-                let decrypt_data =  self.decrypt_content(&nonce,&ciphertext,&associated_data)?;
+                let (_,decrypt_data) = self.decrypt_resource(&encrypt_certificate.algorithm,&nonce,&ciphertext,&associated_data)?;
                 
                 //println!("平台证书内容==>\n{}",decrypt_data);
                 // let cert_content = decode_block(&cert_content)?;
@@ -242,28 +339,16 @@ impl BaseTrait for Payment<WechatConfig> {
     }
     /// build request client
     fn build_request_builder(&self,url: &str,method: &str,body: &str) -> WeaResult<reqwest::RequestBuilder> {
-        let base_url = Url::parse("https://api.mch.weixin.qq.com/").map_err(|_e| e("parse url error"))?;
+        let base_url = Url::parse(self.base_url()).map_err(|_e| e("parse url error"))?;
         let full_url = base_url.join(url).map_err(|_e| e("Join url error"))?;
         let full_url = full_url.as_str();
-        let timestamp = get_timestamp().unwrap().to_string();
-        let nonce_str = generate_random_string(32);
-        let sign_data = vec![method, url, &timestamp, &nonce_str, body];
-        let signature = generate_signature(sign_data,&self.config.apiclient_key)?;
-        let mchid = if self.is_sp() {
-            self.config.sp_mchid.clone().unwrap()
-        } else {
-            self.config.mchid.clone()
-        };
-        let serial_no = get_cert_serial(&self.config.apiclient_cert)?;
-        let authorization = format!(
-            "WECHATPAY2-SHA256-RSA2048 mchid=\"{}\",nonce_str=\"{}\",timestamp=\"{}\",serial_no=\"{}\",signature=\"{}\"", 
-            mchid, nonce_str,timestamp, 
-            serial_no, signature );
+        let authorization = self.build_authorization(method, url, body)?;
         let client = reqwest::Client::new();
         let req_builder = match method {
             "GET" => client.get(full_url),
             "POST" => client.post(full_url),
             "PUT" => client.put(full_url),
+            "PATCH" => client.patch(full_url),
             "DELETE" => client.delete(full_url),
             _ => client.get(full_url),
         };
@@ -280,11 +365,40 @@ impl BaseTrait for Payment<WechatConfig> {
         Ok(req_builder)
         
     }
+    fn build_authorization(&self,method: &str,url: &str,body: &str) -> WeaResult<String> {
+        let timestamp = get_timestamp().unwrap().to_string();
+        let nonce_str = generate_random_string(32);
+        let sign_data = vec![method, url, &timestamp, &nonce_str, body];
+        // 私钥与证书序列号取自同一份配置,避免替换证书期间两者不一致
+        let (apiclient_key,apiclient_cert) = self.merchant_cert();
+        let signature = sign_with_key(&self.config.sign_type.clone().unwrap_or_default(),sign_data,&apiclient_key)?;
+        let mchid = if self.is_sp() {
+            self.config.sp_mchid.clone().unwrap()
+        } else {
+            self.config.mchid.clone()
+        };
+        let serial_no = get_cert_serial(&apiclient_cert)?;
+        let schema = self.config.sign_type.clone().unwrap_or_default().schema();
+        let authorization = format!(
+            "{} mchid=\"{}\",nonce_str=\"{}\",timestamp=\"{}\",serial_no=\"{}\",signature=\"{}\"", 
+            schema, mchid, nonce_str,timestamp, 
+            serial_no, signature );
+        Ok(authorization)
+    }
+    fn sign(&self,data: Vec<&str>) -> WeaResult<String> {
+        sign_with_key(&self.config.sign_type.clone().unwrap_or_default(),data,&self.merchant_cert().0)
+    }
     // do request
     fn do_request<'a, U:DeserializeOwned>(&'a self,url: &'a str,method: &'a str,body: &'a str) ->  BoxFuture<U> {
-       
         let fut = async move {
             let req_builder = self.build_request_builder(url,method,body)?;
+            self.send_request::<U>(req_builder).await
+        };
+        Box::pin(fut)
+    }
+    // send request and verify response signature
+    fn send_request<'a, U:DeserializeOwned>(&'a self,req_builder: reqwest::RequestBuilder) -> BoxFuture<'a, U> {
+        let fut = async move {
             let res = req_builder.send()
             .await?;
             let status_code = res.status();
@@ -305,23 +419,50 @@ impl BaseTrait for Payment<WechatConfig> {
             if !signed {
                 return Err(e("signature verify error"));
             }
-            if status_code == 200 || status_code == 204{
-                //let res = res.text().await?;
-                let res: U = serde_json::from_str(&res.clone())?;
-                return Ok(res);
+            if status_code == 200 || status_code == 202 || status_code == 204{
+                // 202 已受理、204 无应答内容
+                let res = if res.is_empty() { "null".to_string() } else { res };
+                let res: U = serde_json::from_str(&res)?;
+                Ok(res)
+            } else if res.is_empty() {
+                Err(e(&status_code.to_string()))
             } else {
-                //let res = res.text().await?;
-                if res.is_empty() {
-                    return Err(e(&status_code.to_string()));
-                }
-                return Err(e(&res));
+                Err(e(&res))
             }
         };
         Box::pin(fut)
     }
+    // get a valid platform cert
+    fn get_platform_cert(&self) -> BoxFuture<'_, (String,String)> {
+        let fut = async move {
+            let cert_file = if let Some(cert_file) = list_wechat_certs(&self.config.cert_dir(),"")? {
+                cert_file
+            } else {
+                let cert_files = self.download_cert().await?;
+                match cert_files.into_iter().next() {
+                    Some(cert_file) => cert_file,
+                    None => return Err(e("cert file not found")),
+                }
+            };
+            let serial_no = path::Path::new(&cert_file)
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .ok_or(e("cert file name error"))?
+                .to_string();
+            Ok((serial_no,cert_file))
+        };
+        Box::pin(fut)
+    }
     fn is_sp(&self) -> bool {
         self.config.sp_appid.is_some() && self.config.sp_mchid.is_some()
     }
+    fn base_url(&self) -> &'static str {
+        if self.config.is_global.unwrap_or(false) {
+            "https://apihk.mch.weixin.qq.com"
+        } else {
+            "https://api.mch.weixin.qq.com"
+        }
+    }
     fn get_uri(&self, uri: &str,with_mchid:bool,with_sp:bool) -> String {
         let comm = if uri.contains("?"){
             "&"
@@ -354,7 +495,7 @@ impl BaseTrait for Payment<WechatConfig> {
         let fut = async move {
           
             //print!("验证签名数据====>\n{}",data);
-            let cert_file = if let Some(cert_file) = list_wechat_certs(serial)? {
+            let cert_file = if let Some(cert_file) = list_wechat_certs(&self.config.cert_dir(),serial)? {
                 cert_file
             } else {
                 let cert_files = self.download_cert().await?;
@@ -369,34 +510,74 @@ impl BaseTrait for Payment<WechatConfig> {
             let apiclient_cert = fs::read_to_string(cert_file)?;
             let app_cert = X509::from_pem(apiclient_cert.as_bytes())?;
 
+            // 根据平台证书的公钥类型验证 RSA 或 SM2 签名
             let pkey = app_cert.public_key()?;
-            let sign_u8 = decode_block(signature)?;
-            let mut verifier = Verifier::new(MessageDigest::sha256(), &pkey)?;
-            verifier.update(data.as_bytes())?;
-            let result = verifier.verify(&sign_u8)?;
-            Ok(result)
+            verify_with_public_key(data.as_bytes(), signature, &pkey)
         };
         Box::pin(fut)
     }
     // decrypt content
     fn decrypt_content(&self,nonce: &str,ciphertext: &str,associated_data: &str) -> WeaResult<String> {
-        let cipher = Aes256Gcm::new_from_slice(self.config.mch_key.as_bytes());
-        let cipher = match cipher {
-            Ok(cipher) => cipher,
-            Err(error) => return Err(e(&format!("cipher error:{}",error))),
-        };
-        let nonce = Nonce::from_slice(nonce.as_bytes());
-        let ciphertext = decode_block(&ciphertext)?;
-        let ciphertext = &ciphertext[..];
-        let payload = Payload {
-            msg: ciphertext,
-            aad: associated_data.as_bytes(),
+        let (_,content) = self.decrypt_content_with_index(nonce,ciphertext,associated_data)?;
+        Ok(content)
+    }
+    // decrypt content with mch_key then api_v3_keys
+    fn decrypt_content_with_index(&self,nonce: &str,ciphertext: &str,associated_data: &str) -> WeaResult<(usize,String)> {
+        self.decrypt_resource("",nonce,ciphertext,associated_data)
+    }
+    fn decrypt_resource(&self,algorithm: &str,nonce: &str,ciphertext: &str,associated_data: &str) -> WeaResult<(usize,String)> {
+        let sign_type = if algorithm.is_empty() {
+            self.config.sign_type.clone().unwrap_or_default()
+        } else {
+            WechatSignType::from_algorithm(algorithm)
+                .ok_or_else(|| e(&format!("unsupported algorithm:{}",algorithm)))?
         };
-        let decrypt_data = cipher.decrypt(nonce, payload)?;
-        let decrypt_data = String::from_utf8(decrypt_data)?;
-        Ok(decrypt_data)
+        let ciphertext = decode_block(ciphertext)?;
+        let keys = std::iter::once(&self.config.mch_key).chain(self.config.api_v3_keys.iter().flatten());
+        let mut first_error = None;
+        for (index,key) in keys.enumerate() {
+            match decrypt_with_key(&sign_type,key,nonce,&ciphertext,associated_data) {
+                Ok(content) => return Ok((index,content)),
+                Err(error) => {
+                    first_error.get_or_insert(error);
+                }
+            }
+        }
+        Err(first_error.unwrap_or_else(|| e("api v3 key is empty")))
+    }
+    fn merchant_cert(&self) -> (String,String) {
+        match &self.config.merchant_cert {
+            Some(merchant_cert) => merchant_cert.get(),
+            None => (self.config.apiclient_key.clone(),self.config.apiclient_cert.clone()),
+        }
+    }
+}
+// sign with the given private key by sign type
+fn sign_with_key(sign_type: &WechatSignType,data: Vec<&str>,apiclient_key: &str) -> WeaResult<String> {
+    match sign_type {
+        WechatSignType::SM2 => generate_sm2_signature(data,apiclient_key),
+        WechatSignType::RSA => generate_signature(data,apiclient_key),
     }
 }
+// decrypt with one api v3 key, AEAD_SM4_GCM for SM2 otherwise AEAD_AES_256_GCM
+fn decrypt_with_key(sign_type: &WechatSignType,key: &str,nonce: &str,ciphertext: &[u8],associated_data: &str) -> WeaResult<String> {
+    if sign_type == &WechatSignType::SM2 {
+        let key = sm4_key(key)?;
+        let decrypt_data = sm4_gcm_decrypt(&key, nonce.as_bytes(), associated_data.as_bytes(), ciphertext)?;
+        return Ok(String::from_utf8(decrypt_data)?);
+    }
+    let cipher = match Aes256Gcm::new_from_slice(key.as_bytes()) {
+        Ok(cipher) => cipher,
+        Err(error) => return Err(e(&format!("cipher error:{}",error))),
+    };
+    let nonce = Nonce::from_slice(nonce.as_bytes());
+    let payload = Payload {
+        msg: ciphertext,
+        aad: associated_data.as_bytes(),
+    };
+    let decrypt_data = cipher.decrypt(nonce, payload)?;
+    Ok(String::from_utf8(decrypt_data)?)
+}
 #[cfg(test)]
 mod tests {
     use tokio;
@@ -572,6 +753,107 @@ mod tests {
             //assert_eq!(result.out_trade_no, "T20240407003");
         }
     }
+    // 测试通知类型分发
+    #[test]
+    fn test_notify_event_from_content() {
+        use aes_gcm::{aead::{Aead, Payload}, Aes256Gcm, KeyInit, Nonce};
+        let config = crate::WechatConfig {
+            mch_key: "0123456789abcdef0123456789abcdef".to_string(),
+            ..Default::default()
+        };
+        let payment = super::Payment::new(config);
+        let content = r#"{"mchid":"1900000100","transaction_id":"1008450740201411110005820873","out_trade_no":"20150806125346","refund_id":"50200207182018070300011301001","out_refund_no":"7752501201407033233368018","refund_status":"ABNORMAL","success_time":null,"user_received_account":"招商银行信用卡0403","amount":{"total":999,"refund":999,"payer_total":999,"payer_refund":999}}"#;
+        let cipher = Aes256Gcm::new_from_slice(payment.config.mch_key.as_bytes()).unwrap();
+        let ciphertext = cipher.encrypt(Nonce::from_slice(b"fdasflkjasdf"), Payload { msg: content.as_bytes(), aad: b"refund" }).unwrap();
+        let ciphertext = openssl::base64::encode_block(&ciphertext);
+        let decrypted = payment.decrypt_content("fdasflkjasdf", &ciphertext, "refund").unwrap();
+        let event = NotifyEvent::from_content("REFUND.ABNORMAL", &decrypted).unwrap();
+        match event {
+            NotifyEvent::RefundAbnormal(refund) => assert_eq!(refund.refund_status, RefundStatus::ABNORMAL),
+            _ => panic!("event type error"),
+        }
+        let event = NotifyEvent::from_content("UNKNOWN.EVENT", r#"{"foo":"bar"}"#).unwrap();
+        match event {
+            NotifyEvent::Unknown(value) => assert_eq!(value["foo"], "bar"),
+            _ => panic!("event type error"),
+        }
+    }
+    // 测试APIv3密钥轮换,旧密钥加密的内容使用 api_v3_keys 解密
+    #[test]
+    fn test_decrypt_content_with_rotated_key() {
+        use aes_gcm::{aead::{Aead, Payload}, Aes256Gcm, KeyInit, Nonce};
+        let old_key = "0123456789abcdef0123456789abcdef";
+        let config = crate::WechatConfig {
+            mch_key: "fedcba9876543210fedcba9876543210".to_string(),
+            api_v3_keys: Some(vec!["00000000000000000000000000000000".to_string(), old_key.to_string()]),
+            ..Default::default()
+        };
+        let payment = super::Payment::new(config);
+        let cipher = Aes256Gcm::new_from_slice(old_key.as_bytes()).unwrap();
+        let ciphertext = cipher.encrypt(Nonce::from_slice(b"fdasflkjasdf"), Payload { msg: b"{}", aad: b"certificate" }).unwrap();
+        let ciphertext = openssl::base64::encode_block(&ciphertext);
+        let (index, content) = payment.decrypt_content_with_index("fdasflkjasdf", &ciphertext, "certificate").unwrap();
+        assert_eq!(index, 2);
+        assert_eq!(content, "{}");
+        let payment = super::Payment::new(crate::WechatConfig {
+            mch_key: "fedcba9876543210fedcba9876543210".to_string(),
+            ..Default::default()
+        });
+        assert!(payment.decrypt_content("fdasflkjasdf", &ciphertext, "certificate").is_err());
+    }
+    // 测试按资源中的 algorithm 选择解密算法,国密配置下仍可解密 AEAD_AES_256_GCM 内容
+    #[test]
+    fn test_decrypt_resource_by_algorithm() {
+        use aes_gcm::{aead::{Aead, Payload}, Aes256Gcm, KeyInit, Nonce};
+        let config = crate::WechatConfig {
+            mch_key: "0123456789abcdef0123456789abcdef".to_string(),
+            sign_type: Some(WechatSignType::SM2),
+            ..Default::default()
+        };
+        let payment = super::Payment::new(config);
+        let cipher = Aes256Gcm::new_from_slice(payment.config.mch_key.as_bytes()).unwrap();
+        let ciphertext = cipher.encrypt(Nonce::from_slice(b"fdasflkjasdf"), Payload { msg: b"{}", aad: b"transaction" }).unwrap();
+        let ciphertext = openssl::base64::encode_block(&ciphertext);
+        let (index, content) = payment.decrypt_resource("AEAD_AES_256_GCM", "fdasflkjasdf", &ciphertext, "transaction").unwrap();
+        assert_eq!(index, 0);
+        assert_eq!(content, "{}");
+        // 未指定 algorithm 时按 sign_type 使用 AEAD_SM4_GCM
+        assert!(payment.decrypt_resource("", "fdasflkjasdf", &ciphertext, "transaction").is_err());
+        assert!(payment.decrypt_resource("AEAD_SM4_GCM", "fdasflkjasdf", &ciphertext, "transaction").is_err());
+        assert!(payment.decrypt_resource("AEAD_CHACHA20", "fdasflkjasdf", &ciphertext, "transaction").is_err());
+        assert_eq!(WechatSignType::from_algorithm("AEAD_SM4_GCM"), Some(WechatSignType::SM2));
+    }
+    // 测试运行时替换商户私钥及证书
+    #[test]
+    fn test_merchant_cert_update() {
+        let (_, old_key, old_cert) = crate::utils::write_test_cert("merchant_old", 1);
+        let (_, new_key, new_cert) = crate::utils::write_test_cert("merchant_new", 1);
+        let files = vec![old_key.clone(), old_cert.clone(), new_key.clone(), new_cert.clone()];
+        let merchant_cert = MerchantCert::new(old_key.clone(), old_cert.clone());
+        let payment = super::Payment::new(crate::WechatConfig {
+            merchant_cert: Some(merchant_cert.clone()),
+            ..Default::default()
+        });
+        assert_eq!(payment.merchant_cert(), (old_key.clone(), old_cert));
+        assert!(merchant_cert.update(old_key, new_cert.clone()).is_err());
+        merchant_cert.update(new_key.clone(), new_cert.clone()).unwrap();
+        assert_eq!(payment.merchant_cert(), (new_key, new_cert));
+        for file in files {
+            std::fs::remove_file(file).unwrap();
+        }
+    }
+    // 测试http通知缺少请求头
+    #[tokio::test]
+    async fn test_notify_http_missing_header() {
+        let payment = super::Payment::new(crate::WechatConfig::default());
+        let req = http::Request::builder()
+            .header("Wechatpay-Nonce", "wXGCUJV30xenmtIaT9sGPjcty2jova4n")
+            .header("Wechatpay-Timestamp", "1721352091")
+            .body(b"{}".to_vec())
+            .unwrap();
+        let result = payment.notify_request(&req).await;
+        assert!(result.err().unwrap().to_string().contains("Wechatpay-Signature"));
+    }
     // 测试下载证书
     #[tokio::test]
     async fn test_download_cert() {
@@ -589,4 +871,4 @@ mod tests {
             //println!("{:?}", result.unwrap());
         }
     }
-}
\ No newline at end of file
+}
\ No newline at end of file
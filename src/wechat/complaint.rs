@@ -0,0 +1,227 @@
+use crate::utils::*;
+use crate::wechat::prelude::*;
+use crate::*;
+/// 消费者投诉2.0
+/// 投诉通知通过 BaseTrait::notify_event 处理,事件类型为 NotifyEvent::Complaint
+pub trait ComplaintTrait {
+    /// 查询投诉单列表,投诉人联系方式会使用商户私钥解密
+    fn list_complaints(&self, data: ReqComplaintList) -> BoxFuture<'_, ComplaintListResponse>;
+    /// 查询投诉单详情,投诉人联系方式会使用商户私钥解密
+    fn query_complaint(&self, complaint_id: &str) -> BoxFuture<'_, ComplaintDetail>;
+    /// 查询投诉协商历史
+    fn query_negotiation_history(
+        &self,
+        complaint_id: &str,
+        limit: i32,
+        offset: i32,
+    ) -> BoxFuture<'_, ComplaintNegotiationHistoryResponse>;
+    /// 回复用户
+    fn response_complaint(
+        &self,
+        complaint_id: &str,
+        data: ReqComplaintResponse,
+    ) -> BoxFuture<'_, ()>;
+    /// 反馈处理完成
+    fn complete_complaint(&self, complaint_id: &str) -> BoxFuture<'_, ()>;
+    /// 更新退款审批结果
+    fn update_refund_progress(
+        &self,
+        complaint_id: &str,
+        data: ReqRefundProgress,
+    ) -> BoxFuture<'_, ()>;
+    /// 创建投诉通知回调地址
+    fn create_complaint_notification(
+        &self,
+        url: &str,
+    ) -> BoxFuture<'_, ComplaintNotificationResponse>;
+    /// 查询投诉通知回调地址
+    fn query_complaint_notification(&self) -> BoxFuture<'_, ComplaintNotificationResponse>;
+    /// 更新投诉通知回调地址
+    fn update_complaint_notification(
+        &self,
+        url: &str,
+    ) -> BoxFuture<'_, ComplaintNotificationResponse>;
+    /// 删除投诉通知回调地址
+    fn delete_complaint_notification(&self) -> BoxFuture<'_, ()>;
+    /// 下载投诉图片,media_url 为投诉资料中的图片地址
+    fn download_complaint_image(&self, media_url: &str) -> BoxFuture<'_, Vec<u8>>;
+    /// 使用商户私钥解密投诉人联系方式
+    fn decrypt_payer_phone(&self, payer_phone: &str) -> WeaResult<String>;
+}
+impl ComplaintTrait for Payment<WechatConfig> {
+    fn list_complaints(&self, data: ReqComplaintList) -> BoxFuture<'_, ComplaintListResponse> {
+        let mut url = format!(
+            "/v3/merchant-service/complaints-v2?limit={}&offset={}&begin_date={}&end_date={}",
+            data.limit.unwrap_or(10),
+            data.offset.unwrap_or(0),
+            data.begin_date,
+            data.end_date
+        );
+        let complainted_mchid = data
+            .complainted_mchid
+            .or(self.is_sp().then(|| self.config.mchid.clone()));
+        if let Some(mchid) = complainted_mchid {
+            url.push_str(&format!("&complainted_mchid={}", mchid));
+        }
+        Box::pin(async move {
+            let mut resp = self
+                .do_request::<ComplaintListResponse>(&url, "GET", "")
+                .await?;
+            for detail in resp.data.iter_mut().flatten() {
+                if let Some(phone) = &detail.payer_phone {
+                    detail.payer_phone = Some(self.decrypt_payer_phone(phone)?);
+                }
+            }
+            Ok(resp)
+        })
+    }
+    fn query_complaint(&self, complaint_id: &str) -> BoxFuture<'_, ComplaintDetail> {
+        let url = format!("/v3/merchant-service/complaints-v2/{}", complaint_id);
+        Box::pin(async move {
+            let mut detail = self.do_request::<ComplaintDetail>(&url, "GET", "").await?;
+            if let Some(phone) = &detail.payer_phone {
+                detail.payer_phone = Some(self.decrypt_payer_phone(phone)?);
+            }
+            Ok(detail)
+        })
+    }
+    fn query_negotiation_history(
+        &self,
+        complaint_id: &str,
+        limit: i32,
+        offset: i32,
+    ) -> BoxFuture<'_, ComplaintNegotiationHistoryResponse> {
+        let url = format!(
+            "/v3/merchant-service/complaints-v2/{}/negotiation-historys?limit={}&offset={}",
+            complaint_id, limit, offset
+        );
+        Box::pin(async move {
+            self.do_request::<ComplaintNegotiationHistoryResponse>(&url, "GET", "")
+                .await
+        })
+    }
+    fn response_complaint(
+        &self,
+        complaint_id: &str,
+        data: ReqComplaintResponse,
+    ) -> BoxFuture<'_, ()> {
+        let url = format!(
+            "/v3/merchant-service/complaints-v2/{}/response",
+            complaint_id
+        );
+        let new_data = ReqComplaintResponse {
+            complainted_mchid: data
+                .complainted_mchid
+                .clone()
+                .or(Some(self.config.mchid.clone())),
+            ..data
+        };
+        Box::pin(async move {
+            let body = serde_json::to_string(&new_data)?;
+            self.do_request::<()>(&url, "POST", &body).await
+        })
+    }
+    fn complete_complaint(&self, complaint_id: &str) -> BoxFuture<'_, ()> {
+        let url = format!(
+            "/v3/merchant-service/complaints-v2/{}/complete",
+            complaint_id
+        );
+        let body = serde_json::json!({ "complainted_mchid": self.config.mchid }).to_string();
+        Box::pin(async move { self.do_request::<()>(&url, "POST", &body).await })
+    }
+    fn update_refund_progress(
+        &self,
+        complaint_id: &str,
+        data: ReqRefundProgress,
+    ) -> BoxFuture<'_, ()> {
+        let url = format!(
+            "/v3/merchant-service/complaints-v2/{}/update-refund-progress",
+            complaint_id
+        );
+        Box::pin(async move {
+            let body = serde_json::to_string(&data)?;
+            self.do_request::<()>(&url, "POST", &body).await
+        })
+    }
+    fn create_complaint_notification(
+        &self,
+        url: &str,
+    ) -> BoxFuture<'_, ComplaintNotificationResponse> {
+        let body = serde_json::json!({ "url": url }).to_string();
+        Box::pin(async move {
+            self.do_request::<ComplaintNotificationResponse>(
+                "/v3/merchant-service/complaint-notifications",
+                "POST",
+                &body,
+            )
+            .await
+        })
+    }
+    fn query_complaint_notification(&self) -> BoxFuture<'_, ComplaintNotificationResponse> {
+        Box::pin(async move {
+            self.do_request::<ComplaintNotificationResponse>(
+                "/v3/merchant-service/complaint-notifications",
+                "GET",
+                "",
+            )
+            .await
+        })
+    }
+    fn update_complaint_notification(
+        &self,
+        url: &str,
+    ) -> BoxFuture<'_, ComplaintNotificationResponse> {
+        let body = serde_json::json!({ "url": url }).to_string();
+        Box::pin(async move {
+            self.do_request::<ComplaintNotificationResponse>(
+                "/v3/merchant-service/complaint-notifications",
+                "PUT",
+                &body,
+            )
+            .await
+        })
+    }
+    fn delete_complaint_notification(&self) -> BoxFuture<'_, ()> {
+        Box::pin(async move {
+            self.do_request::<()>("/v3/merchant-service/complaint-notifications", "DELETE", "")
+                .await
+        })
+    }
+    fn download_complaint_image(&self, media_url: &str) -> BoxFuture<'_, Vec<u8>> {
+        let media_url = media_url.to_string();
+        Box::pin(async move {
+            let resp = self.download(&media_url).await?;
+            Ok(resp.bytes().await?.to_vec())
+        })
+    }
+    fn decrypt_payer_phone(&self, payer_phone: &str) -> WeaResult<String> {
+        let phone = rsa_decrypt_oaep(payer_phone, &self.merchant_cert().0)?;
+        String::from_utf8(phone).map_err(|_e| e("payer phone is not utf8"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_complaint_notify_event() {
+        let content =
+            r#"{"complaint_id":"200201820200101080076610000","action_type":"CREATE_COMPLAINT"}"#;
+        let event = NotifyEvent::from_content("COMPLAINT.CREATE", content).unwrap();
+        match event {
+            NotifyEvent::Complaint(complaint) => {
+                assert_eq!(complaint.complaint_id, "200201820200101080076610000");
+                assert_eq!(complaint.action_type, "CREATE_COMPLAINT");
+            }
+            _ => panic!("complaint event expected"),
+        }
+    }
+    #[test]
+    fn test_complaint_detail() {
+        let content = r#"{"complaint_id":"200201820200101080076610000","complaint_time":"2015-05-20T13:29:35.120+08:00","complaint_detail":"反馈一个重复扣费的问题","complaint_state":"PENDING","payer_phone":"sGn3/P1GjDTA8w==","complaint_order_info":[{"transaction_id":"4200000404201909069117582536","out_trade_no":"20190906154617947762231","amount":3}],"complaint_full_refunded":true,"incoming_user_response":false,"user_complaint_times":1,"complaint_media_list":[{"media_type":"USER_COMPLAINT_IMAGE","media_url":["https://api.mch.weixin.qq.com/v3/merchant-service/images/xxxxx"]}],"problem_type":"REFUND","apply_refund_amount":10}"#;
+        let detail: ComplaintDetail = serde_json::from_str(content).unwrap();
+        assert_eq!(detail.complaint_state, ComplaintState::PENDING);
+        assert_eq!(detail.complaint_order_info.unwrap()[0].amount, 3);
+        assert_eq!(detail.complaint_media_list.unwrap()[0].media_url.len(), 1);
+    }
+}
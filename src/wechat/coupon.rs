@@ -0,0 +1,397 @@
+use crate::utils::*;
+use crate::wechat::prelude::*;
+use crate::*;
+/// 代金券与商家券
+/// 代金券核销通知通过 BaseTrait::notify_event 处理,事件类型为 NotifyEvent::CouponUse
+pub trait CouponTrait {
+    /// 创建代金券批次
+    fn create_coupon_stock(&self, data: ReqCouponStock) -> BoxFuture<'_, CreateStockResponse>;
+    /// 激活代金券批次
+    fn start_coupon_stock(&self, stock_id: &str) -> BoxFuture<'_, StockActionResponse>;
+    /// 暂停代金券批次
+    fn pause_coupon_stock(&self, stock_id: &str) -> BoxFuture<'_, StockActionResponse>;
+    /// 重启代金券批次
+    fn restart_coupon_stock(&self, stock_id: &str) -> BoxFuture<'_, StockActionResponse>;
+    /// 条件查询批次列表
+    fn list_coupon_stocks(
+        &self,
+        data: ReqCouponStockList,
+    ) -> BoxFuture<'_, CouponStockListResponse>;
+    /// 查询批次详情
+    fn query_coupon_stock(&self, stock_id: &str) -> BoxFuture<'_, CouponStock>;
+    /// 发放代金券
+    fn send_coupon(&self, openid: &str, data: ReqSendCoupon) -> BoxFuture<'_, SendCouponResponse>;
+    /// 查询代金券详情
+    fn query_user_coupon(&self, openid: &str, coupon_id: &str) -> BoxFuture<'_, CouponDetail>;
+    /// 根据商户号查询用户的券
+    fn list_user_coupons(
+        &self,
+        openid: &str,
+        data: ReqUserCouponList,
+    ) -> BoxFuture<'_, UserCouponListResponse>;
+    /// 通过订单优惠详情查询对应的代金券,openid 为支付用户
+    fn query_promotion_coupon<'a>(
+        &'a self,
+        openid: &'a str,
+        promotion: &'a RespPromotionDetail,
+    ) -> BoxFuture<'a, CouponDetail>;
+    /// 设置代金券核销通知地址
+    fn set_coupon_callback(&self, notify_url: &str) -> BoxFuture<'_, CouponCallbackResponse>;
+    /// 生成小程序/H5发券插件的签名参数,使用发券商户的APIv2密钥 api_v2_key(为空时使用 mch_key)进行 HMAC-SHA256 签名
+    /// send_coupon_merchant 为空时使用配置中的 mchid
+    fn send_coupon_sign_package(
+        &self,
+        params: Vec<SendCouponParam>,
+        send_coupon_merchant: Option<String>,
+    ) -> WeaResult<SendCouponSignPackage>;
+    /// 创建商家券
+    fn create_busifavor_stock(&self, data: ReqBusifavorStock)
+        -> BoxFuture<'_, CreateStockResponse>;
+    /// 核销商家券
+    fn use_busifavor_coupon(&self, data: ReqBusifavorUse) -> BoxFuture<'_, BusifavorUseResponse>;
+    /// 退回商家券,用户退款后可将已核销的券退回
+    fn return_busifavor_coupon(
+        &self,
+        data: ReqBusifavorReturn,
+    ) -> BoxFuture<'_, BusifavorReturnResponse>;
+    /// 使商家券失效
+    fn deactivate_busifavor_coupon(
+        &self,
+        data: ReqBusifavorDeactivate,
+    ) -> BoxFuture<'_, BusifavorDeactivateResponse>;
+}
+impl CouponTrait for Payment<WechatConfig> {
+    fn create_coupon_stock(&self, data: ReqCouponStock) -> BoxFuture<'_, CreateStockResponse> {
+        let new_data = ReqCouponStock {
+            belong_merchant: data
+                .belong_merchant
+                .clone()
+                .or(Some(self.config.mchid.clone())),
+            stock_type: data.stock_type.clone().or(Some("NORMAL".to_string())),
+            ..data
+        };
+        Box::pin(async move {
+            let body = serde_json::to_string(&new_data)?;
+            self.do_request::<CreateStockResponse>(
+                "/v3/marketing/favor/coupon-stocks",
+                "POST",
+                &body,
+            )
+            .await
+        })
+    }
+    fn start_coupon_stock(&self, stock_id: &str) -> BoxFuture<'_, StockActionResponse> {
+        let url = format!("/v3/marketing/favor/stocks/{}/start", stock_id);
+        let body = serde_json::json!({ "stock_creator_mchid": self.config.mchid }).to_string();
+        Box::pin(async move {
+            self.do_request::<StockActionResponse>(&url, "POST", &body)
+                .await
+        })
+    }
+    fn pause_coupon_stock(&self, stock_id: &str) -> BoxFuture<'_, StockActionResponse> {
+        let url = format!("/v3/marketing/favor/stocks/{}/pause", stock_id);
+        let body = serde_json::json!({ "stock_creator_mchid": self.config.mchid }).to_string();
+        Box::pin(async move {
+            self.do_request::<StockActionResponse>(&url, "POST", &body)
+                .await
+        })
+    }
+    fn restart_coupon_stock(&self, stock_id: &str) -> BoxFuture<'_, StockActionResponse> {
+        let url = format!("/v3/marketing/favor/stocks/{}/restart", stock_id);
+        let body = serde_json::json!({ "stock_creator_mchid": self.config.mchid }).to_string();
+        Box::pin(async move {
+            self.do_request::<StockActionResponse>(&url, "POST", &body)
+                .await
+        })
+    }
+    fn list_coupon_stocks(
+        &self,
+        data: ReqCouponStockList,
+    ) -> BoxFuture<'_, CouponStockListResponse> {
+        Box::pin(async move {
+            let offset = data.offset.to_string();
+            let limit = data.limit.to_string();
+            let creator = data
+                .stock_creator_mchid
+                .unwrap_or(self.config.mchid.clone());
+            let mut params = vec![
+                ("offset", offset.as_str()),
+                ("limit", limit.as_str()),
+                ("stock_creator_mchid", creator.as_str()),
+            ];
+            if let Some(begin) = &data.create_start_time {
+                params.push(("create_start_time", begin));
+            }
+            if let Some(end) = &data.create_end_time {
+                params.push(("create_end_time", end));
+            }
+            if let Some(status) = &data.status {
+                params.push(("status", status.as_str()));
+            }
+            let url = with_query("/v3/marketing/favor/stocks", &params)?;
+            self.do_request::<CouponStockListResponse>(&url, "GET", "")
+                .await
+        })
+    }
+    fn query_coupon_stock(&self, stock_id: &str) -> BoxFuture<'_, CouponStock> {
+        let url = format!(
+            "/v3/marketing/favor/stocks/{}?stock_creator_mchid={}",
+            stock_id, self.config.mchid
+        );
+        Box::pin(async move { self.do_request::<CouponStock>(&url, "GET", "").await })
+    }
+    fn send_coupon(&self, openid: &str, data: ReqSendCoupon) -> BoxFuture<'_, SendCouponResponse> {
+        let url = format!("/v3/marketing/favor/users/{}/coupons", openid);
+        let new_data = ReqSendCoupon {
+            appid: data.appid.clone().or(Some(self.config.app_id.clone())),
+            stock_creator_mchid: data
+                .stock_creator_mchid
+                .clone()
+                .or(Some(self.config.mchid.clone())),
+            ..data
+        };
+        Box::pin(async move {
+            let body = serde_json::to_string(&new_data)?;
+            self.do_request::<SendCouponResponse>(&url, "POST", &body)
+                .await
+        })
+    }
+    fn query_user_coupon(&self, openid: &str, coupon_id: &str) -> BoxFuture<'_, CouponDetail> {
+        let url = format!(
+            "/v3/marketing/favor/users/{}/coupons/{}?appid={}",
+            openid, coupon_id, self.config.app_id
+        );
+        Box::pin(async move { self.do_request::<CouponDetail>(&url, "GET", "").await })
+    }
+    fn list_user_coupons(
+        &self,
+        openid: &str,
+        data: ReqUserCouponList,
+    ) -> BoxFuture<'_, UserCouponListResponse> {
+        let path = format!("/v3/marketing/favor/users/{}/coupons", openid);
+        Box::pin(async move {
+            let appid = data.appid.unwrap_or(self.config.app_id.clone());
+            let offset = data.offset.map(|v| v.to_string());
+            let limit = data.limit.map(|v| v.to_string());
+            let optional = [
+                ("stock_id", data.stock_id.as_deref()),
+                ("status", data.status.as_ref().map(|v| v.as_str())),
+                ("creator_mchid", data.creator_mchid.as_deref()),
+                ("sender_mchid", data.sender_mchid.as_deref()),
+                ("available_mchid", data.available_mchid.as_deref()),
+                ("offset", offset.as_deref()),
+                ("limit", limit.as_deref()),
+            ];
+            let mut params = vec![("appid", appid.as_str())];
+            params.extend(optional.into_iter().filter_map(|(k, v)| v.map(|v| (k, v))));
+            let url = with_query(&path, &params)?;
+            self.do_request::<UserCouponListResponse>(&url, "GET", "")
+                .await
+        })
+    }
+    fn query_promotion_coupon<'a>(
+        &'a self,
+        openid: &'a str,
+        promotion: &'a RespPromotionDetail,
+    ) -> BoxFuture<'a, CouponDetail> {
+        Box::pin(async move {
+            if promotion.promotion_type().is_none() {
+                return Err(e("promotion is not a coupon"));
+            }
+            self.query_user_coupon(openid, &promotion.coupon_id).await
+        })
+    }
+    fn set_coupon_callback(&self, notify_url: &str) -> BoxFuture<'_, CouponCallbackResponse> {
+        let body = serde_json::json!({
+            "mchid": self.config.mchid,
+            "notify_url": notify_url,
+        })
+        .to_string();
+        Box::pin(async move {
+            self.do_request::<CouponCallbackResponse>(
+                "/v3/marketing/favor/callbacks",
+                "POST",
+                &body,
+            )
+            .await
+        })
+    }
+    fn send_coupon_sign_package(
+        &self,
+        params: Vec<SendCouponParam>,
+        send_coupon_merchant: Option<String>,
+    ) -> WeaResult<SendCouponSignPackage> {
+        let send_coupon_merchant = send_coupon_merchant.unwrap_or(self.config.mchid.clone());
+        send_coupon_sign(self.config.v2_key(), params, send_coupon_merchant)
+    }
+    fn create_busifavor_stock(
+        &self,
+        data: ReqBusifavorStock,
+    ) -> BoxFuture<'_, CreateStockResponse> {
+        let new_data = ReqBusifavorStock {
+            belong_merchant: data
+                .belong_merchant
+                .clone()
+                .or(Some(self.config.mchid.clone())),
+            ..data
+        };
+        Box::pin(async move {
+            let body = serde_json::to_string(&new_data)?;
+            self.do_request::<CreateStockResponse>("/v3/marketing/busifavor/stocks", "POST", &body)
+                .await
+        })
+    }
+    fn use_busifavor_coupon(&self, data: ReqBusifavorUse) -> BoxFuture<'_, BusifavorUseResponse> {
+        let new_data = ReqBusifavorUse {
+            appid: data.appid.clone().or(Some(self.config.app_id.clone())),
+            ..data
+        };
+        Box::pin(async move {
+            let body = serde_json::to_string(&new_data)?;
+            self.do_request::<BusifavorUseResponse>(
+                "/v3/marketing/busifavor/coupons/use",
+                "POST",
+                &body,
+            )
+            .await
+        })
+    }
+    fn return_busifavor_coupon(
+        &self,
+        data: ReqBusifavorReturn,
+    ) -> BoxFuture<'_, BusifavorReturnResponse> {
+        Box::pin(async move {
+            let body = serde_json::to_string(&data)?;
+            self.do_request::<BusifavorReturnResponse>(
+                "/v3/marketing/busifavor/coupons/return",
+                "POST",
+                &body,
+            )
+            .await
+        })
+    }
+    fn deactivate_busifavor_coupon(
+        &self,
+        data: ReqBusifavorDeactivate,
+    ) -> BoxFuture<'_, BusifavorDeactivateResponse> {
+        Box::pin(async move {
+            let body = serde_json::to_string(&data)?;
+            self.do_request::<BusifavorDeactivateResponse>(
+                "/v3/marketing/busifavor/coupons/deactivate",
+                "POST",
+                &body,
+            )
+            .await
+        })
+    }
+}
+/// 发券插件签名,参数按 stock_id0、out_request_no0 等展开后按ASCII码排序拼接
+fn send_coupon_sign(
+    mch_key: &str,
+    params: Vec<SendCouponParam>,
+    send_coupon_merchant: String,
+) -> WeaResult<SendCouponSignPackage> {
+    if params.is_empty() || params.len() > 10 {
+        return Err(e("send coupon params must be 1 to 10"));
+    }
+    let mut pairs = vec![(
+        "send_coupon_merchant".to_string(),
+        send_coupon_merchant.clone(),
+    )];
+    for (index, param) in params.iter().enumerate() {
+        pairs.push((format!("stock_id{}", index), param.stock_id.clone()));
+        pairs.push((
+            format!("out_request_no{}", index),
+            param.out_request_no.clone(),
+        ));
+    }
+    pairs.sort();
+    let data = pairs
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join("&");
+    let sign = hmac_sha256(mch_key, &format!("{}&key={}", data, mch_key))?;
+    Ok(SendCouponSignPackage {
+        send_coupon_params: params,
+        send_coupon_merchant,
+        sign,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_send_coupon_sign() {
+        let params = vec![
+            SendCouponParam {
+                stock_id: "1234567".to_string(),
+                out_request_no: "1234567".to_string(),
+            },
+            SendCouponParam {
+                stock_id: "12345672".to_string(),
+                out_request_no: "12345672".to_string(),
+            },
+        ];
+        let sign_package = send_coupon_sign("key", params, "10016226".to_string()).unwrap();
+        let expected = hmac_sha256(
+            "key",
+            "out_request_no0=1234567&out_request_no1=12345672&send_coupon_merchant=10016226&stock_id0=1234567&stock_id1=12345672&key=key",
+        )
+        .unwrap();
+        assert_eq!(sign_package.sign, expected);
+        assert!(send_coupon_sign("key", vec![], "10016226".to_string()).is_err());
+    }
+    #[test]
+    fn test_send_coupon_sign_package_with_v2_key() {
+        let payment = Payment::new(WechatConfig {
+            mchid: "10016226".to_string(),
+            mch_key: "0123456789abcdef0123456789abcdef".to_string(),
+            api_v2_key: Some("192006250b4c09247ec02edce69f6a2d".to_string()),
+            ..Default::default()
+        });
+        let params = vec![SendCouponParam {
+            stock_id: "1234567".to_string(),
+            out_request_no: "1234567".to_string(),
+        }];
+        let sign_package = payment
+            .send_coupon_sign_package(params.clone(), None)
+            .unwrap();
+        let expected = send_coupon_sign(
+            "192006250b4c09247ec02edce69f6a2d",
+            params,
+            "10016226".to_string(),
+        )
+        .unwrap();
+        assert_eq!(sign_package.sign, expected.sign);
+    }
+    #[test]
+    fn test_coupon_use_notify_event() {
+        let content = r#"{"stock_creator_mchid":"9800064","stock_id":"9865888","coupon_id":"98674556","coupon_name":"微信支付代金券","status":"USED","description":"微信支付营销","create_time":"2015-05-20T13:29:35+08:00","coupon_type":"NORMAL","no_cash":true,"available_begin_time":"2015-05-20T13:29:35+08:00","available_end_time":"2015-05-20T13:29:35+08:00","singleitem":false,"normal_coupon_information":{"coupon_amount":100,"transaction_minimum":100},"consume_information":{"consume_time":"2015-05-20T13:29:35+08:00","consume_mchid":"9856081","transaction_id":"4200000000201905170000000000"}}"#;
+        match NotifyEvent::from_content("COUPON.USE", content).unwrap() {
+            NotifyEvent::CouponUse(coupon) => {
+                assert_eq!(coupon.status, CouponStatus::USED);
+                assert_eq!(
+                    coupon.consume_information.unwrap().transaction_id,
+                    "4200000000201905170000000000"
+                );
+            }
+            _ => panic!("coupon use event expected"),
+        }
+    }
+    #[test]
+    fn test_promotion_type() {
+        let promotion = RespPromotionDetail {
+            coupon_id: "109519".to_string(),
+            r#type: Some("NOCASH".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(promotion.promotion_type(), Some(PromotionType::NOCASH));
+        let promotion = RespPromotionDetail {
+            r#type: Some("DISCOUNT".to_string()),
+            ..promotion
+        };
+        assert_eq!(promotion.promotion_type(), None);
+    }
+}
@@ -0,0 +1,541 @@
+use serde::{Deserialize, Serialize};
+/// 主体类型
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SubjectType {
+    //个体户
+    SubjectTypeIndividual,
+    //企业
+    #[default]
+    SubjectTypeEnterprise,
+    //政府机关
+    SubjectTypeGovernment,
+    //事业单位
+    SubjectTypeInstitutions,
+    //社会组织
+    SubjectTypeOthers,
+}
+/// 申请单状态
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ApplymentState {
+    //编辑中
+    ApplymentStateEditting,
+    //审核中
+    #[default]
+    ApplymentStateAuditing,
+    //已驳回
+    ApplymentStateRejected,
+    //待账户验证
+    ApplymentStateToBeConfirmed,
+    //待签约
+    ApplymentStateToBeSigned,
+    //开通权限中
+    ApplymentStateSigning,
+    //已完成
+    ApplymentStateFinished,
+    //已作废
+    ApplymentStateCanceled,
+}
+/// 特约商户进件
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqApplyment {
+    //业务申请编号
+    pub business_code: String,
+    //超级管理员信息
+    pub contact_info: ApplymentContactInfo,
+    //主体资料
+    pub subject_info: ApplymentSubjectInfo,
+    //经营资料
+    pub business_info: ApplymentBusinessInfo,
+    //结算规则
+    pub settlement_info: ApplymentSettlementInfo,
+    //结算银行账户
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bank_account_info: Option<ApplymentBankAccountInfo>,
+    //补充材料
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub addition_info: Option<ApplymentAdditionInfo>,
+}
+/// 超级管理员信息,姓名、证件号码、手机号、邮箱会自动加密
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ApplymentContactInfo {
+    //超级管理员类型 LEGAL:经营者/法人 SUPER:经办人
+    pub contact_type: String,
+    //超级管理员姓名
+    pub contact_name: String,
+    //超级管理员证件类型
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact_id_doc_type: Option<String>,
+    //超级管理员身份证件号码
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact_id_number: Option<String>,
+    //超级管理员证件正面照片 media_id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact_id_doc_copy: Option<String>,
+    //超级管理员证件反面照片 media_id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact_id_doc_copy_back: Option<String>,
+    //超级管理员证件有效期开始时间
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact_period_begin: Option<String>,
+    //超级管理员证件有效期结束时间
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact_period_end: Option<String>,
+    //业务办理授权函 media_id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub business_authorization_letter: Option<String>,
+    //超级管理员微信 openid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub openid: Option<String>,
+    //联系手机
+    pub mobile_phone: String,
+    //联系邮箱
+    pub contact_email: String,
+}
+/// 主体资料
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ApplymentSubjectInfo {
+    //主体类型
+    pub subject_type: SubjectType,
+    //是否是金融机构
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finance_institution: Option<bool>,
+    //营业执照,个体户/企业必填
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub business_license_info: Option<BusinessLicenseInfo>,
+    //登记证书,政府机关/事业单位/社会组织必填
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub certificate_info: Option<CertificateInfo>,
+    //单位证明函照片 media_id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub certificate_letter_copy: Option<String>,
+    //金融机构许可证信息
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finance_institution_info: Option<FinanceInstitutionInfo>,
+    //经营者/法人身份证件
+    pub identity_info: ApplymentIdentityInfo,
+    //最终受益人信息列表
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ubo_info_list: Option<Vec<UboInfo>>,
+}
+/// 营业执照
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct BusinessLicenseInfo {
+    //营业执照照片 media_id
+    pub license_copy: String,
+    //注册号/统一社会信用代码
+    pub license_number: String,
+    //商户名称
+    pub merchant_name: String,
+    //个体户经营者/法人姓名
+    pub legal_person: String,
+    //注册地址
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license_address: Option<String>,
+    //有效期限开始日期
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub period_begin: Option<String>,
+    //有效期限结束日期
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub period_end: Option<String>,
+}
+/// 登记证书
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct CertificateInfo {
+    //登记证书照片 media_id
+    pub cert_copy: String,
+    //登记证书类型
+    pub cert_type: String,
+    //证书号
+    pub cert_number: String,
+    //商户名称
+    pub merchant_name: String,
+    //注册地址
+    pub company_address: String,
+    //法定代表人
+    pub legal_person: String,
+    //有效期限开始日期
+    pub period_begin: String,
+    //有效期限结束日期
+    pub period_end: String,
+}
+/// 金融机构许可证信息
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct FinanceInstitutionInfo {
+    //金融机构类型
+    pub finance_type: String,
+    //金融机构许可证图片 media_id 列表
+    pub finance_license_pics: Vec<String>,
+}
+/// 经营者/法人身份证件
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ApplymentIdentityInfo {
+    //证件持有人类型 LEGAL:经营者/法人 SUPER:经办人
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id_holder_type: Option<String>,
+    //证件类型
+    pub id_doc_type: String,
+    //法定代表人说明函 media_id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorize_letter_copy: Option<String>,
+    //身份证信息
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id_card_info: Option<IdCardInfo>,
+    //其他类型证件信息
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id_doc_info: Option<IdDocInfo>,
+    //经营者/法人是否为受益人
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<bool>,
+}
+/// 身份证信息,姓名、号码、居住地址会自动加密
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct IdCardInfo {
+    //身份证人像面照片 media_id
+    pub id_card_copy: String,
+    //身份证国徽面照片 media_id
+    pub id_card_national: String,
+    //身份证姓名
+    pub id_card_name: String,
+    //身份证号码
+    pub id_card_number: String,
+    //身份证居住地址
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id_card_address: Option<String>,
+    //身份证有效期开始时间
+    pub card_period_begin: String,
+    //身份证有效期结束时间
+    pub card_period_end: String,
+}
+/// 其他类型证件信息,姓名、号码、居住地址会自动加密
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct IdDocInfo {
+    //证件正面照片 media_id
+    pub id_doc_copy: String,
+    //证件反面照片 media_id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id_doc_copy_back: Option<String>,
+    //证件姓名
+    pub id_doc_name: String,
+    //证件号码
+    pub id_doc_number: String,
+    //证件居住地址
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id_doc_address: Option<String>,
+    //证件有效期开始时间
+    pub doc_period_begin: String,
+    //证件有效期结束时间
+    pub doc_period_end: String,
+}
+/// 最终受益人信息,姓名、号码、居住地址会自动加密
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct UboInfo {
+    //证件类型
+    pub ubo_id_doc_type: String,
+    //证件正面照片 media_id
+    pub ubo_id_doc_copy: String,
+    //证件反面照片 media_id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ubo_id_doc_copy_back: Option<String>,
+    //证件姓名
+    pub ubo_id_doc_name: String,
+    //证件号码
+    pub ubo_id_doc_number: String,
+    //证件居住地址
+    pub ubo_id_doc_address: String,
+    //证件有效期开始时间
+    pub ubo_period_begin: String,
+    //证件有效期结束时间
+    pub ubo_period_end: String,
+}
+/// 经营资料
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ApplymentBusinessInfo {
+    //商户简称
+    pub merchant_shortname: String,
+    //客服电话
+    pub service_phone: String,
+    //经营场景
+    pub sales_info: ApplymentSalesInfo,
+}
+/// 经营场景
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ApplymentSalesInfo {
+    //经营场景类型 SALES_SCENES_STORE/SALES_SCENES_MP/SALES_SCENES_MINI_PROGRAM 等
+    pub sales_scenes_type: Vec<String>,
+    //线下场所场景
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub biz_store_info: Option<BizStoreInfo>,
+    //公众号场景
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mp_info: Option<MpInfo>,
+    //小程序场景
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mini_program_info: Option<MiniProgramInfo>,
+    //App场景
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_info: Option<AppInfo>,
+    //互联网网站场景
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub web_info: Option<WebInfo>,
+    //企业微信场景
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wework_info: Option<WeworkInfo>,
+}
+/// 线下场所场景
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct BizStoreInfo {
+    //线下场所名称
+    pub biz_store_name: String,
+    //线下场所省市编码
+    pub biz_address_code: String,
+    //线下场所地址
+    pub biz_store_address: String,
+    //线下场所门头照片 media_id 列表
+    pub store_entrance_pic: Vec<String>,
+    //线下场所内部照片 media_id 列表
+    pub indoor_pic: Vec<String>,
+    //线下场所对应的商家APPID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub biz_sub_appid: Option<String>,
+}
+/// 公众号场景
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct MpInfo {
+    //服务商公众号APPID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mp_appid: Option<String>,
+    //商家公众号APPID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mp_sub_appid: Option<String>,
+    //公众号页面截图 media_id 列表
+    pub mp_pics: Vec<String>,
+}
+/// 小程序场景
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct MiniProgramInfo {
+    //服务商小程序APPID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mini_program_appid: Option<String>,
+    //商家小程序APPID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mini_program_sub_appid: Option<String>,
+    //小程序截图 media_id 列表
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mini_program_pics: Option<Vec<String>>,
+}
+/// App场景
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct AppInfo {
+    //服务商应用APPID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_appid: Option<String>,
+    //商家应用APPID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_sub_appid: Option<String>,
+    //App截图 media_id 列表
+    pub app_pics: Vec<String>,
+}
+/// 互联网网站场景
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct WebInfo {
+    //互联网网站域名
+    pub domain: String,
+    //网站授权函 media_id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub web_authorisation: Option<String>,
+    //互联网网站对应的商家APPID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub web_appid: Option<String>,
+}
+/// 企业微信场景
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct WeworkInfo {
+    //商家企业微信CorpID
+    pub sub_corp_id: String,
+    //企业微信页面截图 media_id 列表
+    pub wework_pics: Vec<String>,
+}
+/// 结算规则
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ApplymentSettlementInfo {
+    //入驻结算规则ID
+    pub settlement_id: String,
+    //所属行业
+    pub qualification_type: String,
+    //特殊资质图片 media_id 列表
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub qualifications: Option<Vec<String>>,
+    //优惠费率活动ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub activities_id: Option<String>,
+    //优惠费率活动值
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub activities_rate: Option<String>,
+    //优惠费率活动补充材料 media_id 列表
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub activities_additions: Option<Vec<String>>,
+    //非信用卡活动费率值
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub debit_activities_rate: Option<String>,
+    //信用卡活动费率值
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credit_activities_rate: Option<String>,
+}
+/// 结算银行账户,开户名称、银行账号会自动加密
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ApplymentBankAccountInfo {
+    //账户类型 BANK_ACCOUNT_TYPE_CORPORATE:对公 BANK_ACCOUNT_TYPE_PERSONAL:对私
+    pub bank_account_type: String,
+    //开户名称
+    pub account_name: String,
+    //开户银行
+    pub account_bank: String,
+    //开户银行省市编码
+    pub bank_address_code: String,
+    //开户银行联行号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bank_branch_id: Option<String>,
+    //开户银行全称(含支行)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bank_name: Option<String>,
+    //银行账号
+    pub account_number: String,
+}
+/// 补充材料
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ApplymentAdditionInfo {
+    //法人开户承诺函 media_id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub legal_person_commitment: Option<String>,
+    //法人开户意愿视频 media_id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub legal_person_video: Option<String>,
+    //补充材料图片 media_id 列表
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub business_addition_pics: Option<Vec<String>>,
+    //补充说明
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub business_addition_msg: Option<String>,
+}
+/// 提交申请单返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ApplymentResponse {
+    //微信支付申请单号
+    pub applyment_id: u64,
+}
+/// 驳回原因详情
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ApplymentAuditDetail {
+    //字段名
+    pub field: String,
+    //字段名称
+    pub field_name: String,
+    //驳回原因
+    pub reject_reason: String,
+}
+/// 查询申请单状态返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ApplymentQueryResponse {
+    //业务申请编号
+    pub business_code: String,
+    //微信支付申请单号
+    pub applyment_id: u64,
+    //特约商户号,完成后返回
+    pub sub_mchid: Option<String>,
+    //超级管理员签约链接
+    pub sign_url: Option<String>,
+    //申请单状态
+    pub applyment_state: ApplymentState,
+    //申请状态描述
+    pub applyment_state_msg: String,
+    //驳回原因详情
+    pub audit_detail: Option<Vec<ApplymentAuditDetail>>,
+}
+/// 银行信息
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct BankInfo {
+    //银行别名
+    pub bank_alias: String,
+    //银行别名编码,用于查询支行
+    pub bank_alias_code: String,
+    //开户银行,进件时 account_bank 填写该值
+    pub account_bank: String,
+    //开户银行编码
+    pub account_bank_code: i32,
+    //是否需要填写支行
+    pub need_bank_branch: bool,
+}
+/// 银行列表
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct BankListResponse {
+    //查询数据总条数
+    pub total_count: i32,
+    //本次查询数据条数
+    pub count: Option<i32>,
+    //银行列表
+    pub data: Option<Vec<BankInfo>>,
+    //本次查询偏移量
+    pub offset: Option<i32>,
+}
+/// 省份信息
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ProvinceInfo {
+    //省份名称
+    pub province_name: String,
+    //省份编码
+    pub province_code: i32,
+}
+/// 省份列表
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ProvinceListResponse {
+    //查询数据总条数
+    pub total_count: i32,
+    //省份列表
+    pub data: Vec<ProvinceInfo>,
+}
+/// 城市信息
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct CityInfo {
+    //城市名称
+    pub city_name: String,
+    //城市编码
+    pub city_code: i32,
+}
+/// 城市列表
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct CityListResponse {
+    //查询数据总条数
+    pub total_count: i32,
+    //城市列表
+    pub data: Vec<CityInfo>,
+}
+/// 支行信息
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct BankBranchInfo {
+    //开户银行支行名称
+    pub bank_branch_name: String,
+    //开户银行支行联行号
+    pub bank_branch_id: String,
+}
+/// 支行列表
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct BankBranchListResponse {
+    //查询数据总条数
+    pub total_count: i32,
+    //本次查询数据条数
+    pub count: i32,
+    //支行列表
+    pub data: Option<Vec<BankBranchInfo>>,
+    //本次查询偏移量
+    pub offset: i32,
+    //开户银行
+    pub account_bank: String,
+    //开户银行编码
+    pub account_bank_code: i32,
+    //银行别名
+    pub bank_alias: String,
+    //银行别名编码
+    pub bank_alias_code: String,
+}
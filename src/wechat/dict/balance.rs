@@ -0,0 +1,41 @@
+use crate::wechat::prelude::*;
+use serde::{Deserialize, Serialize};
+/// 账户余额,金额单位为分
+/// 直连商户查询时 sub_mchid 为空,服务商查询特约商户时返回特约商户号
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct FundBalanceResponse {
+    //特约商户号
+    pub sub_mchid: Option<String>,
+    //账户类型
+    pub account_type: Option<FundBillAccountType>,
+    //可用余额
+    pub available_amount: i64,
+    //不可用余额
+    pub pending_amount: Option<i64>,
+}
+impl FundBalanceResponse {
+    /// 可用余额是否足够支付 amount,单位为分
+    pub fn is_sufficient(&self, amount: i64) -> bool {
+        self.available_amount >= amount
+    }
+}
+impl From<EcommerceBalanceResponse> for FundBalanceResponse {
+    fn from(resp: EcommerceBalanceResponse) -> Self {
+        FundBalanceResponse {
+            sub_mchid: Some(resp.sub_mchid),
+            account_type: resp.account_type,
+            available_amount: resp.available_amount,
+            pending_amount: resp.pending_amount,
+        }
+    }
+}
+impl From<EcommerceEndDayBalanceResponse> for FundBalanceResponse {
+    fn from(resp: EcommerceEndDayBalanceResponse) -> Self {
+        FundBalanceResponse {
+            sub_mchid: Some(resp.sub_mchid),
+            account_type: None,
+            available_amount: resp.available_amount,
+            pending_amount: resp.pending_amount,
+        }
+    }
+}
@@ -1,4 +1,7 @@
+use crate::utils::*;
+use crate::WeaResult;
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 //bill订单相关
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct BillResponse {
@@ -9,3 +12,556 @@ pub struct BillResponse {
     //下载地址
     pub download_url: String,
 }
+/// 交易账单类型
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub enum TradeBillType {
+    //当日所有订单信息(不含充值退款订单)
+    #[default]
+    ALL,
+    //当日成功支付的订单(不含充值退款订单)
+    SUCCESS,
+    //当日退款订单(不含充值退款订单)
+    REFUND,
+}
+impl TradeBillType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TradeBillType::ALL => "ALL",
+            TradeBillType::SUCCESS => "SUCCESS",
+            TradeBillType::REFUND => "REFUND",
+        }
+    }
+}
+/// 交易账单明细,金额单位为分
+/// ALL、SUCCESS、REFUND 账单的列不同,账单中不存在的列保持默认值
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub struct TradeBillRecord {
+    //交易时间
+    pub trade_time: String,
+    //公众账号ID
+    pub appid: String,
+    //商户号
+    pub mchid: String,
+    //特约商户号
+    pub sub_mchid: String,
+    //设备号
+    pub device_info: String,
+    //微信订单号
+    pub transaction_id: String,
+    //商户订单号
+    pub out_trade_no: String,
+    //用户标识
+    pub openid: String,
+    //交易类型
+    pub trade_type: String,
+    //交易状态
+    pub trade_state: String,
+    //付款银行
+    pub bank_type: String,
+    //货币种类
+    pub currency: String,
+    //应结订单金额
+    pub settlement_total: i64,
+    //代金券金额
+    pub coupon_amount: i64,
+    //退款申请时间,仅 REFUND 账单
+    pub refund_apply_time: String,
+    //退款成功时间,仅 REFUND 账单
+    pub refund_success_time: String,
+    //微信退款单号
+    pub refund_id: String,
+    //商户退款单号
+    pub out_refund_no: String,
+    //退款金额
+    pub refund_amount: i64,
+    //充值券退款金额
+    pub coupon_refund_amount: i64,
+    //退款类型
+    pub refund_type: String,
+    //退款状态
+    pub refund_status: String,
+    //商品名称
+    pub goods_name: String,
+    //商户数据包
+    pub attach: String,
+    //手续费
+    pub fee: i64,
+    //费率
+    pub rate: String,
+    //订单金额
+    pub total_amount: i64,
+    //申请退款金额
+    pub apply_refund_amount: i64,
+    //费率备注
+    pub rate_remark: String,
+}
+/// 交易账单汇总,金额单位为分
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub struct TradeBillSummary {
+    //总交易单数
+    pub total_count: i64,
+    //应结订单总金额
+    pub settlement_total_amount: i64,
+    //退款总金额
+    pub refund_amount: i64,
+    //充值券退款总金额
+    pub coupon_refund_amount: i64,
+    //手续费总金额
+    pub fee_amount: i64,
+    //订单总金额
+    pub total_amount: i64,
+    //申请退款总金额
+    pub apply_refund_amount: i64,
+}
+/// 解析后的交易账单
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub struct TradeBill {
+    //账单明细
+    pub records: Vec<TradeBillRecord>,
+    //账单汇总
+    pub summary: TradeBillSummary,
+}
+impl TradeBill {
+    /// 解析交易账单文本
+    /// 账单第一行为表头,明细行每个字段以 ` 开头,最后两行为汇总表头及汇总数据
+    pub fn parse(content: &str) -> WeaResult<TradeBill> {
+        let mut lines = bill_lines(content);
+        let header = lines.next().ok_or(e("bill header not found"))?;
+        let header = split_bill_header(header);
+        let mut records = Vec::new();
+        let mut summary = None;
+        while let Some(line) = lines.next() {
+            if !line.starts_with('`') {
+                let summary_header = split_bill_header(line);
+                let row = lines.next().ok_or(e("bill summary not found"))?;
+                summary = Some(parse_trade_bill_summary(
+                    &summary_header,
+                    &split_bill_row(row),
+                )?);
+                break;
+            }
+            records.push(parse_trade_bill_record(&header, &split_bill_row(line))?);
+        }
+        Ok(TradeBill {
+            records,
+            summary: summary.ok_or(e("bill summary not found"))?,
+        })
+    }
+    /// 从文件解析交易账单,gzip 压缩的文件会先解压
+    pub fn from_file<P: AsRef<Path>>(path: P) -> WeaResult<TradeBill> {
+        let content = read_bill_file(path)?;
+        TradeBill::parse(&content)
+    }
+}
+/// 资金账户类型
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub enum FundBillAccountType {
+    //基本账户
+    #[default]
+    BASIC,
+    //运营账户
+    OPERATION,
+    //手续费账户
+    FEES,
+}
+impl FundBillAccountType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FundBillAccountType::BASIC => "BASIC",
+            FundBillAccountType::OPERATION => "OPERATION",
+            FundBillAccountType::FEES => "FEES",
+        }
+    }
+}
+/// 资金账单明细,金额单位为分
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub struct FundFlowBillRecord {
+    //记账时间
+    pub bill_time: String,
+    //微信支付业务单号
+    pub transaction_id: String,
+    //资金流水单号
+    pub flow_id: String,
+    //业务名称
+    pub business_name: String,
+    //业务类型
+    pub business_type: String,
+    //收支类型 收入/支出
+    pub flow_type: String,
+    //收支金额
+    pub amount: i64,
+    //账户结余
+    pub balance: i64,
+    //资金变更提交申请人
+    pub applicant: String,
+    //备注
+    pub remark: String,
+    //业务凭证号
+    pub voucher_no: String,
+}
+/// 资金账单汇总,金额单位为分
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub struct FundFlowBillSummary {
+    //资金流水总笔数
+    pub total_count: i64,
+    //收入笔数
+    pub income_count: i64,
+    //收入金额
+    pub income_amount: i64,
+    //支出笔数
+    pub expense_count: i64,
+    //支出金额
+    pub expense_amount: i64,
+}
+/// 解析后的资金账单
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub struct FundFlowBill {
+    //账单明细
+    pub records: Vec<FundFlowBillRecord>,
+    //账单汇总
+    pub summary: FundFlowBillSummary,
+}
+impl FundFlowBill {
+    /// 解析资金账单文本
+    /// 分片账单拼接后解析时,重复的表头会被忽略,多个汇总行会累加
+    pub fn parse(content: &str) -> WeaResult<FundFlowBill> {
+        let mut lines = bill_lines(content);
+        let header_line = lines.next().ok_or(e("bill header not found"))?;
+        let header = split_bill_header(header_line);
+        let mut records = Vec::new();
+        let mut summary: Option<FundFlowBillSummary> = None;
+        while let Some(line) = lines.next() {
+            if line == header_line {
+                continue;
+            }
+            if !line.starts_with('`') {
+                let summary_header = split_bill_header(line);
+                let row = lines.next().ok_or(e("bill summary not found"))?;
+                let part = parse_fund_bill_summary(&summary_header, &split_bill_row(row))?;
+                let total = summary.get_or_insert_with(FundFlowBillSummary::default);
+                total.total_count += part.total_count;
+                total.income_count += part.income_count;
+                total.income_amount += part.income_amount;
+                total.expense_count += part.expense_count;
+                total.expense_amount += part.expense_amount;
+                continue;
+            }
+            records.push(parse_fund_bill_record(&header, &split_bill_row(line))?);
+        }
+        Ok(FundFlowBill {
+            records,
+            summary: summary.ok_or(e("bill summary not found"))?,
+        })
+    }
+    /// 从文件解析资金账单,gzip 压缩的文件会先解压
+    pub fn from_file<P: AsRef<Path>>(path: P) -> WeaResult<FundFlowBill> {
+        let content = read_bill_file(path)?;
+        FundFlowBill::parse(&content)
+    }
+}
+/// 二级商户资金账单申请结果
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct SubMerchantFundBillResponse {
+    //下载信息总数
+    pub download_bill_count: i32,
+    //下载信息明细
+    pub download_bill_list: Vec<EncryptedBillItem>,
+}
+/// 加密账单分片下载信息
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct EncryptedBillItem {
+    //账单文件序号
+    pub bill_sequence: i32,
+    //下载地址
+    pub download_url: String,
+    //加密密钥,使用商户证书公钥加密后 base64 编码
+    pub encrypt_key: String,
+    //哈希类型
+    pub hash_type: String,
+    //哈希值
+    pub hash_value: String,
+    //随机字符串
+    pub nonce: String,
+}
+impl EncryptedBillItem {
+    /// 解密下载的账单分片,private_key 为商户私钥文件路径
+    /// gzip 为 true 时解密后再解压,返回校验摘要后的账单内容
+    pub fn decrypt(&self, content: &[u8], private_key: &str, gzip: bool) -> WeaResult<Vec<u8>> {
+        let key = rsa_decrypt_oaep(&self.encrypt_key, private_key)?;
+        let content = aes_gcm_decrypt(&key, self.nonce.as_bytes(), b"", content)?;
+        let content = if gzip { gunzip(&content)? } else { content };
+        let digest = hex_digest(&self.hash_type, &content)?;
+        check_digest(&digest, &self.hash_value)?;
+        Ok(content)
+    }
+}
+/// 读取账单文件,gzip 压缩的文件会先解压
+pub(crate) fn read_bill_file<P: AsRef<Path>>(path: P) -> WeaResult<String> {
+    let content = std::fs::read(path)?;
+    let content = if content.starts_with(&[0x1f, 0x8b]) {
+        gunzip(&content)?
+    } else {
+        content
+    };
+    Ok(String::from_utf8(content)?)
+}
+/// 按行拆分账单,去掉 BOM 及空行
+pub(crate) fn bill_lines(content: &str) -> impl Iterator<Item = &str> {
+    content
+        .trim_start_matches('\u{feff}')
+        .lines()
+        .map(|line| line.trim_end_matches('\r'))
+        .filter(|line| !line.trim().is_empty())
+}
+/// 追加解密后的账单分片,分片之间以换行分隔,避免后一分片的表头与前一分片末行相连
+pub(crate) fn append_bill_part(merged: &mut Vec<u8>, part: Vec<u8>) {
+    if !merged.is_empty() && !merged.ends_with(b"\n") {
+        merged.push(b'\n');
+    }
+    merged.extend(part);
+}
+/// 拆分账单表头
+pub(crate) fn split_bill_header(line: &str) -> Vec<&str> {
+    line.split(',').map(|name| name.trim()).collect()
+}
+/// 拆分账单数据行,字段以 ` 开头,按 ",`" 拆分以兼容字段中的逗号
+pub(crate) fn split_bill_row(line: &str) -> Vec<&str> {
+    line.strip_prefix('`')
+        .unwrap_or(line)
+        .split(",`")
+        .map(|value| value.trim())
+        .collect()
+}
+fn parse_trade_bill_record(header: &[&str], row: &[&str]) -> WeaResult<TradeBillRecord> {
+    let mut record = TradeBillRecord::default();
+    for (name, value) in header.iter().zip(row.iter()) {
+        let value = value.to_string();
+        match *name {
+            "交易时间" => record.trade_time = value,
+            "公众账号ID" => record.appid = value,
+            "商户号" => record.mchid = value,
+            "特约商户号" | "子商户号" => record.sub_mchid = value,
+            "设备号" => record.device_info = value,
+            "微信订单号" => record.transaction_id = value,
+            "商户订单号" => record.out_trade_no = value,
+            "用户标识" => record.openid = value,
+            "交易类型" => record.trade_type = value,
+            "交易状态" => record.trade_state = value,
+            "付款银行" => record.bank_type = value,
+            "货币种类" => record.currency = value,
+            "应结订单金额" => record.settlement_total = yuan_to_fen(&value)?,
+            "代金券金额" => record.coupon_amount = yuan_to_fen(&value)?,
+            "退款申请时间" => record.refund_apply_time = value,
+            "退款成功时间" => record.refund_success_time = value,
+            "微信退款单号" => record.refund_id = value,
+            "商户退款单号" => record.out_refund_no = value,
+            "退款金额" => record.refund_amount = yuan_to_fen(&value)?,
+            "充值券退款金额" => record.coupon_refund_amount = yuan_to_fen(&value)?,
+            "退款类型" => record.refund_type = value,
+            "退款状态" => record.refund_status = value,
+            "商品名称" => record.goods_name = value,
+            "商户数据包" => record.attach = value,
+            "手续费" => record.fee = yuan_to_fen(&value)?,
+            "费率" => record.rate = value,
+            "订单金额" => record.total_amount = yuan_to_fen(&value)?,
+            "申请退款金额" => record.apply_refund_amount = yuan_to_fen(&value)?,
+            "费率备注" => record.rate_remark = value,
+            _ => {}
+        }
+    }
+    Ok(record)
+}
+fn parse_trade_bill_summary(header: &[&str], row: &[&str]) -> WeaResult<TradeBillSummary> {
+    let mut summary = TradeBillSummary::default();
+    for (name, value) in header.iter().zip(row.iter()) {
+        match *name {
+            "总交易单数" => {
+                summary.total_count = value
+                    .parse()
+                    .map_err(|_e| e(&format!("invalid total count {}", value)))?
+            }
+            "应结订单总金额" => summary.settlement_total_amount = yuan_to_fen(value)?,
+            "退款总金额" => summary.refund_amount = yuan_to_fen(value)?,
+            "充值券退款总金额" => summary.coupon_refund_amount = yuan_to_fen(value)?,
+            "手续费总金额" => summary.fee_amount = yuan_to_fen(value)?,
+            "订单总金额" => summary.total_amount = yuan_to_fen(value)?,
+            "申请退款总金额" => summary.apply_refund_amount = yuan_to_fen(value)?,
+            _ => {}
+        }
+    }
+    Ok(summary)
+}
+
+fn parse_fund_bill_record(header: &[&str], row: &[&str]) -> WeaResult<FundFlowBillRecord> {
+    let mut record = FundFlowBillRecord::default();
+    for (name, value) in header.iter().zip(row.iter()) {
+        let value = value.to_string();
+        match *name {
+            "记账时间" => record.bill_time = value,
+            "微信支付业务单号" => record.transaction_id = value,
+            "资金流水单号" => record.flow_id = value,
+            "业务名称" => record.business_name = value,
+            "业务类型" => record.business_type = value,
+            "收支类型" => record.flow_type = value,
+            "收支金额（元）" | "收支金额(元)" => record.amount = yuan_to_fen(&value)?,
+            "账户结余（元）" | "账户结余(元)" => record.balance = yuan_to_fen(&value)?,
+            "资金变更提交申请人" => record.applicant = value,
+            "备注" => record.remark = value,
+            "业务凭证号" => record.voucher_no = value,
+            _ => {}
+        }
+    }
+    Ok(record)
+}
+fn parse_fund_bill_summary(header: &[&str], row: &[&str]) -> WeaResult<FundFlowBillSummary> {
+    let mut summary = FundFlowBillSummary::default();
+    let count = |value: &str| {
+        value
+            .parse::<i64>()
+            .map_err(|_e| e(&format!("invalid count {}", value)))
+    };
+    for (name, value) in header.iter().zip(row.iter()) {
+        match *name {
+            "资金流水总笔数" => summary.total_count = count(value)?,
+            "收入笔数" => summary.income_count = count(value)?,
+            "收入金额" => summary.income_amount = yuan_to_fen(value)?,
+            "支出笔数" => summary.expense_count = count(value)?,
+            "支出金额" => summary.expense_amount = yuan_to_fen(value)?,
+            _ => {}
+        }
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const ALL_BILL: &str = "\u{feff}交易时间,公众账号ID,商户号,特约商户号,设备号,微信订单号,商户订单号,用户标识,交易类型,交易状态,付款银行,货币种类,应结订单金额,代金券金额,微信退款单号,商户退款单号,退款金额,充值券退款金额,退款类型,退款状态,商品名称,商户数据包,手续费,费率,订单金额,申请退款金额,费率备注\r
+`2024-07-01 10:00:00,`wx8888888888888888,`1900000100,`1900000109,`,`4200000001202407010000000001,`T20240701001,`oUpF8uMuAJO_M2pxb1Q9zNjWeS6o,`JSAPI,`SUCCESS,`OTHERS,`CNY,`1.00,`0.00,`0,`0,`0.00,`0.00,`,`,`咖啡,豆浆,`attach,`0.01,`0.60%,`1.00,`0.00,`\r
+`2024-07-01 11:00:00,`wx8888888888888888,`1900000100,`1900000109,`,`4200000001202407010000000002,`T20240701002,`oUpF8uMuAJO_M2pxb1Q9zNjWeS6o,`JSAPI,`REFUND,`OTHERS,`CNY,`0.00,`0.00,`50300000012024070100000001,`R20240701001,`2.50,`0.00,`ORIGINAL,`SUCCESS,`面包,`,`-0.02,`0.60%,`2.50,`2.50,`\r
+总交易单数,应结订单总金额,退款总金额,充值券退款总金额,手续费总金额,订单总金额,申请退款总金额\r
+`2,`1.00,`2.50,`0.00,`-0.01,`3.50,`2.50\r
+";
+    #[test]
+    fn test_parse_trade_bill() {
+        let bill = TradeBill::parse(ALL_BILL).unwrap();
+        assert_eq!(bill.records.len(), 2);
+        let record = &bill.records[0];
+        assert_eq!(record.trade_time, "2024-07-01 10:00:00");
+        assert_eq!(record.sub_mchid, "1900000109");
+        assert_eq!(record.goods_name, "咖啡,豆浆");
+        assert_eq!(record.settlement_total, 100);
+        assert_eq!(record.fee, 1);
+        let record = &bill.records[1];
+        assert_eq!(record.trade_state, "REFUND");
+        assert_eq!(record.out_refund_no, "R20240701001");
+        assert_eq!(record.refund_amount, 250);
+        assert_eq!(record.fee, -2);
+        assert_eq!(bill.summary.total_count, 2);
+        assert_eq!(bill.summary.total_amount, 350);
+        assert_eq!(bill.summary.fee_amount, -1);
+    }
+    #[test]
+    fn test_parse_trade_bill_from_file() {
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+        let success_bill = "交易时间,公众账号ID,商户号,特约商户号,设备号,微信订单号,商户订单号,用户标识,交易类型,交易状态,付款银行,货币种类,应结订单金额,代金券金额,商品名称,商户数据包,手续费,费率,订单金额,费率备注
+`2024-07-01 10:00:00,`wx8888888888888888,`1900000100,`0,`,`4200000001202407010000000001,`T20240701001,`oUpF8uMuAJO_M2pxb1Q9zNjWeS6o,`NATIVE,`SUCCESS,`CMB_DEBIT,`CNY,`100.00,`0.00,`会员,`,`0.60,`0.60%,`100.00,`
+总交易单数,应结订单总金额,退款总金额,充值券退款总金额,手续费总金额,订单总金额,申请退款总金额
+`1,`100.00,`0.00,`0.00,`0.60,`100.00,`0.00
+";
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(success_bill.as_bytes()).unwrap();
+        let path = crate::utils::write_temp_file("success_bill.csv.gz", &encoder.finish().unwrap());
+        let bill = TradeBill::from_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(bill.records.len(), 1);
+        assert_eq!(bill.records[0].bank_type, "CMB_DEBIT");
+        assert_eq!(bill.records[0].fee, 60);
+        assert_eq!(bill.records[0].refund_amount, 0);
+        assert_eq!(bill.summary.settlement_total_amount, 10000);
+    }
+    #[test]
+    fn test_parse_trade_bill_without_summary() {
+        let content = ALL_BILL.split("总交易单数").next().unwrap();
+        assert!(TradeBill::parse(content).is_err());
+    }
+    const FUND_BILL: &str = "记账时间,微信支付业务单号,资金流水单号,业务名称,业务类型,收支类型,收支金额（元）,账户结余（元）,资金变更提交申请人,备注,业务凭证号
+`2024-07-01 10:00:01,`4200000001202407010000000001,`4200000001202407010000000001,`交易,`交易,`收入,`1.00,`101.00,`system,`,`4200000001202407010000000001
+`2024-07-01 11:00:01,`50300000012024070100000001,`50300000012024070100000001,`退款,`退款,`支出,`0.50,`100.50,`system,`,`R20240701001
+资金流水总笔数,收入笔数,收入金额,支出笔数,支出金额
+`2,`1,`1.00,`1,`0.50
+";
+    #[test]
+    fn test_parse_fund_flow_bill() {
+        let bill = FundFlowBill::parse(FUND_BILL).unwrap();
+        assert_eq!(bill.records.len(), 2);
+        assert_eq!(bill.records[0].flow_type, "收入");
+        assert_eq!(bill.records[0].balance, 10100);
+        assert_eq!(bill.records[1].amount, 50);
+        assert_eq!(bill.records[1].voucher_no, "R20240701001");
+        assert_eq!(bill.summary.total_count, 2);
+        assert_eq!(bill.summary.expense_amount, 50);
+    }
+    #[test]
+    fn test_decrypt_sub_merchant_bill() {
+        use aes_gcm::{aead::Aead, Aes256Gcm, KeyInit, Nonce};
+        use flate2::{write::GzEncoder, Compression};
+        use openssl::{base64::encode_block, rsa::Padding};
+        use std::io::Write;
+        let (pkey, key_file, cert_file) = crate::utils::write_test_cert("bill_apiclient", 1);
+        let rsa = pkey.rsa().unwrap();
+        let key_file = key_file.as_str();
+
+        let mut parts = Vec::new();
+        let mut items = Vec::new();
+        for (i, part) in FUND_BILL.split_inclusive("R20240701001").enumerate() {
+            // 第二个分片带上表头,模拟分片账单
+            let part = if i == 0 {
+                part.to_string()
+            } else {
+                format!("{}\n{}", FUND_BILL.lines().next().unwrap(), part)
+            };
+            let aes_key = generate_random_string(32);
+            let nonce = generate_random_string(12);
+            let mut encrypt_key = vec![0u8; rsa.size() as usize];
+            let len = rsa
+                .public_encrypt(aes_key.as_bytes(), &mut encrypt_key, Padding::PKCS1_OAEP)
+                .unwrap();
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(part.as_bytes()).unwrap();
+            let cipher = Aes256Gcm::new_from_slice(aes_key.as_bytes()).unwrap();
+            let content = cipher
+                .encrypt(
+                    Nonce::from_slice(nonce.as_bytes()),
+                    encoder.finish().unwrap().as_slice(),
+                )
+                .unwrap();
+            parts.push(content);
+            items.push(EncryptedBillItem {
+                bill_sequence: i as i32 + 1,
+                encrypt_key: encode_block(&encrypt_key[..len]),
+                hash_type: "SHA1".to_string(),
+                hash_value: hex_digest("SHA1", part.as_bytes()).unwrap(),
+                nonce,
+                ..Default::default()
+            });
+        }
+        assert_eq!(items.len(), 2);
+        let mut merged = Vec::new();
+        for (item, content) in items.iter().zip(parts.iter()) {
+            append_bill_part(&mut merged, item.decrypt(content, key_file, true).unwrap());
+        }
+        let bill = FundFlowBill::parse(&String::from_utf8(merged).unwrap()).unwrap();
+        assert_eq!(bill.records.len(), 2);
+        assert_eq!(bill.records[1].voucher_no, "R20240701001");
+        assert_eq!(bill.summary.income_count, 1);
+
+        let mut tampered = items[0].clone();
+        tampered.hash_value = hex_digest("SHA1", b"tampered").unwrap();
+        assert!(tampered.decrypt(&parts[0], key_file, true).is_err());
+        assert!(items[0].decrypt(&parts[1], key_file, true).is_err());
+        std::fs::remove_file(key_file).unwrap();
+        std::fs::remove_file(cert_file).unwrap();
+    }
+}
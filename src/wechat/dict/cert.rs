@@ -1,4 +1,8 @@
+use crate::utils::read_pkey;
+use crate::WeaResult;
+use openssl::x509::X509;
 use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
 
 //证书下载返回数据
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -31,3 +35,81 @@ pub struct CertData {
     //证书内容
     pub ciphertext: String,
 }
+/// 签名及加密算法
+/// RSA: WECHATPAY2-SHA256-RSA2048 签名,AEAD_AES_256_GCM 解密
+/// SM2: WECHATPAY2-SM2-WITH-SM3 签名,AEAD_SM4_GCM 解密,商户私钥及平台证书均为国密证书
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub enum WechatSignType {
+    #[default]
+    RSA,
+    SM2,
+}
+impl WechatSignType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WechatSignType::RSA => "RSA",
+            WechatSignType::SM2 => "SM2",
+        }
+    }
+    /// Authorization 认证类型
+    pub fn schema(&self) -> &'static str {
+        match self {
+            WechatSignType::RSA => "WECHATPAY2-SHA256-RSA2048",
+            WechatSignType::SM2 => "WECHATPAY2-SM2-WITH-SM3",
+        }
+    }
+    /// 通知及证书的加密算法
+    pub fn algorithm(&self) -> &'static str {
+        match self {
+            WechatSignType::RSA => "AEAD_AES_256_GCM",
+            WechatSignType::SM2 => "AEAD_SM4_GCM",
+        }
+    }
+    /// 根据通知或证书中的 algorithm 字段获取对应的签名类型
+    pub fn from_algorithm(algorithm: &str) -> Option<WechatSignType> {
+        [WechatSignType::RSA, WechatSignType::SM2]
+            .into_iter()
+            .find(|sign_type| sign_type.algorithm() == algorithm)
+    }
+}
+/// 商户API私钥及证书文件路径,可在运行时替换
+/// 克隆后共享同一份数据,调用 update 后所有持有该配置的实例立即使用新的私钥及证书
+#[derive(Clone, Debug, Default)]
+pub struct MerchantCert {
+    inner: Arc<RwLock<(String, String)>>,
+}
+impl MerchantCert {
+    pub fn new(apiclient_key: impl Into<String>, apiclient_cert: impl Into<String>) -> Self {
+        MerchantCert {
+            inner: Arc::new(RwLock::new((apiclient_key.into(), apiclient_cert.into()))),
+        }
+    }
+    /// 替换私钥及证书,私钥与证书公钥不匹配时返回错误且不替换
+    pub fn update(
+        &self,
+        apiclient_key: impl Into<String>,
+        apiclient_cert: impl Into<String>,
+    ) -> WeaResult<()> {
+        let (apiclient_key, apiclient_cert) = (apiclient_key.into(), apiclient_cert.into());
+        let pkey = read_pkey(&apiclient_key)?;
+        let cert = X509::from_pem(&std::fs::read(&apiclient_cert)?)?;
+        if !cert.public_key()?.public_eq(&pkey) {
+            return Err(crate::utils::e(
+                "apiclient key does not match apiclient cert",
+            ));
+        }
+        let mut inner = self
+            .inner
+            .write()
+            .unwrap_or_else(|error| error.into_inner());
+        *inner = (apiclient_key, apiclient_cert);
+        Ok(())
+    }
+    /// 返回当前的 (私钥路径,证书路径)
+    pub fn get(&self) -> (String, String) {
+        self.inner
+            .read()
+            .unwrap_or_else(|error| error.into_inner())
+            .clone()
+    }
+}
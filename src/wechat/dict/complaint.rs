@@ -0,0 +1,198 @@
+use serde::{Deserialize, Serialize};
+/// 投诉单状态
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub enum ComplaintState {
+    //待处理
+    #[default]
+    PENDING,
+    //处理中
+    PROCESSING,
+    //已处理完成
+    PROCESSED,
+}
+/// 投诉单处理动作
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub enum RefundProgressAction {
+    //同意退款
+    APPROVE,
+    //拒绝退款
+    REJECT,
+}
+/// 查询投诉单列表
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqComplaintList {
+    //分页大小,默认10,最大50
+    pub limit: Option<i32>,
+    //分页开始位置
+    pub offset: Option<i32>,
+    //开始日期 yyyy-MM-dd
+    pub begin_date: String,
+    //结束日期 yyyy-MM-dd,与开始日期间隔不超过30天
+    pub end_date: String,
+    //被诉商户号,服务商模式下为空时使用配置中的 mchid
+    pub complainted_mchid: Option<String>,
+}
+/// 投诉单关联订单
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ComplaintOrderInfo {
+    //微信订单号
+    pub transaction_id: String,
+    //商户订单号
+    pub out_trade_no: String,
+    //订单金额
+    pub amount: i32,
+}
+/// 投诉资料
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ComplaintMedia {
+    //媒体文件业务类型 USER_COMPLAINT_IMAGE/OPERATION_IMAGE
+    pub media_type: String,
+    //媒体文件请求url,通过 ComplaintTrait::download_complaint_image 下载
+    pub media_url: Vec<String>,
+}
+/// 投诉单详情
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ComplaintDetail {
+    //投诉单号
+    pub complaint_id: String,
+    //投诉时间
+    pub complaint_time: String,
+    //投诉详情
+    pub complaint_detail: String,
+    //投诉单状态
+    pub complaint_state: ComplaintState,
+    //被诉商户号
+    pub complainted_mchid: Option<String>,
+    //投诉人联系方式,查询接口返回时已使用商户私钥解密
+    pub payer_phone: Option<String>,
+    //投诉人openid
+    pub payer_openid: Option<String>,
+    //投诉单关联订单信息
+    pub complaint_order_info: Option<Vec<ComplaintOrderInfo>>,
+    //投诉单是否已全额退款
+    pub complaint_full_refunded: bool,
+    //是否有待回复的用户留言
+    pub incoming_user_response: bool,
+    //用户投诉次数
+    pub user_complaint_times: i32,
+    //投诉资料列表
+    pub complaint_media_list: Option<Vec<ComplaintMedia>>,
+    //问题描述
+    pub problem_description: Option<String>,
+    //问题类型 REFUND/SERVICE_NOT_WORK/OTHERS
+    pub problem_type: Option<String>,
+    //申请退款金额
+    pub apply_refund_amount: Option<i32>,
+    //用户标签列表
+    pub user_tag_list: Option<Vec<String>>,
+}
+/// 投诉单列表
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ComplaintListResponse {
+    //用户投诉信息详情
+    pub data: Option<Vec<ComplaintDetail>>,
+    //分页大小
+    pub limit: i32,
+    //分页开始位置
+    pub offset: i32,
+    //投诉总条数
+    pub total_count: Option<i32>,
+}
+/// 协商历史
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ComplaintNegotiationHistory {
+    //操作流水号
+    pub log_id: String,
+    //操作人
+    pub operator: String,
+    //操作时间
+    pub operate_time: String,
+    //操作类型
+    pub operate_type: String,
+    //操作内容
+    pub operate_details: Option<String>,
+    //图片凭证
+    pub image_list: Option<Vec<String>>,
+    //投诉资料
+    pub complaint_media_list: Option<ComplaintMedia>,
+}
+/// 协商历史列表
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ComplaintNegotiationHistoryResponse {
+    //投诉协商历史
+    pub data: Option<Vec<ComplaintNegotiationHistory>>,
+    //分页大小
+    pub limit: i32,
+    //分页开始位置
+    pub offset: i32,
+    //投诉协商历史总条数
+    pub total_count: Option<i32>,
+}
+/// 回复用户
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqComplaintResponse {
+    //被诉商户号,为空时使用配置中的 mchid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub complainted_mchid: Option<String>,
+    //回复内容
+    pub response_content: String,
+    //回复图片 media_id 列表,通过图片上传接口获取
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_images: Option<Vec<String>>,
+    //跳转链接
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jump_url: Option<String>,
+    //跳转链接文案
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jump_url_text: Option<String>,
+}
+/// 更新退款审批结果
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReqRefundProgress {
+    //审批动作
+    pub action: RefundProgressAction,
+    //预计发起退款时间,同意退款时填写
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub launch_refund_day: Option<i32>,
+    //拒绝退款原因
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reject_reason: Option<String>,
+    //拒绝退款的举证图片 media_id 列表
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reject_media_list: Option<Vec<String>>,
+    //备注
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remark: Option<String>,
+}
+/// 投诉通知回调地址
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ComplaintNotificationResponse {
+    //商户号
+    pub mchid: String,
+    //通知地址
+    pub url: String,
+}
+/// 投诉通知解密后的内容
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ResourceComplaintBody {
+    //投诉单号
+    pub complaint_id: String,
+    //动作类型 CREATE_COMPLAINT/CONTINUE_COMPLAINT/USER_RESPONSE/RESPONSE_BY_PLATFORM/SELLER_REFUND/MERCHANT_RESPONSE/MERCHANT_CONFIRM_COMPLETE 等
+    pub action_type: String,
+    //商户订单号
+    pub out_trade_no: Option<String>,
+    //投诉时间
+    pub complaint_time: Option<String>,
+    //订单金额
+    pub amount: Option<i32>,
+    //投诉人联系方式,已加密,可使用 ComplaintTrait::decrypt_payer_phone 解密
+    pub payer_phone: Option<String>,
+    //投诉详情
+    pub complaint_detail: Option<String>,
+    //问题类型
+    pub problem_type: Option<String>,
+    //问题描述
+    pub problem_description: Option<String>,
+    //申请退款金额
+    pub apply_refund_amount: Option<i32>,
+}
@@ -0,0 +1,629 @@
+use serde::{Deserialize, Serialize};
+/// 代金券批次状态
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum StockStatus {
+    //未激活
+    #[default]
+    UNACTIVATED,
+    //审核中
+    AUDIT,
+    //运行中
+    RUNNING,
+    //已停止
+    STOPED,
+    //暂停发放
+    PAUSED,
+}
+impl StockStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StockStatus::UNACTIVATED => "unactivated",
+            StockStatus::AUDIT => "audit",
+            StockStatus::RUNNING => "running",
+            StockStatus::STOPED => "stoped",
+            StockStatus::PAUSED => "paused",
+        }
+    }
+}
+/// 代金券状态
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub enum CouponStatus {
+    //可用
+    #[default]
+    SENDED,
+    //已实扣
+    USED,
+    //已过期
+    EXPIRED,
+}
+impl CouponStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CouponStatus::SENDED => "SENDED",
+            CouponStatus::USED => "USED",
+            CouponStatus::EXPIRED => "EXPIRED",
+        }
+    }
+}
+/// 订单优惠类型
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub enum PromotionType {
+    //充值型代金券
+    CASH,
+    //免充值型代金券
+    NOCASH,
+}
+/// 固定面额满减券
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct FixedNormalCoupon {
+    //面额
+    pub coupon_amount: i64,
+    //门槛
+    pub transaction_minimum: i64,
+}
+/// 代金券发放规则
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct StockUseRule {
+    //发放总上限
+    pub max_coupons: i64,
+    //总预算
+    pub max_amount: i64,
+    //单天预算发放上限
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_amount_by_day: Option<i64>,
+    //单个用户可领个数
+    pub max_coupons_per_user: i32,
+    //是否开启自然人限制
+    pub natural_person_limit: bool,
+    //是否开启防刷拦截
+    pub prevent_api_abuse: bool,
+}
+/// 代金券样式
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct CouponPatternInfo {
+    //使用说明
+    pub description: String,
+    //商户logo
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merchant_logo: Option<String>,
+    //品牌名称
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merchant_name: Option<String>,
+    //背景颜色
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background_color: Option<String>,
+    //券详情图片
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coupon_image: Option<String>,
+}
+/// 代金券核销规则
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct CouponUseRule {
+    //固定面额满减券使用规则
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fixed_normal_coupon: Option<FixedNormalCoupon>,
+    //订单优惠标记
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goods_tag: Option<Vec<String>>,
+    //支付方式
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit_pay: Option<Vec<String>>,
+    //支付方式 MICROAPP/APPPAY/PPAY/CARD/FACE/OTHER
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trade_type: Option<Vec<String>>,
+    //是否可叠加其他优惠
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub combine_use: Option<bool>,
+    //可核销商品编码
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub available_items: Option<Vec<String>>,
+    //不参与优惠商品编码
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unavailable_items: Option<Vec<String>>,
+    //可用商户号
+    pub available_merchants: Vec<String>,
+}
+/// 创建代金券批次
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqCouponStock {
+    //批次名称
+    pub stock_name: String,
+    //批次备注
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    //归属商户号,为空时使用配置中的 mchid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub belong_merchant: Option<String>,
+    //可用时间开始
+    pub available_begin_time: String,
+    //可用时间结束
+    pub available_end_time: String,
+    //发放规则
+    pub stock_use_rule: StockUseRule,
+    //样式设置
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern_info: Option<CouponPatternInfo>,
+    //核销规则
+    pub coupon_use_rule: CouponUseRule,
+    //营销经费 true:免充值 false:预充值
+    pub no_cash: bool,
+    //批次类型,为空时使用 NORMAL
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stock_type: Option<String>,
+    //商户单据号
+    pub out_request_no: String,
+    //扩展属性
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext_info: Option<String>,
+}
+/// 创建批次返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct CreateStockResponse {
+    //批次号
+    pub stock_id: String,
+    //创建时间
+    pub create_time: String,
+}
+/// 激活、暂停、重启批次返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct StockActionResponse {
+    //批次号
+    pub stock_id: String,
+    //生效时间
+    pub start_time: Option<String>,
+    //暂停时间
+    pub pause_time: Option<String>,
+    //重启时间
+    pub restart_time: Option<String>,
+}
+/// 条件查询批次列表
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqCouponStockList {
+    //分页页码
+    pub offset: i32,
+    //分页大小,最大10
+    pub limit: i32,
+    //创建批次的商户号,为空时使用配置中的 mchid
+    pub stock_creator_mchid: Option<String>,
+    //起始时间
+    pub create_start_time: Option<String>,
+    //终止时间
+    pub create_end_time: Option<String>,
+    //批次状态
+    pub status: Option<StockStatus>,
+}
+/// 批次使用规则
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct StockUseRuleInfo {
+    //发放总上限
+    pub max_coupons: i64,
+    //总预算
+    pub max_amount: i64,
+    //单天发放上限金额
+    pub max_amount_by_day: Option<i64>,
+    //固定面额批次特定信息
+    pub fixed_normal_coupon: Option<FixedNormalCoupon>,
+    //单个用户可领个数
+    pub max_coupons_per_user: i32,
+    //券类型 NORMAL/CUT_TO
+    pub coupon_type: Option<String>,
+    //订单优惠标记
+    pub goods_tag: Option<Vec<String>>,
+    //支付方式
+    pub trade_type: Option<Vec<String>>,
+    //是否可叠加其他优惠
+    pub combine_use: Option<bool>,
+}
+/// 代金券批次详情
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct CouponStock {
+    //批次号
+    pub stock_id: String,
+    //创建批次的商户号
+    pub stock_creator_mchid: String,
+    //批次名称
+    pub stock_name: String,
+    //批次状态
+    pub status: StockStatus,
+    //创建时间
+    pub create_time: String,
+    //使用说明
+    pub description: String,
+    //满减券批次使用规则
+    pub stock_use_rule: Option<StockUseRuleInfo>,
+    //可用开始时间
+    pub available_begin_time: String,
+    //可用结束时间
+    pub available_end_time: String,
+    //已发券数量
+    pub distributed_coupons: i64,
+    //是否无资金流
+    pub no_cash: bool,
+    //激活批次的时间
+    pub start_time: Option<String>,
+    //终止批次的时间
+    pub stop_time: Option<String>,
+    //是否单品优惠
+    pub singleitem: bool,
+    //批次类型
+    pub stock_type: String,
+}
+/// 批次列表
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct CouponStockListResponse {
+    //批次总数
+    pub total_count: i64,
+    //批次详情
+    pub data: Option<Vec<CouponStock>>,
+    //分页大小
+    pub limit: i32,
+    //分页页码
+    pub offset: i32,
+}
+/// 发放代金券
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqSendCoupon {
+    //批次号
+    pub stock_id: String,
+    //商户单据号
+    pub out_request_no: String,
+    //公众账号ID,为空时使用配置中的 app_id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub appid: Option<String>,
+    //创建批次的商户号,为空时使用配置中的 mchid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stock_creator_mchid: Option<String>,
+    //指定面额发券,面额
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coupon_value: Option<i64>,
+    //指定面额发券,券门槛
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coupon_minimum: Option<i64>,
+}
+/// 发放代金券返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct SendCouponResponse {
+    //代金券ID
+    pub coupon_id: String,
+}
+/// 代金券实扣信息
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct CouponConsumeInformation {
+    //核销时间
+    pub consume_time: String,
+    //核销商户号
+    pub consume_mchid: String,
+    //核销订单号
+    pub transaction_id: String,
+}
+/// 代金券详情,查询及核销通知均返回该结构
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct CouponDetail {
+    //创建批次的商户号
+    pub stock_creator_mchid: String,
+    //批次号
+    pub stock_id: String,
+    //代金券ID
+    pub coupon_id: String,
+    //代金券名称
+    pub coupon_name: String,
+    //代金券状态
+    pub status: CouponStatus,
+    //使用说明
+    pub description: String,
+    //领券时间
+    pub create_time: String,
+    //券类型 NORMAL/CUT_TO
+    pub coupon_type: String,
+    //是否无资金流
+    pub no_cash: bool,
+    //可用开始时间
+    pub available_begin_time: String,
+    //可用结束时间
+    pub available_end_time: String,
+    //是否单品优惠
+    pub singleitem: bool,
+    //满减券信息
+    pub normal_coupon_information: Option<FixedNormalCoupon>,
+    //实扣代金券信息
+    pub consume_information: Option<CouponConsumeInformation>,
+}
+/// 根据商户号查用户的券
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqUserCouponList {
+    //公众账号ID,为空时使用配置中的 app_id
+    pub appid: Option<String>,
+    //批次号
+    pub stock_id: Option<String>,
+    //券状态
+    pub status: Option<CouponStatus>,
+    //创建批次的商户号
+    pub creator_mchid: Option<String>,
+    //批次发放商户号
+    pub sender_mchid: Option<String>,
+    //可用商户号
+    pub available_mchid: Option<String>,
+    //分页页码
+    pub offset: Option<i32>,
+    //分页大小
+    pub limit: Option<i32>,
+}
+/// 用户的券列表
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct UserCouponListResponse {
+    //结果集
+    pub data: Option<Vec<CouponDetail>>,
+    //总数量
+    pub total_count: i64,
+    //分页大小
+    pub limit: i32,
+    //分页页码
+    pub offset: i32,
+}
+/// 设置消息通知地址返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct CouponCallbackResponse {
+    //修改时间
+    pub update_time: Option<String>,
+    //通知地址
+    pub notify_url: String,
+    //商户号
+    pub mchid: Option<String>,
+}
+/// 发券插件的发券参数
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct SendCouponParam {
+    //批次号
+    pub stock_id: String,
+    //商户单据号
+    pub out_request_no: String,
+}
+/// 小程序/H5发券插件的签名参数
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct SendCouponSignPackage {
+    //发券参数,最多10个
+    pub send_coupon_params: Vec<SendCouponParam>,
+    //发券商户号
+    pub send_coupon_merchant: String,
+    //签名
+    pub sign: String,
+}
+/// 商家券批次类型
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub enum BusifavorStockType {
+    //固定面额满减券
+    #[default]
+    NORMAL,
+    //折扣券
+    DISCOUNT,
+    //换购券
+    EXCHANGE,
+}
+/// 商家券核销方式
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BusifavorUseMethod {
+    //线下滴码核销
+    #[default]
+    OffLine,
+    //线上小程序核销
+    MiniPrograms,
+    //用户自助核销
+    SelfConsume,
+    //付款码核销
+    PaymentCode,
+}
+/// 商家券 code 模式
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CouponCodeMode {
+    //系统分配券 code
+    #[default]
+    WechatpayMode,
+    //商户发放时接口指定券 code
+    MerchantApi,
+    //商户上传自定义 code
+    MerchantUpload,
+}
+/// 商家券可用时间
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct BusifavorAvailableTime {
+    //开始时间
+    pub available_begin_time: String,
+    //结束时间
+    pub available_end_time: String,
+    //生效后N天内有效
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub available_day_after_receive: Option<i32>,
+    //领取后N天开始生效
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wait_days_after_receive: Option<i32>,
+}
+/// 商家券固定面额满减
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct BusifavorNormalCoupon {
+    //优惠金额
+    pub discount_amount: i64,
+    //消费门槛
+    pub transaction_minimum: i64,
+}
+/// 商家券折扣
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct BusifavorDiscountCoupon {
+    //折扣比例,88为8.8折
+    pub discount_percent: i32,
+    //消费门槛
+    pub transaction_minimum: i64,
+}
+/// 商家券换购
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct BusifavorExchangeCoupon {
+    //单品换购价
+    pub exchange_price: i64,
+    //消费门槛
+    pub transaction_minimum: i64,
+}
+/// 商家券核销规则
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct BusifavorUseRule {
+    //券可核销时间
+    pub coupon_available_time: BusifavorAvailableTime,
+    //固定面额满减券使用规则
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fixed_normal_coupon: Option<BusifavorNormalCoupon>,
+    //折扣券使用规则
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub discount_coupon: Option<BusifavorDiscountCoupon>,
+    //换购券使用规则
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exchange_coupon: Option<BusifavorExchangeCoupon>,
+    //核销方式
+    pub use_method: BusifavorUseMethod,
+    //小程序appid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mini_programs_appid: Option<String>,
+    //小程序path
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mini_programs_path: Option<String>,
+}
+/// 商家券发放规则
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct BusifavorSendRule {
+    //批次总预算
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_amount: Option<i64>,
+    //批次最大发放个数
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_coupons: Option<i64>,
+    //用户最大可领个数
+    pub max_coupons_per_user: i32,
+    //单天发放上限金额
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_amount_by_day: Option<i64>,
+    //单天发放上限个数
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_coupons_by_day: Option<i64>,
+    //是否开启自然人限制
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub natural_person_limit: Option<bool>,
+    //可疑账号拦截
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prevent_api_abuse: Option<bool>,
+    //是否允许转赠
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transferable: Option<bool>,
+    //是否允许分享链接
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shareable: Option<bool>,
+}
+/// 商家券样式
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct BusifavorDisplayPattern {
+    //使用须知
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    //商户logo
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merchant_logo_url: Option<String>,
+    //商户名称
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merchant_name: Option<String>,
+    //背景颜色
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background_color: Option<String>,
+    //券详情图片
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coupon_image_url: Option<String>,
+}
+/// 创建商家券
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqBusifavorStock {
+    //商家券批次名称
+    pub stock_name: String,
+    //批次归属商户号,为空时使用配置中的 mchid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub belong_merchant: Option<String>,
+    //批次备注
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    //适用商品范围
+    pub goods_name: String,
+    //批次类型
+    pub stock_type: BusifavorStockType,
+    //核销规则
+    pub coupon_use_rule: BusifavorUseRule,
+    //发放规则
+    pub stock_send_rule: BusifavorSendRule,
+    //商户请求单号
+    pub out_request_no: String,
+    //样式信息
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_pattern_info: Option<BusifavorDisplayPattern>,
+    //券code模式
+    pub coupon_code_mode: CouponCodeMode,
+}
+/// 核销商家券
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqBusifavorUse {
+    //券code
+    pub coupon_code: String,
+    //批次号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stock_id: Option<String>,
+    //公众账号ID,为空时使用配置中的 app_id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub appid: Option<String>,
+    //请求核销时间
+    pub use_time: String,
+    //核销请求单据号
+    pub use_request_no: String,
+    //用户标识
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub openid: Option<String>,
+}
+/// 核销商家券返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct BusifavorUseResponse {
+    //批次号
+    pub stock_id: String,
+    //用户标识
+    pub openid: String,
+    //系统核销券成功的时间
+    pub wechatpay_use_time: String,
+}
+/// 退券
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqBusifavorReturn {
+    //券code
+    pub coupon_code: String,
+    //批次号
+    pub stock_id: String,
+    //退券请求单据号
+    pub return_request_no: String,
+}
+/// 退券返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct BusifavorReturnResponse {
+    //微信退券成功的时间
+    pub wechatpay_return_time: String,
+}
+/// 使券失效
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqBusifavorDeactivate {
+    //券code
+    pub coupon_code: String,
+    //批次号
+    pub stock_id: String,
+    //失效请求单据号
+    pub deactivate_request_no: String,
+    //失效原因
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deactivate_reason: Option<String>,
+}
+/// 使券失效返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct BusifavorDeactivateResponse {
+    //券成功失效的时间
+    pub wechatpay_deactivate_time: String,
+}
@@ -0,0 +1,396 @@
+use crate::wechat::prelude::*;
+use serde::{Deserialize, Serialize};
+/// 电商收付通退款出资账户
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum EcommerceRefundAccount {
+    //二级商户余额不足时由平台垫付
+    RefundSourcePartnerAdvance,
+    //从二级商户账户出款,默认
+    RefundSourceSubMerchant,
+}
+/// 电商收付通退款资金来源
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub enum EcommerceFundsAccount {
+    //可用余额
+    AVAILABLE,
+    //不可用余额
+    UNSETTLED,
+}
+/// 垫付回补结果
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub enum ReturnAdvanceResult {
+    //回补成功
+    SUCCESS,
+    //回补失败
+    FAILED,
+    //回补处理中
+    #[default]
+    PROCESSING,
+}
+/// 补差结果
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub enum SubsidyResult {
+    //成功
+    SUCCESS,
+    //失败
+    #[default]
+    FAIL,
+    //已退款
+    REFUND,
+}
+/// 提现状态
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum WithdrawStatus {
+    //受理成功
+    #[default]
+    CreateSuccess,
+    //提现成功
+    Success,
+    //提现失败
+    Fail,
+    //提现退票
+    Refund,
+    //关单
+    Close,
+    //业务单已创建
+    Init,
+}
+/// 电商收付通退款金额
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqEcommerceRefundAmount {
+    //退款金额
+    pub refund: i32,
+    //原订单金额
+    pub total: i32,
+    //退款币种
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+}
+/// 电商收付通申请退款
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqEcommerceRefund {
+    //二级商户号,为空时使用配置中的 mchid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_mchid: Option<String>,
+    //电商平台APPID,为空时使用配置中的 sp_appid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sp_appid: Option<String>,
+    //二级商户APPID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_appid: Option<String>,
+    //微信订单号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_id: Option<String>,
+    //商户订单号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub out_trade_no: Option<String>,
+    //商户退款单号
+    pub out_refund_no: String,
+    //退款原因
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    //订单金额
+    pub amount: ReqEcommerceRefundAmount,
+    //退款结果回调地址
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notify_url: Option<String>,
+    //退款出资商户
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refund_account: Option<EcommerceRefundAccount>,
+    //资金账户
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub funds_account: Option<EcommerceFundsAccount>,
+}
+/// 电商收付通退款金额信息
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct EcommerceRefundAmount {
+    //退款金额
+    pub refund: i32,
+    //用户退款金额
+    pub payer_refund: Option<i32>,
+    //优惠退款金额
+    pub discount_refund: Option<i32>,
+    //退款币种
+    pub currency: Option<String>,
+    //垫付金额
+    pub advance: Option<i32>,
+}
+/// 电商收付通退款优惠信息
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct EcommerceRefundPromotion {
+    //券ID
+    pub promotion_id: String,
+    //优惠范围 GLOBAL/SINGLE
+    pub scope: String,
+    //优惠类型 COUPON/DISCOUNT
+    pub r#type: String,
+    //优惠券面额
+    pub amount: i32,
+    //优惠退款金额
+    pub refund_amount: i32,
+}
+/// 电商收付通申请退款返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct EcommerceRefundResponse {
+    //微信退款单号
+    pub refund_id: String,
+    //商户退款单号
+    pub out_refund_no: String,
+    //退款创建时间
+    pub create_time: String,
+    //订单金额
+    pub amount: EcommerceRefundAmount,
+    //优惠退款详情
+    pub promotion_detail: Option<Vec<EcommerceRefundPromotion>>,
+    //退款出资商户
+    pub refund_account: Option<EcommerceRefundAccount>,
+}
+/// 电商收付通查询退款返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct EcommerceRefundQueryResponse {
+    //微信退款单号
+    pub refund_id: String,
+    //商户退款单号
+    pub out_refund_no: String,
+    //微信订单号
+    pub transaction_id: String,
+    //商户订单号
+    pub out_trade_no: String,
+    //退款渠道
+    pub channel: Option<RefundChannel>,
+    //退款入账账户
+    pub user_received_account: Option<String>,
+    //退款成功时间
+    pub success_time: Option<String>,
+    //退款创建时间
+    pub create_time: String,
+    //退款状态
+    pub status: RefundStatus,
+    //订单金额
+    pub amount: EcommerceRefundAmount,
+    //优惠退款详情
+    pub promotion_detail: Option<Vec<EcommerceRefundPromotion>>,
+    //退款出资商户
+    pub refund_account: Option<EcommerceRefundAccount>,
+    //资金账户
+    pub funds_account: Option<EcommerceFundsAccount>,
+}
+impl EcommerceRefundQueryResponse {
+    /// 平台垫付的退款成功后,需要调用垫付回补从二级商户账户回补垫付资金
+    pub fn need_return_advance(&self) -> bool {
+        self.status == RefundStatus::SUCCESS
+            && self.refund_account == Some(EcommerceRefundAccount::RefundSourcePartnerAdvance)
+    }
+}
+/// 垫付回补返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReturnAdvanceResponse {
+    //微信退款单号
+    pub refund_id: String,
+    //微信回补单号
+    pub advance_return_id: String,
+    //垫付回补金额
+    pub return_amount: i32,
+    //出款方商户号
+    pub payer_mchid: String,
+    //出款方账户
+    pub payer_account: String,
+    //入账方商户号
+    pub payee_mchid: String,
+    //入账方账户
+    pub payee_account: String,
+    //垫付回补结果
+    pub result: ReturnAdvanceResult,
+    //垫付回补完成时间
+    pub success_time: Option<String>,
+}
+/// 请求补差
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqSubsidyCreate {
+    //二级商户号,为空时使用配置中的 mchid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_mchid: Option<String>,
+    //微信订单号
+    pub transaction_id: String,
+    //商户补差单号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub out_subsidy_no: Option<String>,
+    //补差金额
+    pub amount: i32,
+    //补差描述
+    pub description: String,
+    //微信退款单号,退款补差时必填
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refund_id: Option<String>,
+}
+/// 请求补差返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct SubsidyCreateResponse {
+    //二级商户号
+    pub sub_mchid: String,
+    //微信订单号
+    pub transaction_id: String,
+    //微信补差单号
+    pub subsidy_id: String,
+    //补差描述
+    pub description: String,
+    //补差金额
+    pub amount: i32,
+    //补差单结果
+    pub result: SubsidyResult,
+    //补差完成时间
+    pub success_time: Option<String>,
+}
+/// 请求补差回退
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqSubsidyReturn {
+    //二级商户号,为空时使用配置中的 mchid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_mchid: Option<String>,
+    //商户补差回退单号
+    pub out_order_no: String,
+    //微信订单号
+    pub transaction_id: String,
+    //微信退款单号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refund_id: Option<String>,
+    //补差回退金额
+    pub amount: i32,
+    //补差回退描述
+    pub description: String,
+}
+/// 请求补差回退返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct SubsidyReturnResponse {
+    //二级商户号
+    pub sub_mchid: String,
+    //微信补差回退单号
+    pub target_order_id: Option<String>,
+    //商户补差回退单号
+    pub out_order_no: String,
+    //微信订单号
+    pub transaction_id: String,
+    //补差回退金额
+    pub amount: i32,
+    //补差回退描述
+    pub description: String,
+    //补差回退结果
+    pub result: SubsidyResult,
+    //补差回退完成时间
+    pub success_time: Option<String>,
+    //微信退款单号
+    pub refund_id: Option<String>,
+}
+/// 取消补差
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqSubsidyCancel {
+    //二级商户号,为空时使用配置中的 mchid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_mchid: Option<String>,
+    //微信订单号
+    pub transaction_id: String,
+    //取消补差描述
+    pub description: String,
+}
+/// 取消补差返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct SubsidyCancelResponse {
+    //二级商户号
+    pub sub_mchid: String,
+    //微信订单号
+    pub transaction_id: String,
+    //取消补差结果
+    pub result: SubsidyResult,
+    //取消补差描述
+    pub description: String,
+}
+/// 二级商户账户实时余额
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct EcommerceBalanceResponse {
+    //二级商户号
+    pub sub_mchid: String,
+    //账户类型
+    pub account_type: Option<FundBillAccountType>,
+    //可用余额
+    pub available_amount: i64,
+    //不可用余额
+    pub pending_amount: Option<i64>,
+}
+/// 二级商户账户日终余额
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct EcommerceEndDayBalanceResponse {
+    //二级商户号
+    pub sub_mchid: String,
+    //可用余额
+    pub available_amount: i64,
+    //不可用余额
+    pub pending_amount: Option<i64>,
+}
+/// 二级商户余额提现
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqEcommerceWithdraw {
+    //二级商户号,为空时使用配置中的 mchid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_mchid: Option<String>,
+    //商户提现单号
+    pub out_request_no: String,
+    //提现金额
+    pub amount: i64,
+    //提现备注
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remark: Option<String>,
+    //银行附言
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bank_memo: Option<String>,
+    //出款账户类型
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_type: Option<FundBillAccountType>,
+}
+/// 二级商户余额提现返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct EcommerceWithdrawResponse {
+    //二级商户号
+    pub sub_mchid: String,
+    //微信支付提现单号
+    pub withdraw_id: String,
+    //商户提现单号
+    pub out_request_no: String,
+    //出款账户类型
+    pub account_type: Option<FundBillAccountType>,
+}
+/// 二级商户提现状态
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct EcommerceWithdrawQueryResponse {
+    //二级商户号
+    pub sub_mchid: String,
+    //电商平台商户号
+    pub sp_mchid: String,
+    //提现单状态
+    pub status: WithdrawStatus,
+    //微信支付提现单号
+    pub withdraw_id: String,
+    //商户提现单号
+    pub out_request_no: String,
+    //提现金额
+    pub amount: i64,
+    //发起提现时间
+    pub create_time: String,
+    //提现状态更新时间
+    pub update_time: String,
+    //失败原因
+    pub reason: Option<String>,
+    //提现备注
+    pub remark: Option<String>,
+    //银行附言
+    pub bank_memo: Option<String>,
+    //出款账户类型
+    pub account_type: Option<FundBillAccountType>,
+    //入账银行账号后四位
+    pub account_number: Option<String>,
+    //入账银行
+    pub account_bank: Option<String>,
+    //银行全称(含支行)
+    pub bank_name: Option<String>,
+}
@@ -0,0 +1,500 @@
+use crate::wechat::prelude::*;
+use serde::{Deserialize, Serialize};
+/// 发票数量的单位,100000000 表示数量1
+pub const FAPIAO_QUANTITY_UNIT: i64 = 100_000_000;
+/// 开票场景
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum FapiaoScene {
+    //支付后开票,fapiao_apply_id 为微信支付订单号
+    #[default]
+    WithWechatpay,
+    //非支付场景开票
+    WithoutWechatpay,
+}
+/// 购买方类型
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub enum BuyerType {
+    //个人
+    #[default]
+    INDIVIDUAL,
+    //单位
+    ORGANIZATION,
+}
+/// 发票状态
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum FapiaoStatus {
+    //开票已受理
+    #[default]
+    IssueAccepted,
+    //已开票
+    Issued,
+    //冲红已受理
+    ReverseAccepted,
+    //已冲红
+    Reversed,
+}
+/// 发票文件类型
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub enum FapiaoFileType {
+    #[default]
+    PDF,
+    OFD,
+}
+/// 商户开发配置
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct FapiaoDevelopmentConfig {
+    //商户回调地址,用户提交抬头及开票、冲红结果均通知到该地址
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub callback_url: Option<String>,
+    //是否在支付成功页展示开票入口
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub show_fapiao_cell: Option<bool>,
+}
+/// 电子发票卡券模板信息
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct FapiaoCardTemplateInformation {
+    //收款方名称
+    pub payee_name: String,
+    //卡券logo地址
+    pub logo_url: String,
+}
+/// 创建电子发票卡券模板
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqFapiaoCardTemplate {
+    //插卡公众号AppID,为空时使用配置中的 app_id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub card_appid: Option<String>,
+    //卡券模板信息
+    pub card_template_information: FapiaoCardTemplateInformation,
+}
+/// 创建电子发票卡券模板返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct FapiaoCardTemplateResponse {
+    //插卡公众号AppID
+    pub card_appid: String,
+    //卡券模板ID
+    pub card_id: String,
+}
+/// 销售方信息
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct FapiaoSellerInformation {
+    //销售方名称
+    pub name: String,
+    //销售方纳税人识别号
+    pub taxpayer_id: String,
+    //销售方地址
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    //销售方电话
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub telephone: Option<String>,
+    //销售方开户银行
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bank_name: Option<String>,
+    //销售方银行账号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bank_account: Option<String>,
+}
+/// 发票附加信息
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct FapiaoExtraInformation {
+    //收款人
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payee: Option<String>,
+    //复核人
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reviewer: Option<String>,
+    //开票人
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub drawer: Option<String>,
+}
+/// 商户开票基础信息
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct FapiaoBaseInformation {
+    //销售方信息
+    pub seller_information: FapiaoSellerInformation,
+    //发票附加信息
+    pub extra_information: Option<FapiaoExtraInformation>,
+}
+/// 商品和服务税收分类
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct FapiaoTaxCode {
+    //税局侧规定的货物或应税劳务、服务的分类名称
+    pub goods_category: String,
+    //商户侧维护的货物或应税劳务、服务的名称
+    pub goods_name: String,
+    //商户侧维护的货物或应税劳务、服务的唯一编码
+    pub goods_id: i64,
+    //税收分类编码
+    pub tax_code: String,
+    //税率,1300表示13%
+    pub tax_rate: i32,
+    //税收优惠政策标识
+    pub tax_prefer_mark: Option<String>,
+}
+/// 商品和服务税收分类列表
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct FapiaoTaxCodeListResponse {
+    //税收分类列表
+    pub data: Option<Vec<FapiaoTaxCode>>,
+    //总条数
+    pub total_count: i32,
+    //分页开始位置
+    pub offset: i32,
+    //分页大小
+    pub limit: i32,
+}
+/// 获取抬头填写链接
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqFapiaoTitleUrl {
+    //发票申请单号
+    pub fapiao_apply_id: String,
+    //应用ID,为空时使用配置中的 app_id
+    pub appid: Option<String>,
+    //用户标识
+    pub openid: String,
+    //总金额
+    pub total_amount: i64,
+    //开票来源 WEB/MINIPROGRAM
+    pub source: String,
+}
+/// 抬头填写链接
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct FapiaoTitleUrlResponse {
+    //跳转小程序AppID
+    pub miniprogram_appid: String,
+    //跳转小程序路径
+    pub miniprogram_path: String,
+    //跳转小程序原始ID
+    pub miniprogram_user_name: String,
+}
+/// 购买方信息,用户填写的抬头也返回该结构
+/// 手机号、邮箱在查询时已使用商户私钥解密,开票时会自动使用平台证书加密
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct FapiaoBuyerInformation {
+    //购买方类型
+    pub r#type: BuyerType,
+    //名称
+    pub name: String,
+    //纳税人识别号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub taxpayer_id: Option<String>,
+    //地址
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    //电话
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub telephone: Option<String>,
+    //开户银行
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bank_name: Option<String>,
+    //银行账号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bank_account: Option<String>,
+    //手机号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone: Option<String>,
+    //邮箱地址
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    //需开票金额,仅查询抬头时返回
+    #[serde(skip_serializing)]
+    pub amount: Option<i64>,
+    //商户订单号,仅查询抬头时返回
+    #[serde(skip_serializing)]
+    pub out_trade_no: Option<String>,
+}
+/// 发票行信息
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct FapiaoItem {
+    //税收分类编码
+    pub tax_code: String,
+    //货物或应税劳务、服务的分类名称
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goods_category: Option<String>,
+    //货物或应税劳务、服务的名称
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goods_name: Option<String>,
+    //商户侧维护的商品编码
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goods_id: Option<i64>,
+    //规格型号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub specification: Option<String>,
+    //单位
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    //数量,FAPIAO_QUANTITY_UNIT 表示1
+    pub quantity: i64,
+    //单行金额合计,含税
+    pub total_amount: i64,
+    //税率,1300表示13%
+    pub tax_rate: i32,
+    //税收优惠政策标识,NO_FAVORABLE 表示不享受优惠
+    pub tax_prefer_mark: String,
+    //是否折扣行
+    pub discount: bool,
+}
+/// 需要开具的发票信息
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct FapiaoInformation {
+    //商户发票单号
+    pub fapiao_id: String,
+    //总价税合计
+    pub total_amount: i64,
+    //是否以清单形式开具发票
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub need_list: Option<bool>,
+    //备注
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remark: Option<String>,
+    //发票行信息
+    pub items: Vec<FapiaoItem>,
+}
+/// 开具电子发票
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqFapiaoApplication {
+    //开票场景
+    pub scene: FapiaoScene,
+    //发票申请单号
+    pub fapiao_apply_id: String,
+    //购买方信息
+    pub buyer_information: FapiaoBuyerInformation,
+    //需要开具的发票信息
+    pub fapiao_information: Vec<FapiaoInformation>,
+}
+impl ReqFapiaoApplication {
+    /// 根据支付成功的订单生成开票申请,按订单金额开具一张单行发票
+    /// item 为发票行模板,数量为0时按1开具,金额使用订单总金额
+    pub fn from_order(
+        order: &ResourceOrderBody,
+        buyer_information: FapiaoBuyerInformation,
+        item: FapiaoItem,
+    ) -> Self {
+        let total_amount = order.amount.total as i64;
+        let quantity = if item.quantity == 0 {
+            FAPIAO_QUANTITY_UNIT
+        } else {
+            item.quantity
+        };
+        let tax_prefer_mark = if item.tax_prefer_mark.is_empty() {
+            "NO_FAVORABLE".to_string()
+        } else {
+            item.tax_prefer_mark.clone()
+        };
+        ReqFapiaoApplication {
+            scene: FapiaoScene::WithWechatpay,
+            fapiao_apply_id: order.transaction_id.clone(),
+            buyer_information,
+            fapiao_information: vec![FapiaoInformation {
+                fapiao_id: order.out_trade_no.clone(),
+                total_amount,
+                need_list: None,
+                remark: None,
+                items: vec![FapiaoItem {
+                    quantity,
+                    total_amount,
+                    tax_prefer_mark,
+                    ..item
+                }],
+            }],
+        }
+    }
+}
+/// 发票代码号码
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct FapiaoNumberInfo {
+    //发票代码
+    pub fapiao_code: String,
+    //发票号码
+    pub fapiao_number: String,
+    //校验码
+    pub check_code: Option<String>,
+    //密码
+    pub password: Option<String>,
+    //开票时间
+    pub fapiao_time: String,
+}
+/// 发票卡券信息
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct FapiaoCardInfo {
+    //插卡公众号AppID
+    pub card_appid: Option<String>,
+    //用户在插卡公众号下的openid
+    pub card_openid: Option<String>,
+    //卡券模板ID
+    pub card_id: Option<String>,
+    //卡券code
+    pub card_code: Option<String>,
+    //卡券状态 INSERT_ACCEPTED/INSERTED/DISCARD_ACCEPTED/DISCARDED
+    pub card_status: String,
+}
+/// 发票明细行
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct FapiaoCardItem {
+    //税收分类编码
+    pub tax_code: String,
+    //货物或应税劳务、服务的名称
+    pub goods_name: String,
+    //规格型号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub specification: Option<String>,
+    //单位
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    //数量,FAPIAO_QUANTITY_UNIT 表示1
+    pub quantity: i64,
+    //单价,不含税,单位为分的1/FAPIAO_QUANTITY_UNIT
+    pub unit_price: i64,
+    //单行金额合计,不含税
+    pub amount: i64,
+    //税额
+    pub tax_amount: i64,
+    //价税合计
+    pub total_amount: i64,
+    //税率,1300表示13%
+    pub tax_rate: i32,
+    //税收优惠政策标识
+    pub tax_prefer_mark: String,
+    //是否折扣行
+    pub discount: bool,
+}
+/// 发票详情
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct FapiaoDetail {
+    //商户发票单号
+    pub fapiao_id: String,
+    //发票状态
+    pub status: FapiaoStatus,
+    //蓝字发票信息
+    pub blue_fapiao: Option<FapiaoNumberInfo>,
+    //红字发票信息
+    pub red_fapiao: Option<FapiaoNumberInfo>,
+    //卡券信息
+    pub card_information: Option<FapiaoCardInfo>,
+    //总价税合计
+    pub total_amount: Option<i64>,
+    //总税额
+    pub tax_amount: Option<i64>,
+    //总金额
+    pub amount: Option<i64>,
+    //销售方信息
+    pub seller_information: Option<FapiaoSellerInformation>,
+    //购买方信息
+    pub buyer_information: Option<FapiaoBuyerInformation>,
+    //附加信息
+    pub extra_information: Option<FapiaoExtraInformation>,
+    //发票行信息
+    pub items: Option<Vec<FapiaoCardItem>>,
+    //备注
+    pub remark: Option<String>,
+}
+/// 查询电子发票返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct FapiaoApplicationResponse {
+    //发票数量
+    pub total_count: i32,
+    //发票信息
+    pub fapiao_information: Vec<FapiaoDetail>,
+}
+/// 需要冲红的发票
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReverseFapiaoInformation {
+    //商户发票单号
+    pub fapiao_id: String,
+    //发票代码
+    pub fapiao_code: String,
+    //发票号码
+    pub fapiao_number: String,
+}
+/// 冲红电子发票
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqReverseFapiao {
+    //冲红原因
+    pub reverse_reason: String,
+    //需要冲红的发票信息
+    pub fapiao_information: Vec<ReverseFapiaoInformation>,
+}
+/// 上传电子发票文件的 meta 信息
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct FapiaoFileMeta {
+    //文件类型
+    pub file_type: FapiaoFileType,
+    //摘要算法,固定为 SM3
+    pub digest_algorithm: String,
+    //文件摘要
+    pub digest: String,
+}
+/// 上传电子发票文件返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct FapiaoFileUploadResponse {
+    //电子发票文件标识
+    pub fapiao_media_id: String,
+}
+/// 需要插入微信卡包的发票
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct InsertFapiaoCard {
+    //电子发票文件标识
+    pub fapiao_media_id: String,
+    //发票号码
+    pub fapiao_number: String,
+    //发票代码
+    pub fapiao_code: String,
+    //开票时间
+    pub fapiao_time: String,
+    //校验码
+    pub check_code: String,
+    //密码
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    //总价税合计
+    pub total_amount: i64,
+    //总税额
+    pub tax_amount: i64,
+    //总金额
+    pub amount: i64,
+    //销售方信息
+    pub seller_information: FapiaoSellerInformation,
+    //附加信息
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extra_information: Option<FapiaoExtraInformation>,
+    //发票行信息
+    pub items: Vec<FapiaoCardItem>,
+    //备注
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remark: Option<String>,
+}
+/// 将电子发票插入微信用户卡包
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqInsertFapiaoCards {
+    //开票场景
+    pub scene: FapiaoScene,
+    //购买方信息
+    pub buyer_information: FapiaoBuyerInformation,
+    //发票卡券信息
+    pub fapiao_card_information: Vec<InsertFapiaoCard>,
+}
+/// 发票通知中的发票信息
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct FapiaoNotifyInformation {
+    //商户发票单号
+    pub fapiao_id: String,
+    //发票状态
+    pub status: Option<FapiaoStatus>,
+    //卡券状态
+    pub card_status: Option<String>,
+}
+/// 发票通知解密后的内容
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ResourceFapiaoBody {
+    //商户号
+    pub mchid: String,
+    //发票申请单号
+    pub fapiao_apply_id: String,
+    //抬头提交时间,FAPIAO.USER_APPLIED 时返回
+    pub apply_time: Option<String>,
+    //发票信息,开票、冲红、插卡结果通知时返回
+    pub fapiao_information: Option<Vec<FapiaoNotifyInformation>>,
+}
@@ -0,0 +1,215 @@
+use crate::wechat::prelude::*;
+use serde::{Deserialize, Serialize};
+/// 汇率信息,rate 为汇率值乘以 10^8
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub struct ExchangeRate {
+    //汇率类型 SETTLEMENT_RATE/USERPAYMENT_RATE
+    pub r#type: String,
+    //汇率值,乘以10^8
+    pub rate: i64,
+}
+impl ExchangeRate {
+    /// 换算后的汇率
+    pub fn value(&self) -> f64 {
+        self.rate as f64 / 100_000_000.0
+    }
+}
+/// 境外支付订单金额
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqGlobalAmount {
+    //订单总金额,单位为币种最小单位
+    pub total: i64,
+    //标价币种,如 HKD、USD
+    pub currency: String,
+}
+/// 境外支付场景信息
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqGlobalSceneInfo {
+    //用户终端IP
+    pub payer_client_ip: String,
+    //商户端设备号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device_id: Option<String>,
+    //H5 场景信息
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub h5_info: Option<ReqSceneH5Info>,
+}
+/// 境外支付下单
+/// 直连模式下 appid、mchid 为空时使用配置,机构模式下 sp_* 与 sub_* 使用配置
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqGlobalOrderBody {
+    //应用ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub appid: Option<String>,
+    //商户号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mchid: Option<String>,
+    //机构应用ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sp_appid: Option<String>,
+    //机构商户号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sp_mchid: Option<String>,
+    //子商户应用ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_appid: Option<String>,
+    //子商户号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_mchid: Option<String>,
+    //商品描述
+    pub description: String,
+    //商户订单号
+    pub out_trade_no: String,
+    //交易类型,下单时根据 TradeType 自动填写
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trade_type: Option<String>,
+    //商户类目(MCC)
+    pub merchant_category_code: String,
+    //附加数据
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attach: Option<String>,
+    //通知地址,为空时使用配置中的 notify_url
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notify_url: Option<String>,
+    //订单金额
+    pub amount: ReqGlobalAmount,
+    //支付者,JSAPI 必填
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payer: Option<PayerInfo>,
+    //场景信息
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scene_info: Option<ReqGlobalSceneInfo>,
+}
+/// 境外支付订单金额信息
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct GlobalOrderAmount {
+    //订单总金额
+    pub total: i64,
+    //标价币种
+    pub currency: String,
+    //用户支付金额
+    pub payer_total: Option<i64>,
+    //用户支付币种
+    pub payer_currency: Option<String>,
+    //汇率信息
+    pub exchange_rate: Option<ExchangeRate>,
+}
+/// 境外支付订单查询返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct GlobalOrderQueryResponse {
+    //应用ID
+    pub appid: Option<String>,
+    //商户号
+    pub mchid: Option<String>,
+    //机构商户号
+    pub sp_mchid: Option<String>,
+    //子商户号
+    pub sub_mchid: Option<String>,
+    //商户订单号
+    pub out_trade_no: String,
+    //微信支付订单号
+    pub id: Option<String>,
+    //交易类型 JSAPI/NATIVE/APP/MWEB
+    pub trade_type: Option<String>,
+    //交易状态
+    pub trade_state: TradeState,
+    //交易状态描述
+    pub trade_state_desc: Option<String>,
+    //付款银行
+    pub bank_type: Option<String>,
+    //附加数据
+    pub attach: Option<String>,
+    //支付完成时间
+    pub success_time: Option<String>,
+    //支付者
+    pub payer: Option<PayerInfo>,
+    //订单金额
+    pub amount: Option<GlobalOrderAmount>,
+    //优惠信息
+    pub promotion_detail: Option<Vec<RespPromotionDetail>>,
+}
+/// 境外退款金额
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqGlobalRefundAmount {
+    //退款金额
+    pub refund: i64,
+    //原订单金额
+    pub total: i64,
+    //退款币种,需与下单币种一致
+    pub currency: String,
+}
+/// 境外退款申请
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqGlobalRefund {
+    //商户号,为空时使用配置
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mchid: Option<String>,
+    //机构商户号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sp_mchid: Option<String>,
+    //子商户号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_mchid: Option<String>,
+    //微信支付订单号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_id: Option<String>,
+    //商户订单号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub out_trade_no: Option<String>,
+    //商户退款单号
+    pub out_refund_no: String,
+    //退款原因
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    //退款通知地址,为空时使用配置中的 notify_url
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notify_url: Option<String>,
+    //退款金额
+    pub amount: ReqGlobalRefundAmount,
+}
+/// 境外退款金额信息
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct GlobalRefundAmount {
+    //退款金额
+    pub refund: i64,
+    //原订单金额
+    pub total: Option<i64>,
+    //退款币种
+    pub currency: String,
+    //用户退款金额
+    pub payer_refund: Option<i64>,
+    //用户退款币种
+    pub payer_currency: Option<String>,
+    //结算退款金额
+    pub settlement_refund: Option<i64>,
+    //结算币种
+    pub settlement_currency: Option<String>,
+    //汇率信息
+    pub exchange_rate: Option<ExchangeRate>,
+}
+/// 境外退款返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct GlobalRefundResponse {
+    //微信退款单号
+    pub id: String,
+    //商户退款单号
+    pub out_refund_no: String,
+    //微信支付订单号
+    pub transaction_id: Option<String>,
+    //商户订单号
+    pub out_trade_no: Option<String>,
+    //退款渠道
+    pub channel: Option<String>,
+    //退款入账账户
+    pub recv_account: Option<String>,
+    //退款资金来源
+    pub fund_source: Option<String>,
+    //退款成功时间
+    pub success_time: Option<String>,
+    //退款创建时间
+    pub create_time: Option<String>,
+    //退款状态
+    pub status: RefundStatus,
+    //退款金额
+    pub amount: GlobalRefundAmount,
+}
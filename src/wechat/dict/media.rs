@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+/// 图片/视频上传的 meta 信息,签名时只对该 json 串签名
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct MediaMeta {
+    //文件名,需带后缀
+    pub filename: String,
+    //文件内容的 SHA256 摘要
+    pub sha256: String,
+}
+/// 图片/视频上传返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct MediaUploadResponse {
+    //媒体文件标识
+    pub media_id: String,
+}
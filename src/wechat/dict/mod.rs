@@ -1,4 +1,16 @@
+pub mod applyment;
+pub mod balance;
 pub mod bill;
 pub mod cert;
+pub mod complaint;
+pub mod coupon;
+pub mod ecommerce;
+pub mod fapiao;
+pub mod global;
+pub mod media;
+pub mod notify;
 pub mod order;
-pub mod refund;
\ No newline at end of file
+pub mod payscore;
+pub mod profit_sharing;
+pub mod refund;
+pub mod transfer;
@@ -0,0 +1,95 @@
+use crate::wechat::prelude::*;
+use crate::WeaResult;
+use serde::Serialize;
+/// 解密后的通知内容,根据 event_type 区分
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "type", content = "data")]
+pub enum NotifyEvent {
+    //支付成功 TRANSACTION.SUCCESS
+    TransactionSuccess(ResourceOrderBody),
+    //退款成功 REFUND.SUCCESS
+    RefundSuccess(ResourceRefundBody),
+    //退款异常 REFUND.ABNORMAL
+    RefundAbnormal(ResourceRefundBody),
+    //退款关闭 REFUND.CLOSED
+    RefundClosed(ResourceRefundBody),
+    //分账动账 PROFITSHARING.*
+    ProfitSharing(ResourceProfitSharingBody),
+    //商家转账批次 MCHTRANSFER.BATCH.*
+    TransferBatch(ResourceTransferBatchBody),
+    //商家转账单据 MCHTRANSFER.BILL.*
+    TransferBill(TransferBillQueryResponse),
+    //消费者投诉 COMPLAINT.*
+    Complaint(ResourceComplaintBody),
+    //代金券核销 COUPON.USE
+    CouponUse(CouponDetail),
+    //支付分用户确认订单 PAYSCORE.USER_CONFIRM
+    PayScoreUserConfirm(ServiceOrder),
+    //支付分订单支付成功 PAYSCORE.USER_PAID
+    PayScoreUserPaid(ServiceOrder),
+    //支付分开启/解除授权服务 PAYSCORE.USER_OPEN_SERVICE/PAYSCORE.USER_CLOSE_SERVICE
+    PayScorePermission(ResourcePayScorePermissionBody),
+    //用户发票抬头填写完成 FAPIAO.USER_APPLIED
+    FapiaoUserApplied(ResourceFapiaoBody),
+    //发票开具、冲红、插卡结果 FAPIAO.*
+    Fapiao(ResourceFapiaoBody),
+    //未识别的通知类型,保留解密后的原始JSON
+    Unknown(serde_json::Value),
+}
+impl NotifyEvent {
+    /// 根据通知类型将解密后的内容转换为对应的结构
+    pub fn from_content(event_type: &str, content: &str) -> WeaResult<NotifyEvent> {
+        let event = match event_type {
+            "TRANSACTION.SUCCESS" => {
+                NotifyEvent::TransactionSuccess(serde_json::from_str(content)?)
+            }
+            "REFUND.SUCCESS" => NotifyEvent::RefundSuccess(serde_json::from_str(content)?),
+            "REFUND.ABNORMAL" => NotifyEvent::RefundAbnormal(serde_json::from_str(content)?),
+            "REFUND.CLOSED" => NotifyEvent::RefundClosed(serde_json::from_str(content)?),
+            _ if event_type.starts_with("PROFITSHARING") => {
+                NotifyEvent::ProfitSharing(serde_json::from_str(content)?)
+            }
+            _ if event_type.starts_with("MCHTRANSFER.BATCH") => {
+                NotifyEvent::TransferBatch(serde_json::from_str(content)?)
+            }
+            _ if event_type.starts_with("MCHTRANSFER.BILL") => {
+                NotifyEvent::TransferBill(serde_json::from_str(content)?)
+            }
+            "COUPON.USE" => NotifyEvent::CouponUse(serde_json::from_str(content)?),
+            "PAYSCORE.USER_CONFIRM" => {
+                NotifyEvent::PayScoreUserConfirm(serde_json::from_str(content)?)
+            }
+            "PAYSCORE.USER_PAID" => NotifyEvent::PayScoreUserPaid(serde_json::from_str(content)?),
+            "PAYSCORE.USER_OPEN_SERVICE" | "PAYSCORE.USER_CLOSE_SERVICE" => {
+                NotifyEvent::PayScorePermission(serde_json::from_str(content)?)
+            }
+            _ if event_type.starts_with("COMPLAINT") => {
+                NotifyEvent::Complaint(serde_json::from_str(content)?)
+            }
+            "FAPIAO.USER_APPLIED" => NotifyEvent::FapiaoUserApplied(serde_json::from_str(content)?),
+            _ if event_type.starts_with("FAPIAO") => {
+                NotifyEvent::Fapiao(serde_json::from_str(content)?)
+            }
+            _ => NotifyEvent::Unknown(serde_json::from_str(content)?),
+        };
+        Ok(event)
+    }
+}
+/// 验签解密后的通知
+#[derive(Clone, Debug, Serialize)]
+pub struct NotifyEnvelope {
+    //通知ID
+    pub id: String,
+    //通知创建时间
+    pub create_time: String,
+    //通知数据类型
+    pub resource_type: String,
+    //通知类型
+    pub event_type: String,
+    //回调摘要
+    pub summary: String,
+    //解密后的通知内容
+    pub event: NotifyEvent,
+    //解密成功的APIv3密钥序号,0 为 mch_key,1 起为 api_v3_keys 中的顺序,用于确认旧密钥可以下线
+    pub api_v3_key_index: usize,
+}
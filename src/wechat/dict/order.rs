@@ -1,3 +1,5 @@
+use super::coupon::PromotionType;
+use super::global::ExchangeRate;
 use serde::{Deserialize, Serialize};
 /// 下单支付类型
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
@@ -294,6 +296,8 @@ pub struct RespAmount {
     pub payer_total: i32,
     //用户支付币种
     pub payer_currency: String,
+    //汇率信息,境外支付时返回
+    pub exchange_rate: Option<ExchangeRate>,
 }
 // 支付通知商品信息
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
@@ -335,6 +339,17 @@ pub struct RespPromotionDetail {
     //单品列表信息
     pub goods_detail: Vec<RespGoodsDetail>,
 }
+impl RespPromotionDetail {
+    /// 代金券优惠返回对应的代金券类型,可通过 CouponTrait::query_promotion_coupon 查询券详情
+    /// 其他优惠返回 None
+    pub fn promotion_type(&self) -> Option<PromotionType> {
+        match self.r#type.as_deref() {
+            Some("CASH") => Some(PromotionType::CASH),
+            Some("NOCASH") => Some(PromotionType::NOCASH),
+            _ => None,
+        }
+    }
+}
 // 支付通知返回解密后内容
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct ResourceOrderBody {
@@ -0,0 +1,439 @@
+use serde::{Deserialize, Serialize};
+/// 服务订单状态
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub enum ServiceOrderState {
+    //商户已创建服务订单
+    #[default]
+    CREATED,
+    //服务订单进行中
+    DOING,
+    //服务订单完成
+    DONE,
+    //商户取消服务订单
+    REVOKED,
+    //服务订单已失效
+    EXPIRED,
+}
+/// 服务订单状态说明
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ServiceOrderStateDescription {
+    //用户确认
+    UserConfirm,
+    //商户完结
+    MchComplete,
+    //用户已支付
+    UserPaid,
+}
+/// 收款状态
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CollectionState {
+    //待支付
+    #[default]
+    UserPaying,
+    //已支付
+    UserPaid,
+}
+/// 风险金名称
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RiskFundName {
+    //押金
+    #[default]
+    Deposit,
+    //预付款
+    Advance,
+    //保证金
+    CashDeposit,
+    //预估订单费用
+    EstimateOrderCost,
+}
+/// 用户授权状态
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub enum AuthorizationState {
+    //用户未授权服务
+    #[default]
+    UNAVAILABLE,
+    //用户已授权服务
+    AVAILABLE,
+    //用户已解除服务
+    UNBINDUSER,
+}
+/// 后付费项目
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct PayScorePostPayment {
+    //付费项目名称
+    pub name: String,
+    //金额
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<i32>,
+    //计费说明
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    //付费数量
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<i32>,
+}
+/// 后付费商户优惠
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct PayScorePostDiscount {
+    //优惠名称
+    pub name: String,
+    //优惠说明
+    pub description: String,
+    //优惠金额
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<i32>,
+    //优惠数量
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<i32>,
+}
+/// 服务时间段
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct PayScoreTimeRange {
+    //服务开始时间 yyyyMMddHHmmss 或 OnAccept
+    pub start_time: String,
+    //服务开始时间备注
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_time_remark: Option<String>,
+    //预计服务结束时间
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_time: Option<String>,
+    //预计服务结束时间备注
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_time_remark: Option<String>,
+}
+/// 服务位置
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct PayScoreLocation {
+    //服务开始地点
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_location: Option<String>,
+    //预计服务结束地点
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_location: Option<String>,
+}
+/// 订单风险金
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct PayScoreRiskFund {
+    //风险金名称
+    pub name: RiskFundName,
+    //风险金额
+    pub amount: i32,
+    //风险说明
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+/// 创建支付分订单
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqServiceOrder {
+    //商户服务订单号
+    pub out_order_no: String,
+    //应用ID,为空时使用配置中的 app_id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub appid: Option<String>,
+    //服务ID
+    pub service_id: String,
+    //服务信息
+    pub service_introduction: String,
+    //后付费项目
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_payments: Option<Vec<PayScorePostPayment>>,
+    //后付费商户优惠
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_discounts: Option<Vec<PayScorePostDiscount>>,
+    //服务时间段
+    pub time_range: PayScoreTimeRange,
+    //服务位置
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<PayScoreLocation>,
+    //订单风险金
+    pub risk_fund: PayScoreRiskFund,
+    //商户数据包
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attach: Option<String>,
+    //商户回调地址,为空时使用配置中的 notify_url
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notify_url: Option<String>,
+    //用户标识
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub openid: Option<String>,
+    //是否需要用户确认
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub need_user_confirm: Option<bool>,
+}
+/// 支付分优惠详情
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct PayScorePromotionDetail {
+    //券ID
+    pub coupon_id: String,
+    //优惠名称
+    pub name: Option<String>,
+    //优惠范围
+    pub scope: Option<String>,
+    //优惠类型
+    pub r#type: Option<String>,
+    //优惠券面额
+    pub amount: i32,
+    //活动ID
+    pub stock_id: Option<String>,
+    //微信出资
+    pub wechatpay_contribute: Option<i32>,
+    //商户出资
+    pub merchant_contribute: Option<i32>,
+    //其他出资
+    pub other_contribute: Option<i32>,
+    //优惠币种
+    pub currency: Option<String>,
+}
+/// 收款明细
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct PayScoreCollectionDetail {
+    //收款序号
+    pub seq: i32,
+    //单笔收款金额
+    pub amount: i32,
+    //收款成功渠道 NEWTON/MCH
+    pub paid_type: Option<String>,
+    //收款成功时间
+    pub paid_time: Option<String>,
+    //微信支付交易单号
+    pub transaction_id: Option<String>,
+    //优惠详情
+    pub promotion_detail: Option<Vec<PayScorePromotionDetail>>,
+}
+/// 收款信息
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct PayScoreCollection {
+    //收款状态
+    pub state: CollectionState,
+    //总收款金额
+    pub total_amount: Option<i32>,
+    //待收金额
+    pub paying_amount: Option<i32>,
+    //已收金额
+    pub paid_amount: Option<i32>,
+    //收款明细列表
+    pub details: Option<Vec<PayScoreCollectionDetail>>,
+}
+/// 支付分服务订单,创建、查询、修改、完结、同步及用户确认、支付成功通知均返回该结构
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ServiceOrder {
+    //应用ID
+    pub appid: String,
+    //商户号
+    pub mchid: String,
+    //商户服务订单号
+    pub out_order_no: String,
+    //服务ID
+    pub service_id: String,
+    //服务信息
+    pub service_introduction: Option<String>,
+    //服务订单状态
+    pub state: ServiceOrderState,
+    //订单状态说明
+    pub state_description: Option<ServiceOrderStateDescription>,
+    //后付费项目
+    pub post_payments: Option<Vec<PayScorePostPayment>>,
+    //后付费商户优惠
+    pub post_discounts: Option<Vec<PayScorePostDiscount>>,
+    //订单风险金
+    pub risk_fund: Option<PayScoreRiskFund>,
+    //总金额
+    pub total_amount: Option<i32>,
+    //是否需要收款
+    pub need_collection: Option<bool>,
+    //收款信息
+    pub collection: Option<PayScoreCollection>,
+    //服务时间段
+    pub time_range: Option<PayScoreTimeRange>,
+    //服务位置
+    pub location: Option<PayScoreLocation>,
+    //商户数据包
+    pub attach: Option<String>,
+    //商户回调地址
+    pub notify_url: Option<String>,
+    //微信支付服务订单号
+    pub order_id: Option<String>,
+    //跳转微信侧小程序订单数据,用于生成确认订单的签名包
+    pub package: Option<String>,
+    //用户标识
+    pub openid: Option<String>,
+}
+/// 取消、收款返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ServiceOrderBriefResponse {
+    //应用ID
+    pub appid: String,
+    //商户号
+    pub mchid: String,
+    //商户服务订单号
+    pub out_order_no: String,
+    //服务ID
+    pub service_id: String,
+    //微信支付服务订单号
+    pub order_id: String,
+}
+/// 取消支付分订单
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqCancelServiceOrder {
+    //应用ID,为空时使用配置中的 app_id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub appid: Option<String>,
+    //服务ID
+    pub service_id: String,
+    //取消原因
+    pub reason: String,
+}
+/// 修改订单金额
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqModifyServiceOrder {
+    //应用ID,为空时使用配置中的 app_id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub appid: Option<String>,
+    //服务ID
+    pub service_id: String,
+    //后付费项目
+    pub post_payments: Vec<PayScorePostPayment>,
+    //后付费商户优惠
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_discounts: Option<Vec<PayScorePostDiscount>>,
+    //总金额
+    pub total_amount: i32,
+    //修改原因
+    pub reason: String,
+}
+/// 完结支付分订单
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqCompleteServiceOrder {
+    //应用ID,为空时使用配置中的 app_id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub appid: Option<String>,
+    //服务ID
+    pub service_id: String,
+    //后付费项目
+    pub post_payments: Vec<PayScorePostPayment>,
+    //后付费商户优惠
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_discounts: Option<Vec<PayScorePostDiscount>>,
+    //总金额
+    pub total_amount: i32,
+    //实际服务时间段
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_range: Option<PayScoreTimeRange>,
+    //服务位置
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<PayScoreLocation>,
+    //微信支付服务分账标记
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profit_sharing: Option<bool>,
+    //订单优惠标记
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goods_tag: Option<String>,
+}
+/// 同步订单信息的内容
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct SyncServiceOrderDetail {
+    //收款成功时间 yyyyMMddHHmmss
+    pub paid_time: String,
+}
+/// 同步服务订单信息,用户通过其他方式完成支付后同步给微信支付分
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqSyncServiceOrder {
+    //应用ID,为空时使用配置中的 app_id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub appid: Option<String>,
+    //服务ID
+    pub service_id: String,
+    //场景类型,目前只有 Order_Paid
+    pub r#type: String,
+    //内容信息详情
+    pub detail: SyncServiceOrderDetail,
+}
+/// 商户预授权
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqPayScorePermission {
+    //服务ID
+    pub service_id: String,
+    //应用ID,为空时使用配置中的 app_id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub appid: Option<String>,
+    //授权协议号
+    pub authorization_code: String,
+    //商户接收授权回调通知的地址
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notify_url: Option<String>,
+}
+/// 商户预授权返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct PayScorePermissionResponse {
+    //预授权token,用于拉起授权页面
+    pub apply_permissions_token: String,
+}
+/// 用户授权记录
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct PayScoreAuthorization {
+    //服务ID
+    pub service_id: String,
+    //应用ID
+    pub appid: String,
+    //商户号
+    pub mchid: String,
+    //用户标识
+    pub openid: Option<String>,
+    //授权协议号
+    pub authorization_code: Option<String>,
+    //授权状态
+    pub authorization_state: AuthorizationState,
+    //授权通知地址
+    pub notify_url: Option<String>,
+    //最近一次解除授权时间
+    pub cancel_authorization_time: Option<String>,
+    //最近一次授权成功时间
+    pub authorization_success_time: Option<String>,
+}
+/// 开启/解除授权服务通知解密后的内容
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ResourcePayScorePermissionBody {
+    //应用ID
+    pub appid: String,
+    //商户号
+    pub mchid: String,
+    //服务ID
+    pub service_id: String,
+    //用户标识
+    pub openid: String,
+    //回调状态 USER_OPEN_SERVICE/USER_CLOSE_SERVICE
+    pub user_service_status: String,
+    //授权或解除授权时间
+    pub openorclose_time: String,
+    //授权协议号
+    pub authorization_code: Option<String>,
+}
+/// 调起支付分确认订单页面的签名包
+/// 小程序 wx.openBusinessView 的 extraData 直接使用该结构,APP 使用 to_query_string 的结果
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct PayScoreSignPackage {
+    //商户号
+    pub mch_id: String,
+    //服务订单返回的 package
+    pub package: String,
+    //时间戳
+    pub timestamp: String,
+    //随机字符串
+    pub nonce_str: String,
+    //签名类型,固定为 HMAC-SHA256
+    pub sign_type: String,
+    //签名
+    pub sign: String,
+}
+impl PayScoreSignPackage {
+    /// APP 调起确认订单页面时使用的 query 参数
+    pub fn to_query_string(&self) -> String {
+        format!(
+            "mch_id={}&package={}&timestamp={}&nonce_str={}&sign_type={}&sign={}",
+            self.mch_id, self.package, self.timestamp, self.nonce_str, self.sign_type, self.sign
+        )
+    }
+}
@@ -0,0 +1,318 @@
+use serde::{Deserialize, Serialize};
+/// 分账接收方类型
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ReceiverType {
+    //商户号
+    #[default]
+    MerchantId,
+    //个人openid（由父商户APPID转换得到）
+    PersonalOpenid,
+    //个人sub_openid（由子商户APPID转换得到）,仅服务商模式
+    PersonalSubOpenid,
+}
+/// 与分账方的关系类型
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RelationType {
+    //门店
+    Store,
+    //员工
+    Staff,
+    //店主
+    StoreOwner,
+    //合作伙伴
+    Partner,
+    //总部
+    Headquarter,
+    //品牌方
+    Brand,
+    //分销商
+    Distributor,
+    //用户
+    User,
+    //供应商
+    Supplier,
+    //自定义
+    #[default]
+    Custom,
+}
+/// 分账单状态
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub enum ProfitSharingState {
+    //处理中
+    #[default]
+    PROCESSING,
+    //分账完成
+    FINISHED,
+}
+/// 分账接收方的分账结果
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub enum ProfitSharingResult {
+    //待分账
+    #[default]
+    PENDING,
+    //分账成功
+    SUCCESS,
+    //已关闭
+    CLOSED,
+}
+/// 分账回退结果
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub enum ProfitSharingReturnResult {
+    //处理中
+    #[default]
+    PROCESSING,
+    //已成功
+    SUCCESS,
+    //已失败
+    FAILED,
+}
+// 分账接收方
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqProfitSharingReceiver {
+    //分账接收方类型
+    pub r#type: ReceiverType,
+    //分账接收方帐号
+    pub account: String,
+    //分账个人接收方姓名,明文传入,发起请求时会自动使用平台证书加密
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    //分账金额,单位为分
+    pub amount: i32,
+    //分账描述
+    pub description: String,
+}
+/// 请求分账
+/// appid,sub_mchid 为空时会根据配置自动填充
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqProfitSharingOrder {
+    //服务商子商户号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_mchid: Option<String>,
+    //应用ID,服务商模式下为服务商appid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub appid: Option<String>,
+    //子商户应用ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_appid: Option<String>,
+    //微信订单号
+    pub transaction_id: String,
+    //商户分账单号
+    pub out_order_no: String,
+    //分账接收方列表
+    pub receivers: Vec<ReqProfitSharingReceiver>,
+    //是否解冻剩余未分资金
+    pub unfreeze_unsplit: bool,
+}
+// 分账结果中的接收方
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct RespProfitSharingReceiver {
+    //分账金额
+    pub amount: i32,
+    //分账描述
+    pub description: String,
+    //分账接收方类型
+    pub r#type: ReceiverType,
+    //分账接收方帐号
+    pub account: String,
+    //分账结果
+    pub result: ProfitSharingResult,
+    //分账失败原因
+    pub fail_reason: Option<String>,
+    //分账创建时间
+    pub create_time: String,
+    //分账完成时间
+    pub finish_time: String,
+    //分账明细单号
+    pub detail_id: String,
+}
+/// 分账单返回,请求分账、查询分账、解冻剩余资金均返回此结构
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ProfitSharingOrderResponse {
+    //服务商子商户号
+    pub sub_mchid: Option<String>,
+    //微信订单号
+    pub transaction_id: String,
+    //商户分账单号
+    pub out_order_no: String,
+    //微信分账单号
+    pub order_id: String,
+    //分账单状态
+    pub state: ProfitSharingState,
+    //分账接收方列表
+    pub receivers: Option<Vec<RespProfitSharingReceiver>>,
+}
+/// 请求分账回退
+/// order_id 与 out_order_no 二选一
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqProfitSharingReturn {
+    //服务商子商户号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_mchid: Option<String>,
+    //微信分账单号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_id: Option<String>,
+    //商户分账单号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub out_order_no: Option<String>,
+    //商户回退单号
+    pub out_return_no: String,
+    //回退商户号
+    pub return_mchid: String,
+    //回退金额
+    pub amount: i32,
+    //回退描述
+    pub description: String,
+}
+/// 分账回退返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ProfitSharingReturnResponse {
+    //服务商子商户号
+    pub sub_mchid: Option<String>,
+    //微信分账单号
+    pub order_id: String,
+    //商户分账单号
+    pub out_order_no: String,
+    //商户回退单号
+    pub out_return_no: String,
+    //微信回退单号
+    pub return_id: String,
+    //回退商户号
+    pub return_mchid: String,
+    //回退金额
+    pub amount: i32,
+    //回退描述
+    pub description: String,
+    //回退结果
+    pub result: ProfitSharingReturnResult,
+    //失败原因
+    pub fail_reason: Option<String>,
+    //创建时间
+    pub create_time: String,
+    //完成时间
+    pub finish_time: Option<String>,
+}
+/// 解冻剩余资金
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqProfitSharingUnfreeze {
+    //服务商子商户号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_mchid: Option<String>,
+    //微信订单号
+    pub transaction_id: String,
+    //商户分账单号
+    pub out_order_no: String,
+    //分账描述
+    pub description: String,
+}
+/// 剩余待分金额返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ProfitSharingAmountResponse {
+    //微信订单号
+    pub transaction_id: String,
+    //订单剩余待分金额
+    pub unsplit_amount: i32,
+}
+/// 添加分账接收方
+/// appid,sub_mchid 为空时会根据配置自动填充
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqAddReceiver {
+    //服务商子商户号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_mchid: Option<String>,
+    //应用ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub appid: Option<String>,
+    //子商户应用ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_appid: Option<String>,
+    //分账接收方类型
+    pub r#type: ReceiverType,
+    //分账接收方帐号
+    pub account: String,
+    //分账接收方全称,明文传入,发起请求时会自动使用平台证书加密
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    //与分账方的关系类型
+    pub relation_type: RelationType,
+    //自定义的分账关系
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custom_relation: Option<String>,
+}
+/// 添加分账接收方返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct AddReceiverResponse {
+    //服务商子商户号
+    pub sub_mchid: Option<String>,
+    //分账接收方类型
+    pub r#type: ReceiverType,
+    //分账接收方帐号
+    pub account: String,
+    //分账接收方全称
+    pub name: Option<String>,
+    //与分账方的关系类型
+    pub relation_type: RelationType,
+    //自定义的分账关系
+    pub custom_relation: Option<String>,
+}
+/// 删除分账接收方
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqDeleteReceiver {
+    //服务商子商户号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_mchid: Option<String>,
+    //应用ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub appid: Option<String>,
+    //子商户应用ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_appid: Option<String>,
+    //分账接收方类型
+    pub r#type: ReceiverType,
+    //分账接收方帐号
+    pub account: String,
+}
+/// 删除分账接收方返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct DeleteReceiverResponse {
+    //服务商子商户号
+    pub sub_mchid: Option<String>,
+    //分账接收方类型
+    pub r#type: ReceiverType,
+    //分账接收方帐号
+    pub account: String,
+}
+// 分账动账通知接收方
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ResourceProfitSharingReceiver {
+    //分账接收方类型
+    pub r#type: ReceiverType,
+    //分账接收方帐号
+    pub account: String,
+    //分账动账金额
+    pub amount: i32,
+    //分账/回退描述
+    pub description: String,
+}
+/// 分账动账通知解密后内容
+/// event_type 为 PROFITSHARING.SUCCESS,PROFITSHARING.RETURN 等
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ResourceProfitSharingBody {
+    //服务商商户号
+    pub sp_mchid: Option<String>,
+    //服务商子商户号
+    pub sub_mchid: Option<String>,
+    //直连商户号
+    pub mchid: Option<String>,
+    //微信订单号
+    pub transaction_id: String,
+    //微信分账/回退单号
+    pub order_id: String,
+    //商户分账/回退单号
+    pub out_order_no: String,
+    //分账接收方
+    pub receiver: ResourceProfitSharingReceiver,
+    //成功时间
+    pub success_time: String,
+}
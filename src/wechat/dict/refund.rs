@@ -1,4 +1,41 @@
 use serde::{Deserialize, Serialize};
+/// 退款状态
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub enum RefundStatus {
+    //退款成功
+    SUCCESS,
+    //退款关闭
+    CLOSED,
+    //退款处理中
+    #[default]
+    PROCESSING,
+    //退款异常,需要人工处理或调用异常退款接口
+    ABNORMAL,
+}
+/// 退款渠道
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RefundChannel {
+    //原路退款
+    #[default]
+    Original,
+    //退回到余额
+    Balance,
+    //原账户异常退到其他余额账户
+    OtherBalance,
+    //原银行卡异常退到其他银行卡
+    OtherBankcard,
+}
+/// 异常退款处理方式
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AbnormalRefundType {
+    //退款到用户银行卡
+    #[default]
+    UserBankCard,
+    //退款至交易商户银行账户
+    MerchantBankCard,
+}
 // 退款通知退款金额
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct ResourceRefundAmount {
@@ -26,7 +63,7 @@ pub struct ResourceRefundBody {
     pub out_refund_no: String,
 
     //退款状态
-    pub refund_status: String,
+    pub refund_status: RefundStatus,
     //退款成功时间
     pub success_time: Option<String>,
     //当前退款单的退款入账方
@@ -149,7 +186,7 @@ pub struct RefundResponse {
     //商户订单号
     pub out_trade_no: String,
     //退款渠道
-    pub channel: String,
+    pub channel: RefundChannel,
     //退款入账账户
     pub user_received_account: String,
     //退款成功时间
@@ -157,7 +194,7 @@ pub struct RefundResponse {
     //退款创建时间
     pub create_time: String,
     //退款状态
-    pub status: String,
+    pub status: RefundStatus,
     //资金账户
     pub funds_account: String,
     //金额信息
@@ -165,3 +202,31 @@ pub struct RefundResponse {
     //优惠退款信息
     pub promotion_detail: Vec<ReqRefundPromotionDetail>,
 }
+impl RefundResponse {
+    /// 退款是否需要人工跟进,状态为 ABNORMAL 时需要调用异常退款接口或在商户平台处理
+    pub fn need_follow_up(&self) -> bool {
+        self.status == RefundStatus::ABNORMAL
+    }
+}
+/// 发起异常退款
+/// 退款到用户银行卡时 bank_type,bank_account,real_name 必填
+/// bank_account,real_name 明文传入,发起请求时会自动使用平台证书加密
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqAbnormalRefund {
+    //子商户号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_mchid: Option<String>,
+    //商户退款单号
+    pub out_refund_no: String,
+    //异常退款处理方式
+    pub r#type: AbnormalRefundType,
+    //开户银行
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bank_type: Option<String>,
+    //收款银行卡号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bank_account: Option<String>,
+    //收款用户姓名
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub real_name: Option<String>,
+}
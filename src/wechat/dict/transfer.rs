@@ -0,0 +1,415 @@
+use serde::{Deserialize, Serialize};
+/// 转账批次状态
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub enum TransferBatchStatus {
+    //已受理
+    #[default]
+    ACCEPTED,
+    //转账中
+    PROCESSING,
+    //已完成
+    FINISHED,
+    //已关闭
+    CLOSED,
+}
+/// 转账明细状态
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TransferDetailStatus {
+    //初始态
+    #[default]
+    Init,
+    //待确认
+    WaitPay,
+    //转账中
+    Processing,
+    //转账成功
+    Success,
+    //转账失败
+    Fail,
+}
+/// 转账单据状态
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TransferBillState {
+    //转账已受理
+    #[default]
+    Accepted,
+    //转账锁定资金中
+    Processing,
+    //待收款用户确认
+    WaitUserConfirm,
+    //转账中
+    Transfering,
+    //转账成功
+    Success,
+    //转账失败
+    Fail,
+    //商户撤销请求受理成功
+    Canceling,
+    //转账撤销完成
+    Cancelled,
+}
+/// 电子回单状态
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub enum TransferReceiptState {
+    //已受理
+    #[default]
+    ACCEPTED,
+    //处理中
+    GENERATING,
+    //已完成
+    FINISHED,
+    //已失败
+    FAILED,
+}
+// 转账明细
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqTransferDetail {
+    //商家明细单号
+    pub out_detail_no: String,
+    //转账金额,单位为分
+    pub transfer_amount: i32,
+    //转账备注
+    pub transfer_remark: String,
+    //收款用户openid
+    pub openid: String,
+    //收款用户姓名,明文传入,发起请求时会自动使用平台证书加密
+    //转账金额>=2000元时必填
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_name: Option<String>,
+}
+/// 发起商家转账批次
+/// appid 为空时会使用配置中的app_id, total_amount,total_num 为0时会根据明细自动计算
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqTransferBatch {
+    //商户appid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub appid: Option<String>,
+    //商家批次单号
+    pub out_batch_no: String,
+    //批次名称
+    pub batch_name: String,
+    //批次备注
+    pub batch_remark: String,
+    //转账总金额
+    pub total_amount: i64,
+    //转账总笔数
+    pub total_num: i32,
+    //转账明细列表
+    pub transfer_detail_list: Vec<ReqTransferDetail>,
+    //转账场景ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transfer_scene_id: Option<String>,
+    //通知地址
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notify_url: Option<String>,
+}
+/// 发起商家转账批次返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct TransferBatchResponse {
+    //商家批次单号
+    pub out_batch_no: String,
+    //微信批次单号
+    pub batch_id: String,
+    //批次创建时间
+    pub create_time: String,
+    //批次状态
+    pub batch_status: Option<TransferBatchStatus>,
+}
+/// 查询转账批次的参数
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqTransferBatchQuery {
+    //是否查询转账明细单
+    pub need_query_detail: bool,
+    //请求资源起始位置
+    pub offset: Option<i32>,
+    //最大资源条数
+    pub limit: Option<i32>,
+    //明细状态 ALL,SUCCESS,FAIL
+    pub detail_status: Option<String>,
+}
+// 转账批次单基本信息
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct RespTransferBatch {
+    //商户号
+    pub mchid: String,
+    //商家批次单号
+    pub out_batch_no: String,
+    //微信批次单号
+    pub batch_id: String,
+    //商户appid
+    pub appid: String,
+    //批次状态
+    pub batch_status: TransferBatchStatus,
+    //批次类型 API,WEB
+    pub batch_type: String,
+    //批次名称
+    pub batch_name: String,
+    //批次备注
+    pub batch_remark: String,
+    //批次关闭原因
+    pub close_reason: Option<String>,
+    //转账总金额
+    pub total_amount: i64,
+    //转账总笔数
+    pub total_num: i32,
+    //批次创建时间
+    pub create_time: Option<String>,
+    //批次更新时间
+    pub update_time: Option<String>,
+    //转账成功金额
+    pub success_amount: Option<i64>,
+    //转账成功笔数
+    pub success_num: Option<i32>,
+    //转账失败金额
+    pub fail_amount: Option<i64>,
+    //转账失败笔数
+    pub fail_num: Option<i32>,
+    //转账场景ID
+    pub transfer_scene_id: Option<String>,
+}
+// 转账批次中的明细
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct RespTransferDetailItem {
+    //微信明细单号
+    pub detail_id: String,
+    //商家明细单号
+    pub out_detail_no: String,
+    //明细状态
+    pub detail_status: TransferDetailStatus,
+}
+/// 查询转账批次返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct TransferBatchQueryResponse {
+    //转账批次单
+    pub transfer_batch: RespTransferBatch,
+    //转账明细单列表
+    pub transfer_detail_list: Option<Vec<RespTransferDetailItem>>,
+}
+/// 查询转账明细返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct TransferDetailResponse {
+    //商户号
+    pub mchid: String,
+    //商家批次单号
+    pub out_batch_no: String,
+    //微信批次单号
+    pub batch_id: String,
+    //商户appid
+    pub appid: String,
+    //商家明细单号
+    pub out_detail_no: String,
+    //微信明细单号
+    pub detail_id: String,
+    //明细状态
+    pub detail_status: TransferDetailStatus,
+    //转账金额
+    pub transfer_amount: i32,
+    //转账备注
+    pub transfer_remark: String,
+    //明细失败原因
+    pub fail_reason: Option<String>,
+    //收款用户openid
+    pub openid: String,
+    //收款用户姓名,已加密
+    pub user_name: Option<String>,
+    //转账发起时间
+    pub initiate_time: String,
+    //明细更新时间
+    pub update_time: String,
+}
+/// 转账批次电子回单返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct TransferReceiptResponse {
+    //商家批次单号
+    pub out_batch_no: String,
+    //电子回单申请单号
+    pub signature_no: String,
+    //电子回单状态
+    pub signature_status: Option<TransferReceiptState>,
+    //哈希类型
+    pub hash_type: Option<String>,
+    //哈希值
+    pub hash_value: Option<String>,
+    //电子回单文件的下载地址
+    pub download_url: Option<String>,
+    //创建时间
+    pub create_time: Option<String>,
+    //更新时间
+    pub update_time: Option<String>,
+}
+/// 申请转账明细电子回单
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqTransferDetailReceipt {
+    //受理类型 BATCH_TRANSFER,TRANSFER_TO_POCKET,TRANSFER_TO_BANK
+    pub accept_type: String,
+    //商家转账批次单号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub out_batch_no: Option<String>,
+    //商家转账明细单号
+    pub out_detail_no: String,
+}
+/// 转账明细电子回单返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct TransferDetailReceiptResponse {
+    //受理类型
+    pub accept_type: String,
+    //商家转账批次单号
+    pub out_batch_no: Option<String>,
+    //商家转账明细单号
+    pub out_detail_no: String,
+    //电子回单受理单号
+    pub signature_no: Option<String>,
+    //电子回单状态
+    pub state: Option<TransferReceiptState>,
+    //哈希类型
+    pub hash_type: Option<String>,
+    //哈希值
+    pub hash_value: Option<String>,
+    //电子回单文件的下载地址
+    pub download_url: Option<String>,
+}
+// 转账场景报备信息
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqTransferSceneReportInfo {
+    //信息类型
+    pub info_type: String,
+    //信息内容
+    pub info_content: String,
+}
+/// 发起转账单据
+/// appid 为空时会使用配置中的app_id
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqTransferBill {
+    //商户appid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub appid: Option<String>,
+    //商户单号
+    pub out_bill_no: String,
+    //转账场景ID
+    pub transfer_scene_id: String,
+    //收款用户openid
+    pub openid: String,
+    //收款用户姓名,明文传入,发起请求时会自动使用平台证书加密
+    //转账金额>=2000元时必填
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_name: Option<String>,
+    //转账金额,单位为分
+    pub transfer_amount: i32,
+    //转账备注
+    pub transfer_remark: String,
+    //通知地址
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notify_url: Option<String>,
+    //用户收款感知
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_recv_perception: Option<String>,
+    //转账场景报备信息
+    pub transfer_scene_report_infos: Vec<ReqTransferSceneReportInfo>,
+}
+/// 发起转账单据返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct TransferBillResponse {
+    //商户单号
+    pub out_bill_no: String,
+    //微信转账单号
+    pub transfer_bill_no: String,
+    //单据创建时间
+    pub create_time: String,
+    //单据状态
+    pub state: TransferBillState,
+    //失败原因
+    pub fail_reason: Option<String>,
+    //跳转领取页面的package信息
+    pub package_info: Option<String>,
+}
+/// 查询转账单据返回,转账单据通知解密后内容也使用此结构
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct TransferBillQueryResponse {
+    //商户号
+    pub mch_id: String,
+    //商户单号
+    pub out_bill_no: String,
+    //微信转账单号
+    pub transfer_bill_no: String,
+    //商户appid
+    pub appid: Option<String>,
+    //单据状态
+    pub state: TransferBillState,
+    //转账金额
+    pub transfer_amount: i32,
+    //转账备注
+    pub transfer_remark: Option<String>,
+    //失败原因
+    pub fail_reason: Option<String>,
+    //收款用户openid
+    pub openid: Option<String>,
+    //收款用户姓名,已加密
+    pub user_name: Option<String>,
+    //单据创建时间
+    pub create_time: String,
+    //最后一次状态变更时间
+    pub update_time: String,
+}
+/// 撤销转账单据返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct TransferBillCancelResponse {
+    //商户单号
+    pub out_bill_no: String,
+    //微信转账单号
+    pub transfer_bill_no: String,
+    //单据状态
+    pub state: TransferBillState,
+    //最后一次状态变更时间
+    pub update_time: String,
+}
+/// 转账单据电子回单返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct TransferBillReceiptResponse {
+    //商户单号
+    pub out_bill_no: String,
+    //电子回单状态
+    pub state: TransferReceiptState,
+    //哈希类型
+    pub hash_type: Option<String>,
+    //哈希值
+    pub hash_value: Option<String>,
+    //电子回单文件的下载地址
+    pub download_url: Option<String>,
+    //失败原因
+    pub fail_reason: Option<String>,
+    //创建时间
+    pub create_time: Option<String>,
+    //更新时间
+    pub update_time: Option<String>,
+}
+/// 商家转账批次回调通知解密后内容
+/// event_type 为 MCHTRANSFER.BATCH.FINISHED 或 MCHTRANSFER.BATCH.CLOSED
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ResourceTransferBatchBody {
+    //商户号
+    pub mchid: String,
+    //商家批次单号
+    pub out_batch_no: String,
+    //微信批次单号
+    pub batch_id: String,
+    //批次状态
+    pub batch_status: TransferBatchStatus,
+    //批次总笔数
+    pub total_num: i32,
+    //批次总金额
+    pub total_amount: i64,
+    //转账成功金额
+    pub success_amount: Option<i64>,
+    //转账成功笔数
+    pub success_num: Option<i32>,
+    //转账失败金额
+    pub fail_amount: Option<i64>,
+    //转账失败笔数
+    pub fail_num: Option<i32>,
+    //批次更新时间
+    pub update_time: String,
+    //批次关闭原因
+    pub close_reason: Option<String>,
+}
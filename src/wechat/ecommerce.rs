@@ -0,0 +1,301 @@
+use crate::wechat::prelude::*;
+use crate::*;
+/// 电商收付通
+/// 配置需为服务商模式,mchid 为二级商户号,请求中 sub_mchid 为空时使用配置中的 mchid
+/// 退款结果通知与普通退款相同,使用 BaseTrait::notify 处理
+pub trait EcommerceTrait {
+    /// 申请退款,二级商户余额不足时可指定 refund_account 由平台垫付
+    fn ecommerce_refund(&self, data: ReqEcommerceRefund) -> BoxFuture<'_, EcommerceRefundResponse>;
+    /// 通过微信退款单号查询退款
+    fn query_ecommerce_refund(
+        &self,
+        refund_id: &str,
+    ) -> BoxFuture<'_, EcommerceRefundQueryResponse>;
+    /// 通过商户退款单号查询退款
+    fn query_ecommerce_refund_by_out_no(
+        &self,
+        out_refund_no: &str,
+    ) -> BoxFuture<'_, EcommerceRefundQueryResponse>;
+    /// 垫付退款回补,平台垫付的退款成功后从二级商户账户回补垫付资金
+    fn return_advance(&self, refund_id: &str) -> BoxFuture<'_, ReturnAdvanceResponse>;
+    /// 查询垫付回补结果
+    fn query_return_advance(&self, refund_id: &str) -> BoxFuture<'_, ReturnAdvanceResponse>;
+    /// 请求补差
+    fn create_subsidy(&self, data: ReqSubsidyCreate) -> BoxFuture<'_, SubsidyCreateResponse>;
+    /// 请求补差回退
+    fn return_subsidy(&self, data: ReqSubsidyReturn) -> BoxFuture<'_, SubsidyReturnResponse>;
+    /// 取消补差
+    fn cancel_subsidy(&self, data: ReqSubsidyCancel) -> BoxFuture<'_, SubsidyCancelResponse>;
+    /// 查询二级商户账户实时余额,account_type 为空时查询基本账户
+    fn query_ecommerce_balance(
+        &self,
+        account_type: Option<FundBillAccountType>,
+    ) -> BoxFuture<'_, EcommerceBalanceResponse>;
+    /// 查询二级商户账户日终余额,date 格式为 YYYY-MM-DD
+    fn query_ecommerce_end_day_balance(
+        &self,
+        date: &str,
+    ) -> BoxFuture<'_, EcommerceEndDayBalanceResponse>;
+    /// 二级商户余额提现
+    fn ecommerce_withdraw(
+        &self,
+        data: ReqEcommerceWithdraw,
+    ) -> BoxFuture<'_, EcommerceWithdrawResponse>;
+    /// 通过微信支付提现单号查询提现状态
+    fn query_ecommerce_withdraw(
+        &self,
+        withdraw_id: &str,
+    ) -> BoxFuture<'_, EcommerceWithdrawQueryResponse>;
+    /// 通过商户提现单号查询提现状态
+    fn query_ecommerce_withdraw_by_out_no(
+        &self,
+        out_request_no: &str,
+    ) -> BoxFuture<'_, EcommerceWithdrawQueryResponse>;
+}
+impl EcommerceTrait for Payment<WechatConfig> {
+    fn ecommerce_refund(&self, data: ReqEcommerceRefund) -> BoxFuture<'_, EcommerceRefundResponse> {
+        let new_data = ReqEcommerceRefund {
+            sub_mchid: data.sub_mchid.clone().or(Some(self.config.mchid.clone())),
+            sp_appid: data.sp_appid.clone().or(self.config.sp_appid.clone()),
+            notify_url: data
+                .notify_url
+                .clone()
+                .or(Some(self.config.notify_url.clone())),
+            ..data
+        };
+        Box::pin(async move {
+            let body = serde_json::to_string(&new_data)?;
+            self.do_request::<EcommerceRefundResponse>("/v3/ecommerce/refunds/apply", "POST", &body)
+                .await
+        })
+    }
+    fn query_ecommerce_refund(
+        &self,
+        refund_id: &str,
+    ) -> BoxFuture<'_, EcommerceRefundQueryResponse> {
+        let url = format!(
+            "/v3/ecommerce/refunds/id/{}?sub_mchid={}",
+            refund_id, self.config.mchid
+        );
+        Box::pin(async move {
+            self.do_request::<EcommerceRefundQueryResponse>(&url, "GET", "")
+                .await
+        })
+    }
+    fn query_ecommerce_refund_by_out_no(
+        &self,
+        out_refund_no: &str,
+    ) -> BoxFuture<'_, EcommerceRefundQueryResponse> {
+        let url = format!(
+            "/v3/ecommerce/refunds/out-refund-no/{}?sub_mchid={}",
+            out_refund_no, self.config.mchid
+        );
+        Box::pin(async move {
+            self.do_request::<EcommerceRefundQueryResponse>(&url, "GET", "")
+                .await
+        })
+    }
+    fn return_advance(&self, refund_id: &str) -> BoxFuture<'_, ReturnAdvanceResponse> {
+        let url = format!("/v3/ecommerce/refunds/{}/return-advance", refund_id);
+        let body = serde_json::json!({ "sub_mchid": self.config.mchid }).to_string();
+        Box::pin(async move {
+            self.do_request::<ReturnAdvanceResponse>(&url, "POST", &body)
+                .await
+        })
+    }
+    fn query_return_advance(&self, refund_id: &str) -> BoxFuture<'_, ReturnAdvanceResponse> {
+        let url = format!(
+            "/v3/ecommerce/refunds/{}/return-advance?sub_mchid={}",
+            refund_id, self.config.mchid
+        );
+        Box::pin(async move {
+            self.do_request::<ReturnAdvanceResponse>(&url, "GET", "")
+                .await
+        })
+    }
+    fn create_subsidy(&self, data: ReqSubsidyCreate) -> BoxFuture<'_, SubsidyCreateResponse> {
+        let new_data = ReqSubsidyCreate {
+            sub_mchid: data.sub_mchid.clone().or(Some(self.config.mchid.clone())),
+            ..data
+        };
+        Box::pin(async move {
+            let body = serde_json::to_string(&new_data)?;
+            self.do_request::<SubsidyCreateResponse>(
+                "/v3/ecommerce/subsidies/create",
+                "POST",
+                &body,
+            )
+            .await
+        })
+    }
+    fn return_subsidy(&self, data: ReqSubsidyReturn) -> BoxFuture<'_, SubsidyReturnResponse> {
+        let new_data = ReqSubsidyReturn {
+            sub_mchid: data.sub_mchid.clone().or(Some(self.config.mchid.clone())),
+            ..data
+        };
+        Box::pin(async move {
+            let body = serde_json::to_string(&new_data)?;
+            self.do_request::<SubsidyReturnResponse>(
+                "/v3/ecommerce/subsidies/return",
+                "POST",
+                &body,
+            )
+            .await
+        })
+    }
+    fn cancel_subsidy(&self, data: ReqSubsidyCancel) -> BoxFuture<'_, SubsidyCancelResponse> {
+        let new_data = ReqSubsidyCancel {
+            sub_mchid: data.sub_mchid.clone().or(Some(self.config.mchid.clone())),
+            ..data
+        };
+        Box::pin(async move {
+            let body = serde_json::to_string(&new_data)?;
+            self.do_request::<SubsidyCancelResponse>(
+                "/v3/ecommerce/subsidies/cancel",
+                "POST",
+                &body,
+            )
+            .await
+        })
+    }
+    fn query_ecommerce_balance(
+        &self,
+        account_type: Option<FundBillAccountType>,
+    ) -> BoxFuture<'_, EcommerceBalanceResponse> {
+        let url = ecommerce_balance_url(&self.config.mchid, account_type);
+        Box::pin(async move {
+            self.do_request::<EcommerceBalanceResponse>(&url, "GET", "")
+                .await
+        })
+    }
+    fn query_ecommerce_end_day_balance(
+        &self,
+        date: &str,
+    ) -> BoxFuture<'_, EcommerceEndDayBalanceResponse> {
+        let url = ecommerce_end_day_balance_url(&self.config.mchid, date);
+        Box::pin(async move {
+            self.do_request::<EcommerceEndDayBalanceResponse>(&url, "GET", "")
+                .await
+        })
+    }
+    fn ecommerce_withdraw(
+        &self,
+        data: ReqEcommerceWithdraw,
+    ) -> BoxFuture<'_, EcommerceWithdrawResponse> {
+        let new_data = ReqEcommerceWithdraw {
+            sub_mchid: data.sub_mchid.clone().or(Some(self.config.mchid.clone())),
+            ..data
+        };
+        Box::pin(async move {
+            let body = serde_json::to_string(&new_data)?;
+            self.do_request::<EcommerceWithdrawResponse>(
+                "/v3/ecommerce/fund/withdraw",
+                "POST",
+                &body,
+            )
+            .await
+        })
+    }
+    fn query_ecommerce_withdraw(
+        &self,
+        withdraw_id: &str,
+    ) -> BoxFuture<'_, EcommerceWithdrawQueryResponse> {
+        let url = format!(
+            "/v3/ecommerce/fund/withdraw/{}?sub_mchid={}",
+            withdraw_id, self.config.mchid
+        );
+        Box::pin(async move {
+            self.do_request::<EcommerceWithdrawQueryResponse>(&url, "GET", "")
+                .await
+        })
+    }
+    fn query_ecommerce_withdraw_by_out_no(
+        &self,
+        out_request_no: &str,
+    ) -> BoxFuture<'_, EcommerceWithdrawQueryResponse> {
+        let url = format!(
+            "/v3/ecommerce/fund/withdraw/out-request-no/{}?sub_mchid={}",
+            out_request_no, self.config.mchid
+        );
+        Box::pin(async move {
+            self.do_request::<EcommerceWithdrawQueryResponse>(&url, "GET", "")
+                .await
+        })
+    }
+}
+/// 二级商户实时余额查询地址
+pub(crate) fn ecommerce_balance_url(
+    sub_mchid: &str,
+    account_type: Option<FundBillAccountType>,
+) -> String {
+    format!(
+        "/v3/ecommerce/fund/balances/{}?account_type={}",
+        sub_mchid,
+        account_type.unwrap_or_default().as_str()
+    )
+}
+/// 二级商户日终余额查询地址
+pub(crate) fn ecommerce_end_day_balance_url(sub_mchid: &str, date: &str) -> String {
+    format!(
+        "/v3/ecommerce/fund/enddaybalances/{}?date={}",
+        sub_mchid, date
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_ecommerce_refund_request() {
+        let data = ReqEcommerceRefund {
+            sub_mchid: Some("1900000109".to_string()),
+            out_trade_no: Some("T20240701001".to_string()),
+            out_refund_no: "R20240701001".to_string(),
+            amount: ReqEcommerceRefundAmount {
+                refund: 100,
+                total: 100,
+                currency: None,
+            },
+            refund_account: Some(EcommerceRefundAccount::RefundSourcePartnerAdvance),
+            ..Default::default()
+        };
+        let body: serde_json::Value = serde_json::to_value(&data).unwrap();
+        assert_eq!(body["refund_account"], "REFUND_SOURCE_PARTNER_ADVANCE");
+        assert!(body.get("transaction_id").is_none());
+        assert!(body["amount"].get("currency").is_none());
+    }
+    #[test]
+    fn test_ecommerce_refund_query_response() {
+        let content = r#"{"refund_id":"50000000382019052709732678859","out_refund_no":"R20240701001","transaction_id":"4200000001202407010000000001","out_trade_no":"T20240701001","channel":"ORIGINAL","user_received_account":"招商银行信用卡0403","success_time":"2024-07-01T10:00:00+08:00","create_time":"2024-07-01T09:59:00+08:00","status":"SUCCESS","amount":{"refund":100,"payer_refund":100,"discount_refund":0,"currency":"CNY","advance":100},"refund_account":"REFUND_SOURCE_PARTNER_ADVANCE","funds_account":"AVAILABLE"}"#;
+        let resp: EcommerceRefundQueryResponse = serde_json::from_str(content).unwrap();
+        assert_eq!(resp.amount.advance, Some(100));
+        assert!(resp.need_return_advance());
+        let resp = EcommerceRefundQueryResponse {
+            status: RefundStatus::PROCESSING,
+            ..resp
+        };
+        assert!(!resp.need_return_advance());
+    }
+    #[test]
+    fn test_withdraw_status() {
+        let content = r#"{"sub_mchid":"1900000109","sp_mchid":"1900000100","status":"CREATE_SUCCESS","withdraw_id":"12321937198237912739132791732123","out_request_no":"W20240701001","amount":1000,"create_time":"2024-07-01T10:00:00+08:00","update_time":"2024-07-01T10:00:00+08:00","account_type":"BASIC"}"#;
+        let resp: EcommerceWithdrawQueryResponse = serde_json::from_str(content).unwrap();
+        assert_eq!(resp.status, WithdrawStatus::CreateSuccess);
+        assert_eq!(resp.account_type, Some(FundBillAccountType::BASIC));
+    }
+    #[test]
+    fn test_balance_url() {
+        assert_eq!(
+            ecommerce_balance_url("1900000109", None),
+            "/v3/ecommerce/fund/balances/1900000109?account_type=BASIC"
+        );
+        assert_eq!(
+            ecommerce_balance_url("1900000109", Some(FundBillAccountType::FEES)),
+            "/v3/ecommerce/fund/balances/1900000109?account_type=FEES"
+        );
+        assert_eq!(
+            ecommerce_end_day_balance_url("1900000109", "2024-07-01"),
+            "/v3/ecommerce/fund/enddaybalances/1900000109?date=2024-07-01"
+        );
+    }
+}
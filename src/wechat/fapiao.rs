@@ -0,0 +1,356 @@
+use crate::utils::*;
+use crate::wechat::prelude::*;
+use crate::*;
+/// 电子发票(公共应用)
+/// 开票、冲红均为异步受理,结果通过 FAPIAO.ISSUED/FAPIAO.REVERSED 通知,使用 BaseTrait::notify 处理
+pub trait FapiaoTrait {
+    /// 配置开发选项,设置回调地址及是否在支付成功页展示开票入口
+    fn update_fapiao_development_config(
+        &self,
+        data: FapiaoDevelopmentConfig,
+    ) -> BoxFuture<'_, FapiaoDevelopmentConfig>;
+    /// 查询商户配置的开发选项
+    fn query_fapiao_development_config(&self) -> BoxFuture<'_, FapiaoDevelopmentConfig>;
+    /// 创建电子发票卡券模板,插卡前需要先创建模板
+    fn create_fapiao_card_template(
+        &self,
+        data: ReqFapiaoCardTemplate,
+    ) -> BoxFuture<'_, FapiaoCardTemplateResponse>;
+    /// 查询商户开票基础信息
+    fn query_fapiao_base_information(&self) -> BoxFuture<'_, FapiaoBaseInformation>;
+    /// 获取商品和服务税收分类对照表
+    fn list_fapiao_tax_codes(
+        &self,
+        offset: i32,
+        limit: i32,
+    ) -> BoxFuture<'_, FapiaoTaxCodeListResponse>;
+    /// 获取抬头填写链接,用于非支付成功页引导用户填写抬头
+    fn fapiao_title_url(&self, data: ReqFapiaoTitleUrl) -> BoxFuture<'_, FapiaoTitleUrlResponse>;
+    /// 获取用户填写的抬头,收到 FAPIAO.USER_APPLIED 通知后调用,手机号和邮箱会自动解密
+    fn query_fapiao_user_title(
+        &self,
+        fapiao_apply_id: &str,
+    ) -> BoxFuture<'_, FapiaoBuyerInformation>;
+    /// 开具电子发票,购买方手机号和邮箱会自动加密
+    fn issue_fapiao(&self, data: ReqFapiaoApplication) -> BoxFuture<'_, ()>;
+    /// 根据支付成功通知自动开票,获取用户填写的抬头后按订单金额开具
+    fn issue_fapiao_for_order<'a>(
+        &'a self,
+        order: &'a ResourceOrderBody,
+        item: FapiaoItem,
+    ) -> BoxFuture<'a, ()>;
+    /// 查询电子发票,fapiao_id 为空时查询申请单下全部发票
+    fn query_fapiao<'a>(
+        &'a self,
+        fapiao_apply_id: &'a str,
+        fapiao_id: Option<&'a str>,
+    ) -> BoxFuture<'a, FapiaoApplicationResponse>;
+    /// 冲红电子发票
+    fn reverse_fapiao(&self, fapiao_apply_id: &str, data: ReqReverseFapiao) -> BoxFuture<'_, ()>;
+    /// 上传电子发票文件,支持 PDF、OFD,使用 SM3 计算摘要
+    fn upload_fapiao_file<'a>(
+        &'a self,
+        file_name: &'a str,
+        content: Vec<u8>,
+    ) -> BoxFuture<'a, FapiaoFileUploadResponse>;
+    /// 将电子发票插入微信用户卡包,购买方手机号和邮箱会自动加密
+    fn insert_fapiao_cards(
+        &self,
+        fapiao_apply_id: &str,
+        data: ReqInsertFapiaoCards,
+    ) -> BoxFuture<'_, ()>;
+}
+impl FapiaoTrait for Payment<WechatConfig> {
+    fn update_fapiao_development_config(
+        &self,
+        data: FapiaoDevelopmentConfig,
+    ) -> BoxFuture<'_, FapiaoDevelopmentConfig> {
+        Box::pin(async move {
+            let body = serde_json::to_string(&data)?;
+            self.do_request::<FapiaoDevelopmentConfig>(
+                "/v3/new-tax-control-fapiao/merchant/development-config",
+                "PATCH",
+                &body,
+            )
+            .await
+        })
+    }
+    fn query_fapiao_development_config(&self) -> BoxFuture<'_, FapiaoDevelopmentConfig> {
+        Box::pin(async move {
+            self.do_request::<FapiaoDevelopmentConfig>(
+                "/v3/new-tax-control-fapiao/merchant/development-config",
+                "GET",
+                "",
+            )
+            .await
+        })
+    }
+    fn create_fapiao_card_template(
+        &self,
+        data: ReqFapiaoCardTemplate,
+    ) -> BoxFuture<'_, FapiaoCardTemplateResponse> {
+        let new_data = ReqFapiaoCardTemplate {
+            card_appid: data.card_appid.clone().or(Some(self.config.app_id.clone())),
+            ..data
+        };
+        Box::pin(async move {
+            let body = serde_json::to_string(&new_data)?;
+            self.do_request::<FapiaoCardTemplateResponse>(
+                "/v3/new-tax-control-fapiao/card-template",
+                "POST",
+                &body,
+            )
+            .await
+        })
+    }
+    fn query_fapiao_base_information(&self) -> BoxFuture<'_, FapiaoBaseInformation> {
+        Box::pin(async move {
+            self.do_request::<FapiaoBaseInformation>(
+                "/v3/new-tax-control-fapiao/merchant/base-information",
+                "GET",
+                "",
+            )
+            .await
+        })
+    }
+    fn list_fapiao_tax_codes(
+        &self,
+        offset: i32,
+        limit: i32,
+    ) -> BoxFuture<'_, FapiaoTaxCodeListResponse> {
+        let url = format!(
+            "/v3/new-tax-control-fapiao/merchant/tax-codes?offset={}&limit={}",
+            offset, limit
+        );
+        Box::pin(async move {
+            self.do_request::<FapiaoTaxCodeListResponse>(&url, "GET", "")
+                .await
+        })
+    }
+    fn fapiao_title_url(&self, data: ReqFapiaoTitleUrl) -> BoxFuture<'_, FapiaoTitleUrlResponse> {
+        Box::pin(async move {
+            let appid = data.appid.clone().unwrap_or(self.config.app_id.clone());
+            let total_amount = data.total_amount.to_string();
+            let url = with_query(
+                "/v3/new-tax-control-fapiao/user-title/title-url",
+                &[
+                    ("fapiao_apply_id", &data.fapiao_apply_id),
+                    ("appid", &appid),
+                    ("openid", &data.openid),
+                    ("total_amount", &total_amount),
+                    ("source", &data.source),
+                ],
+            )?;
+            self.do_request::<FapiaoTitleUrlResponse>(&url, "GET", "")
+                .await
+        })
+    }
+    fn query_fapiao_user_title(
+        &self,
+        fapiao_apply_id: &str,
+    ) -> BoxFuture<'_, FapiaoBuyerInformation> {
+        let fapiao_apply_id = fapiao_apply_id.to_string();
+        Box::pin(async move {
+            let url = with_query(
+                "/v3/new-tax-control-fapiao/user-title",
+                &[
+                    ("fapiao_apply_id", &fapiao_apply_id),
+                    ("scene", "WITH_WECHATPAY"),
+                ],
+            )?;
+            let mut title = self
+                .do_request::<FapiaoBuyerInformation>(&url, "GET", "")
+                .await?;
+            let apiclient_key = self.merchant_cert().0;
+            title.phone = decrypt_optional(title.phone, &apiclient_key)?;
+            title.email = decrypt_optional(title.email, &apiclient_key)?;
+            Ok(title)
+        })
+    }
+    fn issue_fapiao(&self, data: ReqFapiaoApplication) -> BoxFuture<'_, ()> {
+        let mut new_data = data;
+        Box::pin(async move {
+            let (serial_no, cert_file) = self.get_platform_cert().await?;
+            encrypt_buyer(&mut new_data.buyer_information, &cert_file)?;
+            let body = serde_json::to_string(&new_data)?;
+            let req_builder = self
+                .build_request_builder(
+                    "/v3/new-tax-control-fapiao/fapiao-applications",
+                    "POST",
+                    &body,
+                )?
+                .header("Wechatpay-Serial", serial_no);
+            self.send_request::<()>(req_builder).await
+        })
+    }
+    fn issue_fapiao_for_order<'a>(
+        &'a self,
+        order: &'a ResourceOrderBody,
+        item: FapiaoItem,
+    ) -> BoxFuture<'a, ()> {
+        Box::pin(async move {
+            let buyer = self.query_fapiao_user_title(&order.transaction_id).await?;
+            let data = ReqFapiaoApplication::from_order(order, buyer, item);
+            self.issue_fapiao(data).await
+        })
+    }
+    fn query_fapiao<'a>(
+        &'a self,
+        fapiao_apply_id: &'a str,
+        fapiao_id: Option<&'a str>,
+    ) -> BoxFuture<'a, FapiaoApplicationResponse> {
+        Box::pin(async move {
+            let path = format!(
+                "/v3/new-tax-control-fapiao/fapiao-applications/{}",
+                fapiao_apply_id
+            );
+            let url = match fapiao_id {
+                Some(fapiao_id) => with_query(&path, &[("fapiao_id", fapiao_id)])?,
+                None => path,
+            };
+            self.do_request::<FapiaoApplicationResponse>(&url, "GET", "")
+                .await
+        })
+    }
+    fn reverse_fapiao(&self, fapiao_apply_id: &str, data: ReqReverseFapiao) -> BoxFuture<'_, ()> {
+        let url = format!(
+            "/v3/new-tax-control-fapiao/fapiao-applications/{}/reverse",
+            fapiao_apply_id
+        );
+        Box::pin(async move {
+            let body = serde_json::to_string(&data)?;
+            self.do_request::<()>(&url, "POST", &body).await
+        })
+    }
+    fn upload_fapiao_file<'a>(
+        &'a self,
+        file_name: &'a str,
+        content: Vec<u8>,
+    ) -> BoxFuture<'a, FapiaoFileUploadResponse> {
+        Box::pin(async move {
+            let meta = FapiaoFileMeta {
+                file_type: fapiao_file_type(file_name)?,
+                digest_algorithm: "SM3".to_string(),
+                digest: hex_digest("SM3", &content)?,
+            };
+            let meta = serde_json::to_string(&meta)?;
+            self.upload_multipart::<FapiaoFileUploadResponse>(
+                "/v3/new-tax-control-fapiao/fapiao-applications/upload-fapiao-file",
+                meta,
+                file_name,
+                content,
+            )
+            .await
+        })
+    }
+    fn insert_fapiao_cards(
+        &self,
+        fapiao_apply_id: &str,
+        data: ReqInsertFapiaoCards,
+    ) -> BoxFuture<'_, ()> {
+        let url = format!(
+            "/v3/new-tax-control-fapiao/fapiao-applications/{}/insert-cards",
+            fapiao_apply_id
+        );
+        let mut new_data = data;
+        Box::pin(async move {
+            let (serial_no, cert_file) = self.get_platform_cert().await?;
+            encrypt_buyer(&mut new_data.buyer_information, &cert_file)?;
+            let body = serde_json::to_string(&new_data)?;
+            let req_builder = self
+                .build_request_builder(&url, "POST", &body)?
+                .header("Wechatpay-Serial", serial_no);
+            self.send_request::<()>(req_builder).await
+        })
+    }
+}
+/// 使用平台证书加密购买方手机号和邮箱
+fn encrypt_buyer(buyer: &mut FapiaoBuyerInformation, cert_file: &str) -> WeaResult<()> {
+    if let Some(phone) = &buyer.phone {
+        buyer.phone = Some(rsa_encrypt_oaep(phone, cert_file)?);
+    }
+    if let Some(email) = &buyer.email {
+        buyer.email = Some(rsa_encrypt_oaep(email, cert_file)?);
+    }
+    Ok(())
+}
+/// 使用商户私钥解密可选的敏感字段
+fn decrypt_optional(value: Option<String>, key_file: &str) -> WeaResult<Option<String>> {
+    match value {
+        Some(v) if !v.is_empty() => {
+            let plain = rsa_decrypt_oaep(&v, key_file)?;
+            let plain = String::from_utf8(plain).map_err(|_e| e("decrypted value is not utf8"))?;
+            Ok(Some(plain))
+        }
+        other => Ok(other),
+    }
+}
+/// 根据文件后缀获取发票文件类型
+fn fapiao_file_type(file_name: &str) -> WeaResult<FapiaoFileType> {
+    let ext = file_name
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+        .unwrap_or_default();
+    match ext.as_str() {
+        "pdf" => Ok(FapiaoFileType::PDF),
+        "ofd" => Ok(FapiaoFileType::OFD),
+        _ => Err(e("fapiao file must be pdf or ofd")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_fapiao_file_type() {
+        assert_eq!(fapiao_file_type("a.PDF").unwrap(), FapiaoFileType::PDF);
+        assert_eq!(fapiao_file_type("b.ofd").unwrap(), FapiaoFileType::OFD);
+        assert!(fapiao_file_type("c.png").is_err());
+    }
+    #[test]
+    fn test_fapiao_from_order() {
+        let content = r#"{"appid":"wx123456","mchid":"1900000109","out_trade_no":"T20240701001","transaction_id":"4200000001202407010000000001","trade_type":"JSAPI","trade_state":"SUCCESS","trade_state_desc":"支付成功","bank_type":"CMC","attach":"","success_time":"2024-07-01T10:00:00+08:00","payer":{"openid":"oUpF8uMuAJO_M2pxb1Q9zNjWeS6o"},"amount":{"total":11300,"payer_total":11300,"currency":"CNY","payer_currency":"CNY"}}"#;
+        let order: ResourceOrderBody = serde_json::from_str(content).unwrap();
+        let buyer = FapiaoBuyerInformation {
+            name: "张三".to_string(),
+            phone: Some("13800000000".to_string()),
+            ..Default::default()
+        };
+        let item = FapiaoItem {
+            tax_code: "3010101020203000000".to_string(),
+            goods_name: Some("技术服务费".to_string()),
+            tax_rate: 1300,
+            ..Default::default()
+        };
+        let data = ReqFapiaoApplication::from_order(&order, buyer, item);
+        assert_eq!(data.fapiao_apply_id, "4200000001202407010000000001");
+        assert_eq!(data.fapiao_information[0].fapiao_id, "T20240701001");
+        assert_eq!(data.fapiao_information[0].total_amount, 11300);
+        let body: serde_json::Value = serde_json::to_value(&data).unwrap();
+        assert_eq!(body["scene"], "WITH_WECHATPAY");
+        assert_eq!(body["buyer_information"]["type"], "INDIVIDUAL");
+        assert!(body["buyer_information"].get("amount").is_none());
+        let item = &body["fapiao_information"][0]["items"][0];
+        assert_eq!(item["quantity"], FAPIAO_QUANTITY_UNIT);
+        assert_eq!(item["total_amount"], 11300);
+        assert_eq!(item["tax_prefer_mark"], "NO_FAVORABLE");
+    }
+    #[test]
+    fn test_fapiao_notify_event() {
+        let content = r#"{"mchid":"1900000109","fapiao_apply_id":"4200000001202407010000000001","apply_time":"2024-07-01T10:00:00+08:00"}"#;
+        match NotifyEvent::from_content("FAPIAO.USER_APPLIED", content).unwrap() {
+            NotifyEvent::FapiaoUserApplied(body) => {
+                assert_eq!(body.fapiao_apply_id, "4200000001202407010000000001");
+            }
+            _ => panic!("fapiao user applied event expected"),
+        }
+        let content = r#"{"mchid":"1900000109","fapiao_apply_id":"4200000001202407010000000001","fapiao_information":[{"fapiao_id":"T20240701001","status":"ISSUED"}]}"#;
+        match NotifyEvent::from_content("FAPIAO.ISSUED", content).unwrap() {
+            NotifyEvent::Fapiao(body) => {
+                let info = body.fapiao_information.unwrap();
+                assert_eq!(info[0].status, Some(FapiaoStatus::Issued));
+            }
+            _ => panic!("fapiao event expected"),
+        }
+    }
+}
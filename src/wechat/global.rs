@@ -0,0 +1,250 @@
+use crate::utils::*;
+use crate::wechat::prelude::*;
+use crate::*;
+/// 境外(香港)微信支付,需在配置中开启 is_global
+/// 直连模式使用 appid/mchid,服务商配置(sp_appid、sp_mchid)时为机构模式
+/// 支付、退款通知与境内相同,使用 BaseTrait::notify_event 处理,金额中会带有汇率信息
+pub trait GlobalTrait {
+    /// 境外支付下单,支持 JSAPI、NATIVE、App、MWEB
+    fn create_global_order(
+        &self,
+        trade_type: TradeType,
+        data: ReqGlobalOrderBody,
+    ) -> BoxFuture<'_, CreateOrderResult>;
+    /// 通过商户订单号查询订单
+    fn query_global_order(&self, out_trade_no: &str) -> BoxFuture<'_, GlobalOrderQueryResponse>;
+    /// 通过微信支付订单号查询订单
+    fn query_global_order_by_id(
+        &self,
+        transaction_id: &str,
+    ) -> BoxFuture<'_, GlobalOrderQueryResponse>;
+    /// 关闭订单
+    fn close_global_order(&self, out_trade_no: &str) -> BoxFuture<'_, ()>;
+    /// 申请退款
+    fn global_refund(&self, data: ReqGlobalRefund) -> BoxFuture<'_, GlobalRefundResponse>;
+    /// 通过商户退款单号查询退款
+    fn query_global_refund(&self, out_refund_no: &str) -> BoxFuture<'_, GlobalRefundResponse>;
+}
+impl GlobalTrait for Payment<WechatConfig> {
+    fn create_global_order(
+        &self,
+        trade_type: TradeType,
+        data: ReqGlobalOrderBody,
+    ) -> BoxFuture<'_, CreateOrderResult> {
+        Box::pin(async move {
+            let (url, trade_type_str) = match trade_type {
+                TradeType::JSAPI => ("/v3/global/transactions/jsapi", "JSAPI"),
+                TradeType::NATIVE => ("/v3/global/transactions/native", "NATIVE"),
+                TradeType::App => ("/v3/global/transactions/app", "APP"),
+                TradeType::MWEB => ("/v3/global/transactions/mweb", "MWEB"),
+                _ => return Err(e("trade type error")),
+            };
+            let order_body = if self.is_sp() {
+                ReqGlobalOrderBody {
+                    sp_appid: self.config.sp_appid.clone(),
+                    sp_mchid: self.config.sp_mchid.clone(),
+                    sub_appid: data.sub_appid.clone().or(Some(self.config.app_id.clone())),
+                    sub_mchid: Some(self.config.mchid.clone()),
+                    ..data
+                }
+            } else {
+                ReqGlobalOrderBody {
+                    appid: data.appid.clone().or(Some(self.config.app_id.clone())),
+                    mchid: Some(self.config.mchid.clone()),
+                    ..data
+                }
+            };
+            let order_body = ReqGlobalOrderBody {
+                trade_type: Some(trade_type_str.to_string()),
+                notify_url: order_body
+                    .notify_url
+                    .clone()
+                    .or(Some(self.config.notify_url.clone())),
+                ..order_body
+            };
+            let body = serde_json::to_string(&order_body)?;
+            let rs = self
+                .do_request::<CreateOrderResponse>(url, "POST", &body)
+                .await?;
+            sign_order_result(self, &trade_type, rs)
+        })
+    }
+    fn query_global_order(&self, out_trade_no: &str) -> BoxFuture<'_, GlobalOrderQueryResponse> {
+        let url = format!(
+            "/v3/global/transactions/out-trade-no/{}?{}",
+            out_trade_no,
+            mch_query(self)
+        );
+        Box::pin(async move {
+            self.do_request::<GlobalOrderQueryResponse>(&url, "GET", "")
+                .await
+        })
+    }
+    fn query_global_order_by_id(
+        &self,
+        transaction_id: &str,
+    ) -> BoxFuture<'_, GlobalOrderQueryResponse> {
+        let url = format!(
+            "/v3/global/transactions/id/{}?{}",
+            transaction_id,
+            mch_query(self)
+        );
+        Box::pin(async move {
+            self.do_request::<GlobalOrderQueryResponse>(&url, "GET", "")
+                .await
+        })
+    }
+    fn close_global_order(&self, out_trade_no: &str) -> BoxFuture<'_, ()> {
+        let url = format!(
+            "/v3/global/transactions/out-trade-no/{}/close",
+            out_trade_no
+        );
+        let body = if self.is_sp() {
+            serde_json::json!({
+                "sp_mchid": self.config.sp_mchid,
+                "sub_mchid": self.config.mchid,
+            })
+        } else {
+            serde_json::json!({ "mchid": self.config.mchid })
+        }
+        .to_string();
+        Box::pin(async move { self.do_request::<()>(&url, "POST", &body).await })
+    }
+    fn global_refund(&self, data: ReqGlobalRefund) -> BoxFuture<'_, GlobalRefundResponse> {
+        let new_data = if self.is_sp() {
+            ReqGlobalRefund {
+                sp_mchid: self.config.sp_mchid.clone(),
+                sub_mchid: Some(self.config.mchid.clone()),
+                ..data
+            }
+        } else {
+            ReqGlobalRefund {
+                mchid: Some(self.config.mchid.clone()),
+                ..data
+            }
+        };
+        let new_data = ReqGlobalRefund {
+            notify_url: new_data
+                .notify_url
+                .clone()
+                .or(Some(self.config.notify_url.clone())),
+            ..new_data
+        };
+        Box::pin(async move {
+            let body = serde_json::to_string(&new_data)?;
+            self.do_request::<GlobalRefundResponse>("/v3/global/refunds", "POST", &body)
+                .await
+        })
+    }
+    fn query_global_refund(&self, out_refund_no: &str) -> BoxFuture<'_, GlobalRefundResponse> {
+        let url = format!(
+            "/v3/global/refunds/out-refund-no/{}?{}",
+            out_refund_no,
+            mch_query(self)
+        );
+        Box::pin(async move {
+            self.do_request::<GlobalRefundResponse>(&url, "GET", "")
+                .await
+        })
+    }
+}
+/// 查询接口的商户号参数,机构模式下为 sp_mchid 和 sub_mchid
+fn mch_query(payment: &Payment<WechatConfig>) -> String {
+    if payment.is_sp() {
+        format!(
+            "sp_mchid={}&sub_mchid={}",
+            payment.config.sp_mchid.clone().unwrap_or_default(),
+            payment.config.mchid
+        )
+    } else {
+        format!("mchid={}", payment.config.mchid)
+    }
+}
+/// JSAPI、App 下单后生成调起支付的签名数据,其他类型直接返回下单结果
+fn sign_order_result(
+    payment: &Payment<WechatConfig>,
+    trade_type: &TradeType,
+    rs: CreateOrderResponse,
+) -> WeaResult<CreateOrderResult> {
+    let prepay_id = match (trade_type, rs.prepay_id.clone()) {
+        (TradeType::JSAPI | TradeType::App, Some(prepay_id)) => prepay_id,
+        _ => return Ok(CreateOrderResult::Default(rs)),
+    };
+    let app_id = payment.config.app_id.clone();
+    let time_stamp = get_timestamp()?.to_string();
+    let nonce_str = generate_random_string(32);
+    if let TradeType::JSAPI = trade_type {
+        let package = format!("prepay_id={}", prepay_id);
+        let pay_sign = payment.sign(vec![&app_id, &time_stamp, &nonce_str, &package])?;
+        return Ok(CreateOrderResult::JSAPI(JsapiSignPackage {
+            app_id,
+            time_stamp,
+            nonce_str,
+            package,
+            sign_type: payment
+                .config
+                .sign_type
+                .clone()
+                .unwrap_or_default()
+                .as_str()
+                .to_string(),
+            pay_sign,
+        }));
+    }
+    let sign = payment.sign(vec![&app_id, &time_stamp, &nonce_str, &prepay_id])?;
+    Ok(CreateOrderResult::APP(AppSignPackage {
+        app_id,
+        partner_id: payment.config.mchid.clone(),
+        prepay_id,
+        package_value: "Sign=WXPay".to_string(),
+        nonce_str,
+        time_stamp,
+        sign,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_global_base_url() {
+        let config = WechatConfig {
+            mchid: "1900000109".to_string(),
+            is_global: Some(true),
+            ..Default::default()
+        };
+        let payment = Payment::new(config);
+        assert_eq!(payment.base_url(), "https://apihk.mch.weixin.qq.com");
+        assert_eq!(mch_query(&payment), "mchid=1900000109");
+        let payment = Payment::new(WechatConfig::default());
+        assert_eq!(payment.base_url(), "https://api.mch.weixin.qq.com");
+    }
+    #[test]
+    fn test_global_order_query_response() {
+        let content = r#"{"id":"1217752501201407033233368018","appid":"wxdace645e0bc2cXXX","mchid":"1900000109","out_trade_no":"YX201710140020Z","trade_type":"MWEB","trade_state":"SUCCESS","trade_state_desc":"支付成功","bank_type":"CCB_DEBIT","attach":"","success_time":"2018-06-08T10:34:56+08:00","payer":{"openid":"oUpF8uN95-Ptaags6E_roPHg7AG0"},"amount":{"total":528800,"currency":"HKD","payer_total":470652,"payer_currency":"CNY","exchange_rate":{"type":"SETTLEMENT_RATE","rate":89004300}}}"#;
+        let resp: GlobalOrderQueryResponse = serde_json::from_str(content).unwrap();
+        let amount = resp.amount.unwrap();
+        assert_eq!(amount.currency, "HKD");
+        assert_eq!(amount.payer_total, Some(470652));
+        let rate = amount.exchange_rate.unwrap();
+        assert!((rate.value() - 0.890043).abs() < 1e-9);
+    }
+    #[test]
+    fn test_global_order_body() {
+        let data = ReqGlobalOrderBody {
+            description: "Image形象店-深圳腾大-QQ公仔".to_string(),
+            out_trade_no: "YX201710140020Z".to_string(),
+            trade_type: Some("NATIVE".to_string()),
+            merchant_category_code: "4111".to_string(),
+            amount: ReqGlobalAmount {
+                total: 1,
+                currency: "HKD".to_string(),
+            },
+            ..Default::default()
+        };
+        let body: serde_json::Value = serde_json::to_value(&data).unwrap();
+        assert_eq!(body["amount"]["currency"], "HKD");
+        assert_eq!(body["trade_type"], "NATIVE");
+        assert!(body.get("sp_mchid").is_none());
+    }
+}
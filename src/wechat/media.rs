@@ -0,0 +1,188 @@
+use crate::utils::*;
+use crate::wechat::prelude::*;
+use crate::*;
+use serde::de::DeserializeOwned;
+/// 图片上传大小上限 2M
+pub const IMAGE_MAX_SIZE: usize = 2 * 1024 * 1024;
+/// 视频上传大小上限 5M
+pub const VIDEO_MAX_SIZE: usize = 5 * 1024 * 1024;
+/// 图片/视频上传
+/// 上传使用 multipart/form-data,只对 meta 的 json 串签名而不是整个请求体
+pub trait MediaTrait {
+    /// 上传图片,支持 JPG、BMP、PNG,返回的 media_id 用于进件等接口
+    fn upload_image(&self, file_name: &str, content: Vec<u8>)
+        -> BoxFuture<'_, MediaUploadResponse>;
+    /// 上传视频,支持 avi、wmv、mpeg、mp4、mov、mkv、flv、f4v、m4v、rmvb
+    fn upload_video(&self, file_name: &str, content: Vec<u8>)
+        -> BoxFuture<'_, MediaUploadResponse>;
+    /// 通用的签名 multipart 上传,url 为上传接口地址,如营销图片上传 /v3/marketing/favor/media/image-upload
+    fn upload_media<'a>(
+        &'a self,
+        url: &'a str,
+        file_name: &'a str,
+        content: Vec<u8>,
+    ) -> BoxFuture<'a, MediaUploadResponse>;
+    /// 签名 multipart 上传,meta 为参与签名的 json 串,适用于 meta 结构与图片上传不同的接口
+    fn upload_multipart<'a, U: DeserializeOwned + 'a>(
+        &'a self,
+        url: &'a str,
+        meta: String,
+        file_name: &'a str,
+        content: Vec<u8>,
+    ) -> BoxFuture<'a, U>;
+}
+impl MediaTrait for Payment<WechatConfig> {
+    fn upload_image(
+        &self,
+        file_name: &str,
+        content: Vec<u8>,
+    ) -> BoxFuture<'_, MediaUploadResponse> {
+        let file_name = file_name.to_string();
+        Box::pin(async move {
+            if content.len() > IMAGE_MAX_SIZE {
+                return Err(e("image size exceeds 2M"));
+            }
+            self.upload_media("/v3/merchant/media/upload", &file_name, content)
+                .await
+        })
+    }
+    fn upload_video(
+        &self,
+        file_name: &str,
+        content: Vec<u8>,
+    ) -> BoxFuture<'_, MediaUploadResponse> {
+        let file_name = file_name.to_string();
+        Box::pin(async move {
+            if content.len() > VIDEO_MAX_SIZE {
+                return Err(e("video size exceeds 5M"));
+            }
+            self.upload_media("/v3/merchant/media/video_upload", &file_name, content)
+                .await
+        })
+    }
+    fn upload_media<'a>(
+        &'a self,
+        url: &'a str,
+        file_name: &'a str,
+        content: Vec<u8>,
+    ) -> BoxFuture<'a, MediaUploadResponse> {
+        Box::pin(async move {
+            let meta = MediaMeta {
+                filename: file_name.to_string(),
+                sha256: hex_digest("SHA256", &content)?,
+            };
+            let meta = serde_json::to_string(&meta)?;
+            self.upload_multipart::<MediaUploadResponse>(url, meta, file_name, content)
+                .await
+        })
+    }
+    fn upload_multipart<'a, U: DeserializeOwned + 'a>(
+        &'a self,
+        url: &'a str,
+        meta: String,
+        file_name: &'a str,
+        content: Vec<u8>,
+    ) -> BoxFuture<'a, U> {
+        Box::pin(async move {
+            let content_type = media_content_type(file_name)?;
+            let authorization = self.build_authorization("POST", url, &meta)?;
+            let boundary = generate_random_string(32);
+            let body = multipart_body(&boundary, &meta, file_name, content_type, &content);
+            let full_url = format!("{}{}", self.base_url(), url);
+            let req_builder = reqwest::Client::new()
+                .post(full_url)
+                .header(
+                    "Content-Type",
+                    format!("multipart/form-data; boundary={}", boundary),
+                )
+                .header("Accept", "application/json")
+                .header("User-Agent", SDK_UA)
+                .header("Authorization", authorization)
+                .body(body);
+            self.send_request::<U>(req_builder).await
+        })
+    }
+}
+/// 根据文件后缀获取 Content-Type
+fn media_content_type(file_name: &str) -> WeaResult<&'static str> {
+    let ext = file_name
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+        .ok_or(e("file name must have an extension"))?;
+    let content_type = match ext.as_str() {
+        "jpg" | "jpeg" => "image/jpeg",
+        "png" => "image/png",
+        "bmp" => "image/bmp",
+        "avi" => "video/x-msvideo",
+        "wmv" => "video/x-ms-wmv",
+        "mpeg" => "video/mpeg",
+        "mp4" => "video/mp4",
+        "mov" => "video/quicktime",
+        "mkv" => "video/x-matroska",
+        "flv" => "video/x-flv",
+        "f4v" => "video/x-f4v",
+        "m4v" => "video/x-m4v",
+        "rmvb" => "application/vnd.rn-realmedia-vbr",
+        "pdf" => "application/pdf",
+        "ofd" => "application/ofd",
+        _ => return Err(e("unsupported media type")),
+    };
+    Ok(content_type)
+}
+/// 构建 multipart/form-data 请求体,meta 在前,file 在后
+fn multipart_body(
+    boundary: &str,
+    meta: &str,
+    file_name: &str,
+    content_type: &str,
+    content: &[u8],
+) -> Vec<u8> {
+    let mut body = Vec::with_capacity(content.len() + meta.len() + 256);
+    body.extend_from_slice(
+        format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"meta\";\r\nContent-Type: application/json\r\n\r\n{}\r\n",
+            boundary, meta
+        )
+        .as_bytes(),
+    );
+    body.extend_from_slice(
+        format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{}\";\r\nContent-Type: {}\r\n\r\n",
+            boundary, file_name, content_type
+        )
+        .as_bytes(),
+    );
+    body.extend_from_slice(content);
+    body.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_media_content_type() {
+        assert_eq!(media_content_type("a.JPG").unwrap(), "image/jpeg");
+        assert_eq!(media_content_type("b.mp4").unwrap(), "video/mp4");
+        assert!(media_content_type("c.gif").is_err());
+        assert!(media_content_type("noext").is_err());
+    }
+    #[test]
+    fn test_multipart_body() {
+        let content = b"\x89PNG\r\n".to_vec();
+        let meta = serde_json::to_string(&MediaMeta {
+            filename: "a.png".to_string(),
+            sha256: hex_digest("SHA256", &content).unwrap(),
+        })
+        .unwrap();
+        let body = multipart_body("xyz", &meta, "a.png", "image/png", &content);
+        let expected = [
+            format!("--xyz\r\nContent-Disposition: form-data; name=\"meta\";\r\nContent-Type: application/json\r\n\r\n{}\r\n", meta).as_bytes(),
+            b"--xyz\r\nContent-Disposition: form-data; name=\"file\"; filename=\"a.png\";\r\nContent-Type: image/png\r\n\r\n",
+            &content,
+            b"\r\n--xyz--\r\n",
+        ]
+        .concat();
+        assert_eq!(body, expected);
+    }
+}
@@ -1,6 +1,18 @@
+pub mod applyment;
+pub mod balance;
 pub mod bill;
 pub mod common;
+pub mod complaint;
+pub mod coupon;
 pub mod dict;
+pub mod ecommerce;
+pub mod fapiao;
+pub mod global;
+pub mod media;
+pub mod payscore;
 //pub mod myboxed;
 pub mod prelude;
+pub mod profit_sharing;
 pub mod refund;
+pub mod transfer;
+pub mod v2;
@@ -0,0 +1,372 @@
+use crate::utils::*;
+use crate::wechat::prelude::*;
+use crate::*;
+/// 微信支付分
+/// 用户确认订单、支付成功及授权变更通知通过 BaseTrait::notify_event 处理
+/// 对应 NotifyEvent::PayScoreUserConfirm、NotifyEvent::PayScoreUserPaid、NotifyEvent::PayScorePermission
+pub trait PayScoreTrait {
+    /// 创建支付分订单,返回的 package 用于生成确认订单的签名包
+    fn create_service_order(&self, data: ReqServiceOrder) -> BoxFuture<'_, ServiceOrder>;
+    /// 通过商户服务订单号查询支付分订单
+    fn query_service_order(
+        &self,
+        service_id: &str,
+        out_order_no: &str,
+    ) -> BoxFuture<'_, ServiceOrder>;
+    /// 通过回跳查询ID查询支付分订单
+    fn query_service_order_by_query_id(
+        &self,
+        service_id: &str,
+        query_id: &str,
+    ) -> BoxFuture<'_, ServiceOrder>;
+    /// 取消支付分订单
+    fn cancel_service_order(
+        &self,
+        out_order_no: &str,
+        data: ReqCancelServiceOrder,
+    ) -> BoxFuture<'_, ServiceOrderBriefResponse>;
+    /// 修改订单金额
+    fn modify_service_order(
+        &self,
+        out_order_no: &str,
+        data: ReqModifyServiceOrder,
+    ) -> BoxFuture<'_, ServiceOrder>;
+    /// 完结支付分订单
+    fn complete_service_order(
+        &self,
+        out_order_no: &str,
+        data: ReqCompleteServiceOrder,
+    ) -> BoxFuture<'_, ServiceOrder>;
+    /// 商户发起催收扣款
+    fn pay_service_order(
+        &self,
+        out_order_no: &str,
+        service_id: &str,
+    ) -> BoxFuture<'_, ServiceOrderBriefResponse>;
+    /// 同步服务订单信息
+    fn sync_service_order(
+        &self,
+        out_order_no: &str,
+        data: ReqSyncServiceOrder,
+    ) -> BoxFuture<'_, ServiceOrder>;
+    /// 商户预授权
+    fn apply_payscore_permission(
+        &self,
+        data: ReqPayScorePermission,
+    ) -> BoxFuture<'_, PayScorePermissionResponse>;
+    /// 通过授权协议号查询用户授权记录
+    fn query_payscore_authorization(
+        &self,
+        service_id: &str,
+        authorization_code: &str,
+    ) -> BoxFuture<'_, PayScoreAuthorization>;
+    /// 通过openid查询用户授权记录
+    fn query_payscore_authorization_by_openid(
+        &self,
+        service_id: &str,
+        openid: &str,
+    ) -> BoxFuture<'_, PayScoreAuthorization>;
+    /// 通过授权协议号解除用户授权
+    fn terminate_payscore_authorization(
+        &self,
+        service_id: &str,
+        authorization_code: &str,
+        reason: &str,
+    ) -> BoxFuture<'_, ()>;
+    /// 通过openid解除用户授权
+    fn terminate_payscore_authorization_by_openid(
+        &self,
+        service_id: &str,
+        openid: &str,
+        reason: &str,
+    ) -> BoxFuture<'_, ()>;
+    /// 生成小程序/APP调起确认订单页面的签名包,使用APIv2密钥 api_v2_key(为空时使用 mch_key)进行 HMAC-SHA256 签名
+    fn payscore_sign_package(&self, package: &str) -> WeaResult<PayScoreSignPackage>;
+}
+impl PayScoreTrait for Payment<WechatConfig> {
+    fn create_service_order(&self, data: ReqServiceOrder) -> BoxFuture<'_, ServiceOrder> {
+        let new_data = ReqServiceOrder {
+            appid: data.appid.clone().or(Some(self.config.app_id.clone())),
+            notify_url: data
+                .notify_url
+                .clone()
+                .or(Some(self.config.notify_url.clone())),
+            ..data
+        };
+        Box::pin(async move {
+            let body = serde_json::to_string(&new_data)?;
+            self.do_request::<ServiceOrder>("/v3/payscore/serviceorder", "POST", &body)
+                .await
+        })
+    }
+    fn query_service_order(
+        &self,
+        service_id: &str,
+        out_order_no: &str,
+    ) -> BoxFuture<'_, ServiceOrder> {
+        let url = format!(
+            "/v3/payscore/serviceorder?service_id={}&out_order_no={}&appid={}",
+            service_id, out_order_no, self.config.app_id
+        );
+        Box::pin(async move { self.do_request::<ServiceOrder>(&url, "GET", "").await })
+    }
+    fn query_service_order_by_query_id(
+        &self,
+        service_id: &str,
+        query_id: &str,
+    ) -> BoxFuture<'_, ServiceOrder> {
+        let url = format!(
+            "/v3/payscore/serviceorder?service_id={}&query_id={}&appid={}",
+            service_id, query_id, self.config.app_id
+        );
+        Box::pin(async move { self.do_request::<ServiceOrder>(&url, "GET", "").await })
+    }
+    fn cancel_service_order(
+        &self,
+        out_order_no: &str,
+        data: ReqCancelServiceOrder,
+    ) -> BoxFuture<'_, ServiceOrderBriefResponse> {
+        let url = format!("/v3/payscore/serviceorder/{}/cancel", out_order_no);
+        let new_data = ReqCancelServiceOrder {
+            appid: data.appid.clone().or(Some(self.config.app_id.clone())),
+            ..data
+        };
+        Box::pin(async move {
+            let body = serde_json::to_string(&new_data)?;
+            self.do_request::<ServiceOrderBriefResponse>(&url, "POST", &body)
+                .await
+        })
+    }
+    fn modify_service_order(
+        &self,
+        out_order_no: &str,
+        data: ReqModifyServiceOrder,
+    ) -> BoxFuture<'_, ServiceOrder> {
+        let url = format!("/v3/payscore/serviceorder/{}/modify", out_order_no);
+        let new_data = ReqModifyServiceOrder {
+            appid: data.appid.clone().or(Some(self.config.app_id.clone())),
+            ..data
+        };
+        Box::pin(async move {
+            let body = serde_json::to_string(&new_data)?;
+            self.do_request::<ServiceOrder>(&url, "POST", &body).await
+        })
+    }
+    fn complete_service_order(
+        &self,
+        out_order_no: &str,
+        data: ReqCompleteServiceOrder,
+    ) -> BoxFuture<'_, ServiceOrder> {
+        let url = format!("/v3/payscore/serviceorder/{}/complete", out_order_no);
+        let new_data = ReqCompleteServiceOrder {
+            appid: data.appid.clone().or(Some(self.config.app_id.clone())),
+            ..data
+        };
+        Box::pin(async move {
+            let body = serde_json::to_string(&new_data)?;
+            self.do_request::<ServiceOrder>(&url, "POST", &body).await
+        })
+    }
+    fn pay_service_order(
+        &self,
+        out_order_no: &str,
+        service_id: &str,
+    ) -> BoxFuture<'_, ServiceOrderBriefResponse> {
+        let url = format!("/v3/payscore/serviceorder/{}/pay", out_order_no);
+        let body = serde_json::json!({
+            "appid": self.config.app_id,
+            "service_id": service_id,
+        })
+        .to_string();
+        Box::pin(async move {
+            self.do_request::<ServiceOrderBriefResponse>(&url, "POST", &body)
+                .await
+        })
+    }
+    fn sync_service_order(
+        &self,
+        out_order_no: &str,
+        data: ReqSyncServiceOrder,
+    ) -> BoxFuture<'_, ServiceOrder> {
+        let url = format!("/v3/payscore/serviceorder/{}/sync", out_order_no);
+        let new_data = ReqSyncServiceOrder {
+            appid: data.appid.clone().or(Some(self.config.app_id.clone())),
+            ..data
+        };
+        Box::pin(async move {
+            let body = serde_json::to_string(&new_data)?;
+            self.do_request::<ServiceOrder>(&url, "POST", &body).await
+        })
+    }
+    fn apply_payscore_permission(
+        &self,
+        data: ReqPayScorePermission,
+    ) -> BoxFuture<'_, PayScorePermissionResponse> {
+        let new_data = ReqPayScorePermission {
+            appid: data.appid.clone().or(Some(self.config.app_id.clone())),
+            ..data
+        };
+        Box::pin(async move {
+            let body = serde_json::to_string(&new_data)?;
+            self.do_request::<PayScorePermissionResponse>("/v3/payscore/permissions", "POST", &body)
+                .await
+        })
+    }
+    fn query_payscore_authorization(
+        &self,
+        service_id: &str,
+        authorization_code: &str,
+    ) -> BoxFuture<'_, PayScoreAuthorization> {
+        let url = format!(
+            "/v3/payscore/permissions/authorization-code/{}?service_id={}",
+            authorization_code, service_id
+        );
+        Box::pin(async move {
+            self.do_request::<PayScoreAuthorization>(&url, "GET", "")
+                .await
+        })
+    }
+    fn query_payscore_authorization_by_openid(
+        &self,
+        service_id: &str,
+        openid: &str,
+    ) -> BoxFuture<'_, PayScoreAuthorization> {
+        let url = format!(
+            "/v3/payscore/permissions/openid/{}?appid={}&service_id={}",
+            openid, self.config.app_id, service_id
+        );
+        Box::pin(async move {
+            self.do_request::<PayScoreAuthorization>(&url, "GET", "")
+                .await
+        })
+    }
+    fn terminate_payscore_authorization(
+        &self,
+        service_id: &str,
+        authorization_code: &str,
+        reason: &str,
+    ) -> BoxFuture<'_, ()> {
+        let url = format!(
+            "/v3/payscore/permissions/authorization-code/{}/terminate",
+            authorization_code
+        );
+        let body = serde_json::json!({
+            "service_id": service_id,
+            "reason": reason,
+        })
+        .to_string();
+        Box::pin(async move { self.do_request::<()>(&url, "POST", &body).await })
+    }
+    fn terminate_payscore_authorization_by_openid(
+        &self,
+        service_id: &str,
+        openid: &str,
+        reason: &str,
+    ) -> BoxFuture<'_, ()> {
+        let url = format!("/v3/payscore/permissions/openid/{}/terminate", openid);
+        let body = serde_json::json!({
+            "service_id": service_id,
+            "appid": self.config.app_id,
+            "reason": reason,
+        })
+        .to_string();
+        Box::pin(async move { self.do_request::<()>(&url, "POST", &body).await })
+    }
+    fn payscore_sign_package(&self, package: &str) -> WeaResult<PayScoreSignPackage> {
+        let timestamp = get_timestamp()?.to_string();
+        let nonce_str = generate_random_string(32);
+        payscore_sign(
+            self.config.v2_key(),
+            &self.config.mchid,
+            package,
+            &timestamp,
+            &nonce_str,
+        )
+    }
+}
+/// 按参数名ASCII码排序拼接后使用 HMAC-SHA256 签名
+fn payscore_sign(
+    mch_key: &str,
+    mch_id: &str,
+    package: &str,
+    timestamp: &str,
+    nonce_str: &str,
+) -> WeaResult<PayScoreSignPackage> {
+    let sign_type = "HMAC-SHA256";
+    let data = format!(
+        "mch_id={}&nonce_str={}&package={}&sign_type={}&timestamp={}&key={}",
+        mch_id, nonce_str, package, sign_type, timestamp, mch_key
+    );
+    Ok(PayScoreSignPackage {
+        mch_id: mch_id.to_string(),
+        package: package.to_string(),
+        timestamp: timestamp.to_string(),
+        nonce_str: nonce_str.to_string(),
+        sign_type: sign_type.to_string(),
+        sign: hmac_sha256(mch_key, &data)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_payscore_sign() {
+        let sign_package = payscore_sign(
+            "192006250b4c09247ec02edce69f6a2d",
+            "1230000109",
+            "XXXXXXXX",
+            "1530097563",
+            "zyx53Nkey8o4bHpxTQvd8m7e92nG5mG2",
+        )
+        .unwrap();
+        let expected = hmac_sha256(
+            "192006250b4c09247ec02edce69f6a2d",
+            "mch_id=1230000109&nonce_str=zyx53Nkey8o4bHpxTQvd8m7e92nG5mG2&package=XXXXXXXX&sign_type=HMAC-SHA256&timestamp=1530097563&key=192006250b4c09247ec02edce69f6a2d",
+        )
+        .unwrap();
+        assert_eq!(sign_package.sign, expected);
+        assert_eq!(
+            sign_package.to_query_string(),
+            format!("mch_id=1230000109&package=XXXXXXXX&timestamp=1530097563&nonce_str=zyx53Nkey8o4bHpxTQvd8m7e92nG5mG2&sign_type=HMAC-SHA256&sign={}", expected)
+        );
+    }
+    #[test]
+    fn test_payscore_sign_package_with_v2_key() {
+        let payment = Payment::new(WechatConfig {
+            mchid: "1230000109".to_string(),
+            mch_key: "0123456789abcdef0123456789abcdef".to_string(),
+            api_v2_key: Some("192006250b4c09247ec02edce69f6a2d".to_string()),
+            ..Default::default()
+        });
+        let sign_package = payment.payscore_sign_package("XXXXXXXX").unwrap();
+        let expected = payscore_sign(
+            "192006250b4c09247ec02edce69f6a2d",
+            "1230000109",
+            "XXXXXXXX",
+            &sign_package.timestamp,
+            &sign_package.nonce_str,
+        )
+        .unwrap();
+        assert_eq!(sign_package.sign, expected.sign);
+    }
+    #[test]
+    fn test_payscore_notify_event() {
+        let content = r#"{"appid":"wxd678efh567hg6787","mchid":"1230000109","out_order_no":"1234323JKHDFE1243252","service_id":"500001","state":"DOING","state_description":"USER_CONFIRM","order_id":"15646546545165651651","openid":"oUpF8uMuAJO_M2pxb1Q9zNjWeS6o"}"#;
+        match NotifyEvent::from_content("PAYSCORE.USER_CONFIRM", content).unwrap() {
+            NotifyEvent::PayScoreUserConfirm(order) => {
+                assert_eq!(order.state, ServiceOrderState::DOING);
+                assert_eq!(
+                    order.state_description,
+                    Some(ServiceOrderStateDescription::UserConfirm)
+                );
+            }
+            _ => panic!("pay score user confirm event expected"),
+        }
+        let content = r#"{"appid":"wxd678efh567hg6787","mchid":"1230000109","service_id":"500001","openid":"oUpF8uMuAJO_M2pxb1Q9zNjWeS6o","user_service_status":"USER_CLOSE_SERVICE","openorclose_time":"20150901151005"}"#;
+        assert!(matches!(
+            NotifyEvent::from_content("PAYSCORE.USER_CLOSE_SERVICE", content).unwrap(),
+            NotifyEvent::PayScorePermission(_)
+        ));
+    }
+}
@@ -25,8 +25,15 @@ pub use super::dict::order::TradeState;
 pub use super::dict::order::TradeType;
 
 pub use super::common::BaseTrait;
+//通知相关
+pub use super::dict::notify::NotifyEnvelope;
+pub use super::dict::notify::NotifyEvent;
 //退款相关
+pub use super::dict::refund::AbnormalRefundType;
+pub use super::dict::refund::RefundChannel;
 pub use super::dict::refund::RefundResponse;
+pub use super::dict::refund::RefundStatus;
+pub use super::dict::refund::ReqAbnormalRefund;
 pub use super::dict::refund::ReqRefundAmountFrom;
 pub use super::dict::refund::ReqRefundAmountInfo;
 pub use super::dict::refund::ReqRefundGoodsDetail;
@@ -36,10 +43,291 @@ pub use super::dict::refund::ResourceRefundBody;
 pub use super::refund::RefundTrait;
 
 //帐单相关
+pub use super::bill::verify_bill_hash;
+pub use super::bill::BillStreamWriter;
 pub use super::bill::BillTrait;
 pub use super::dict::bill::BillResponse;
+pub use super::dict::bill::EncryptedBillItem;
+pub use super::dict::bill::FundBillAccountType;
+pub use super::dict::bill::FundFlowBill;
+pub use super::dict::bill::FundFlowBillRecord;
+pub use super::dict::bill::FundFlowBillSummary;
+pub use super::dict::bill::SubMerchantFundBillResponse;
+pub use super::dict::bill::TradeBill;
+pub use super::dict::bill::TradeBillRecord;
+pub use super::dict::bill::TradeBillSummary;
+pub use super::dict::bill::TradeBillType;
 
 //证书相关
 pub use super::dict::cert::CertData;
+pub use super::dict::cert::MerchantCert;
 pub use super::dict::cert::RespCert;
 pub use super::dict::cert::RespCertItem;
+pub use super::dict::cert::WechatSignType;
+
+//分账相关
+pub use super::dict::profit_sharing::AddReceiverResponse;
+pub use super::dict::profit_sharing::DeleteReceiverResponse;
+pub use super::dict::profit_sharing::ProfitSharingAmountResponse;
+pub use super::dict::profit_sharing::ProfitSharingOrderResponse;
+pub use super::dict::profit_sharing::ProfitSharingResult;
+pub use super::dict::profit_sharing::ProfitSharingReturnResponse;
+pub use super::dict::profit_sharing::ProfitSharingReturnResult;
+pub use super::dict::profit_sharing::ProfitSharingState;
+pub use super::dict::profit_sharing::ReceiverType;
+pub use super::dict::profit_sharing::RelationType;
+pub use super::dict::profit_sharing::ReqAddReceiver;
+pub use super::dict::profit_sharing::ReqDeleteReceiver;
+pub use super::dict::profit_sharing::ReqProfitSharingOrder;
+pub use super::dict::profit_sharing::ReqProfitSharingReceiver;
+pub use super::dict::profit_sharing::ReqProfitSharingReturn;
+pub use super::dict::profit_sharing::ReqProfitSharingUnfreeze;
+pub use super::dict::profit_sharing::ResourceProfitSharingBody;
+pub use super::dict::profit_sharing::ResourceProfitSharingReceiver;
+pub use super::dict::profit_sharing::RespProfitSharingReceiver;
+pub use super::profit_sharing::ProfitSharingTrait;
+
+//商家转账相关
+pub use super::dict::transfer::ReqTransferBatch;
+pub use super::dict::transfer::ReqTransferBatchQuery;
+pub use super::dict::transfer::ReqTransferBill;
+pub use super::dict::transfer::ReqTransferDetail;
+pub use super::dict::transfer::ReqTransferDetailReceipt;
+pub use super::dict::transfer::ReqTransferSceneReportInfo;
+pub use super::dict::transfer::ResourceTransferBatchBody;
+pub use super::dict::transfer::RespTransferBatch;
+pub use super::dict::transfer::RespTransferDetailItem;
+pub use super::dict::transfer::TransferBatchQueryResponse;
+pub use super::dict::transfer::TransferBatchResponse;
+pub use super::dict::transfer::TransferBatchStatus;
+pub use super::dict::transfer::TransferBillCancelResponse;
+pub use super::dict::transfer::TransferBillQueryResponse;
+pub use super::dict::transfer::TransferBillReceiptResponse;
+pub use super::dict::transfer::TransferBillResponse;
+pub use super::dict::transfer::TransferBillState;
+pub use super::dict::transfer::TransferDetailReceiptResponse;
+pub use super::dict::transfer::TransferDetailResponse;
+pub use super::dict::transfer::TransferDetailStatus;
+pub use super::dict::transfer::TransferReceiptResponse;
+pub use super::dict::transfer::TransferReceiptState;
+pub use super::transfer::TransferTrait;
+
+//电商收付通相关
+pub use super::dict::ecommerce::EcommerceBalanceResponse;
+pub use super::dict::ecommerce::EcommerceEndDayBalanceResponse;
+pub use super::dict::ecommerce::EcommerceFundsAccount;
+pub use super::dict::ecommerce::EcommerceRefundAccount;
+pub use super::dict::ecommerce::EcommerceRefundAmount;
+pub use super::dict::ecommerce::EcommerceRefundPromotion;
+pub use super::dict::ecommerce::EcommerceRefundQueryResponse;
+pub use super::dict::ecommerce::EcommerceRefundResponse;
+pub use super::dict::ecommerce::EcommerceWithdrawQueryResponse;
+pub use super::dict::ecommerce::EcommerceWithdrawResponse;
+pub use super::dict::ecommerce::ReqEcommerceRefund;
+pub use super::dict::ecommerce::ReqEcommerceRefundAmount;
+pub use super::dict::ecommerce::ReqEcommerceWithdraw;
+pub use super::dict::ecommerce::ReqSubsidyCancel;
+pub use super::dict::ecommerce::ReqSubsidyCreate;
+pub use super::dict::ecommerce::ReqSubsidyReturn;
+pub use super::dict::ecommerce::ReturnAdvanceResponse;
+pub use super::dict::ecommerce::ReturnAdvanceResult;
+pub use super::dict::ecommerce::SubsidyCancelResponse;
+pub use super::dict::ecommerce::SubsidyCreateResponse;
+pub use super::dict::ecommerce::SubsidyResult;
+pub use super::dict::ecommerce::SubsidyReturnResponse;
+pub use super::dict::ecommerce::WithdrawStatus;
+pub use super::ecommerce::EcommerceTrait;
+
+//特约商户进件相关
+pub use super::applyment::ApplymentTrait;
+pub use super::dict::applyment::AppInfo;
+pub use super::dict::applyment::ApplymentAdditionInfo;
+pub use super::dict::applyment::ApplymentAuditDetail;
+pub use super::dict::applyment::ApplymentBankAccountInfo;
+pub use super::dict::applyment::ApplymentBusinessInfo;
+pub use super::dict::applyment::ApplymentContactInfo;
+pub use super::dict::applyment::ApplymentIdentityInfo;
+pub use super::dict::applyment::ApplymentQueryResponse;
+pub use super::dict::applyment::ApplymentResponse;
+pub use super::dict::applyment::ApplymentSalesInfo;
+pub use super::dict::applyment::ApplymentSettlementInfo;
+pub use super::dict::applyment::ApplymentState;
+pub use super::dict::applyment::ApplymentSubjectInfo;
+pub use super::dict::applyment::BankBranchInfo;
+pub use super::dict::applyment::BankBranchListResponse;
+pub use super::dict::applyment::BankInfo;
+pub use super::dict::applyment::BankListResponse;
+pub use super::dict::applyment::BizStoreInfo;
+pub use super::dict::applyment::BusinessLicenseInfo;
+pub use super::dict::applyment::CertificateInfo;
+pub use super::dict::applyment::CityInfo;
+pub use super::dict::applyment::CityListResponse;
+pub use super::dict::applyment::FinanceInstitutionInfo;
+pub use super::dict::applyment::IdCardInfo;
+pub use super::dict::applyment::IdDocInfo;
+pub use super::dict::applyment::MiniProgramInfo;
+pub use super::dict::applyment::MpInfo;
+pub use super::dict::applyment::ProvinceInfo;
+pub use super::dict::applyment::ProvinceListResponse;
+pub use super::dict::applyment::ReqApplyment;
+pub use super::dict::applyment::SubjectType;
+pub use super::dict::applyment::UboInfo;
+pub use super::dict::applyment::WebInfo;
+pub use super::dict::applyment::WeworkInfo;
+
+//图片视频上传相关
+pub use super::dict::media::MediaMeta;
+pub use super::dict::media::MediaUploadResponse;
+pub use super::media::MediaTrait;
+
+//消费者投诉相关
+pub use super::complaint::ComplaintTrait;
+pub use super::dict::complaint::ComplaintDetail;
+pub use super::dict::complaint::ComplaintListResponse;
+pub use super::dict::complaint::ComplaintMedia;
+pub use super::dict::complaint::ComplaintNegotiationHistory;
+pub use super::dict::complaint::ComplaintNegotiationHistoryResponse;
+pub use super::dict::complaint::ComplaintNotificationResponse;
+pub use super::dict::complaint::ComplaintOrderInfo;
+pub use super::dict::complaint::ComplaintState;
+pub use super::dict::complaint::RefundProgressAction;
+pub use super::dict::complaint::ReqComplaintList;
+pub use super::dict::complaint::ReqComplaintResponse;
+pub use super::dict::complaint::ReqRefundProgress;
+pub use super::dict::complaint::ResourceComplaintBody;
+
+//支付分相关
+pub use super::dict::payscore::AuthorizationState;
+pub use super::dict::payscore::CollectionState;
+pub use super::dict::payscore::PayScoreAuthorization;
+pub use super::dict::payscore::PayScoreCollection;
+pub use super::dict::payscore::PayScoreCollectionDetail;
+pub use super::dict::payscore::PayScoreLocation;
+pub use super::dict::payscore::PayScorePermissionResponse;
+pub use super::dict::payscore::PayScorePostDiscount;
+pub use super::dict::payscore::PayScorePostPayment;
+pub use super::dict::payscore::PayScorePromotionDetail;
+pub use super::dict::payscore::PayScoreRiskFund;
+pub use super::dict::payscore::PayScoreSignPackage;
+pub use super::dict::payscore::PayScoreTimeRange;
+pub use super::dict::payscore::ReqCancelServiceOrder;
+pub use super::dict::payscore::ReqCompleteServiceOrder;
+pub use super::dict::payscore::ReqModifyServiceOrder;
+pub use super::dict::payscore::ReqPayScorePermission;
+pub use super::dict::payscore::ReqServiceOrder;
+pub use super::dict::payscore::ReqSyncServiceOrder;
+pub use super::dict::payscore::ResourcePayScorePermissionBody;
+pub use super::dict::payscore::RiskFundName;
+pub use super::dict::payscore::ServiceOrder;
+pub use super::dict::payscore::ServiceOrderBriefResponse;
+pub use super::dict::payscore::ServiceOrderState;
+pub use super::dict::payscore::ServiceOrderStateDescription;
+pub use super::dict::payscore::SyncServiceOrderDetail;
+pub use super::payscore::PayScoreTrait;
+
+//代金券商家券相关
+pub use super::coupon::CouponTrait;
+pub use super::dict::coupon::BusifavorAvailableTime;
+pub use super::dict::coupon::BusifavorDeactivateResponse;
+pub use super::dict::coupon::BusifavorDiscountCoupon;
+pub use super::dict::coupon::BusifavorDisplayPattern;
+pub use super::dict::coupon::BusifavorExchangeCoupon;
+pub use super::dict::coupon::BusifavorNormalCoupon;
+pub use super::dict::coupon::BusifavorReturnResponse;
+pub use super::dict::coupon::BusifavorSendRule;
+pub use super::dict::coupon::BusifavorStockType;
+pub use super::dict::coupon::BusifavorUseMethod;
+pub use super::dict::coupon::BusifavorUseResponse;
+pub use super::dict::coupon::BusifavorUseRule;
+pub use super::dict::coupon::CouponCallbackResponse;
+pub use super::dict::coupon::CouponCodeMode;
+pub use super::dict::coupon::CouponConsumeInformation;
+pub use super::dict::coupon::CouponDetail;
+pub use super::dict::coupon::CouponPatternInfo;
+pub use super::dict::coupon::CouponStatus;
+pub use super::dict::coupon::CouponStock;
+pub use super::dict::coupon::CouponStockListResponse;
+pub use super::dict::coupon::CouponUseRule;
+pub use super::dict::coupon::CreateStockResponse;
+pub use super::dict::coupon::FixedNormalCoupon;
+pub use super::dict::coupon::PromotionType;
+pub use super::dict::coupon::ReqBusifavorDeactivate;
+pub use super::dict::coupon::ReqBusifavorReturn;
+pub use super::dict::coupon::ReqBusifavorStock;
+pub use super::dict::coupon::ReqBusifavorUse;
+pub use super::dict::coupon::ReqCouponStock;
+pub use super::dict::coupon::ReqCouponStockList;
+pub use super::dict::coupon::ReqSendCoupon;
+pub use super::dict::coupon::ReqUserCouponList;
+pub use super::dict::coupon::SendCouponParam;
+pub use super::dict::coupon::SendCouponResponse;
+pub use super::dict::coupon::SendCouponSignPackage;
+pub use super::dict::coupon::StockActionResponse;
+pub use super::dict::coupon::StockStatus;
+pub use super::dict::coupon::StockUseRule;
+pub use super::dict::coupon::StockUseRuleInfo;
+pub use super::dict::coupon::UserCouponListResponse;
+
+//电子发票相关
+pub use super::dict::fapiao::BuyerType;
+pub use super::dict::fapiao::FapiaoApplicationResponse;
+pub use super::dict::fapiao::FapiaoBaseInformation;
+pub use super::dict::fapiao::FapiaoBuyerInformation;
+pub use super::dict::fapiao::FapiaoCardInfo;
+pub use super::dict::fapiao::FapiaoCardItem;
+pub use super::dict::fapiao::FapiaoCardTemplateInformation;
+pub use super::dict::fapiao::FapiaoCardTemplateResponse;
+pub use super::dict::fapiao::FapiaoDetail;
+pub use super::dict::fapiao::FapiaoDevelopmentConfig;
+pub use super::dict::fapiao::FapiaoExtraInformation;
+pub use super::dict::fapiao::FapiaoFileMeta;
+pub use super::dict::fapiao::FapiaoFileType;
+pub use super::dict::fapiao::FapiaoFileUploadResponse;
+pub use super::dict::fapiao::FapiaoInformation;
+pub use super::dict::fapiao::FapiaoItem;
+pub use super::dict::fapiao::FapiaoNotifyInformation;
+pub use super::dict::fapiao::FapiaoNumberInfo;
+pub use super::dict::fapiao::FapiaoScene;
+pub use super::dict::fapiao::FapiaoSellerInformation;
+pub use super::dict::fapiao::FapiaoStatus;
+pub use super::dict::fapiao::FapiaoTaxCode;
+pub use super::dict::fapiao::FapiaoTaxCodeListResponse;
+pub use super::dict::fapiao::FapiaoTitleUrlResponse;
+pub use super::dict::fapiao::InsertFapiaoCard;
+pub use super::dict::fapiao::ReqFapiaoApplication;
+pub use super::dict::fapiao::ReqFapiaoCardTemplate;
+pub use super::dict::fapiao::ReqFapiaoTitleUrl;
+pub use super::dict::fapiao::ReqInsertFapiaoCards;
+pub use super::dict::fapiao::ReqReverseFapiao;
+pub use super::dict::fapiao::ResourceFapiaoBody;
+pub use super::dict::fapiao::ReverseFapiaoInformation;
+pub use super::dict::fapiao::FAPIAO_QUANTITY_UNIT;
+pub use super::fapiao::FapiaoTrait;
+
+//账户余额及特约商户提现相关
+pub use super::balance::BalanceTrait;
+pub use super::dict::balance::FundBalanceResponse;
+
+//境外支付相关
+pub use super::dict::global::ExchangeRate;
+pub use super::dict::global::GlobalOrderAmount;
+pub use super::dict::global::GlobalOrderQueryResponse;
+pub use super::dict::global::GlobalRefundAmount;
+pub use super::dict::global::GlobalRefundResponse;
+pub use super::dict::global::ReqGlobalAmount;
+pub use super::dict::global::ReqGlobalOrderBody;
+pub use super::dict::global::ReqGlobalRefund;
+pub use super::dict::global::ReqGlobalRefundAmount;
+pub use super::dict::global::ReqGlobalSceneInfo;
+pub use super::global::GlobalTrait;
+//v2 红包相关
+pub use super::v2::common::V2Trait;
+pub use super::v2::dict::RedPackQueryResponse;
+pub use super::v2::dict::RedPackReceiveInfo;
+pub use super::v2::dict::RedPackReceiveList;
+pub use super::v2::dict::RedPackStatus;
+pub use super::v2::dict::ReqSendGroupRedPack;
+pub use super::v2::dict::ReqSendRedPack;
+pub use super::v2::dict::SendRedPackResponse;
+pub use super::v2::dict::V2SignType;
+pub use super::v2::redpack::RedPackTrait;
@@ -0,0 +1,260 @@
+use crate::utils::*;
+use crate::wechat::prelude::*;
+use crate::*;
+/// 分账
+/// 下单时需设置 settle_info.profit_sharing 为 true 冻结资金,之后通过分账接口完成资金分配或解冻
+/// 分账动账通知使用 BaseTrait::notify,U 为 ResourceProfitSharingBody
+pub trait ProfitSharingTrait {
+    /// 请求分账,接收方姓名会自动使用平台证书加密
+    fn profit_sharing(
+        &self,
+        data: ReqProfitSharingOrder,
+    ) -> BoxFuture<'_, ProfitSharingOrderResponse>;
+    /// 查询分账结果
+    fn query_profit_sharing(
+        &self,
+        transaction_id: &str,
+        out_order_no: &str,
+    ) -> BoxFuture<'_, ProfitSharingOrderResponse>;
+    /// 请求分账回退
+    fn profit_sharing_return(
+        &self,
+        data: ReqProfitSharingReturn,
+    ) -> BoxFuture<'_, ProfitSharingReturnResponse>;
+    /// 查询分账回退结果
+    fn query_profit_sharing_return(
+        &self,
+        out_order_no: &str,
+        out_return_no: &str,
+    ) -> BoxFuture<'_, ProfitSharingReturnResponse>;
+    /// 解冻剩余资金
+    fn profit_sharing_unfreeze(
+        &self,
+        data: ReqProfitSharingUnfreeze,
+    ) -> BoxFuture<'_, ProfitSharingOrderResponse>;
+    /// 查询剩余待分金额
+    fn query_profit_sharing_amount(
+        &self,
+        transaction_id: &str,
+    ) -> BoxFuture<'_, ProfitSharingAmountResponse>;
+    /// 添加分账接收方,接收方全称会自动使用平台证书加密
+    fn add_receiver(&self, data: ReqAddReceiver) -> BoxFuture<'_, AddReceiverResponse>;
+    /// 删除分账接收方
+    fn delete_receiver(&self, data: ReqDeleteReceiver) -> BoxFuture<'_, DeleteReceiverResponse>;
+}
+impl ProfitSharingTrait for Payment<WechatConfig> {
+    fn profit_sharing(
+        &self,
+        data: ReqProfitSharingOrder,
+    ) -> BoxFuture<'_, ProfitSharingOrderResponse> {
+        let mut new_data = if self.is_sp() {
+            ReqProfitSharingOrder {
+                sub_mchid: Some(self.config.mchid.clone()),
+                appid: data.appid.clone().or(self.config.sp_appid.clone()),
+                ..data
+            }
+        } else {
+            ReqProfitSharingOrder {
+                appid: data.appid.clone().or(Some(self.config.app_id.clone())),
+                ..data
+            }
+        };
+        Box::pin(async move {
+            let url = "/v3/profitsharing/orders";
+            let has_name = new_data.receivers.iter().any(|item| item.name.is_some());
+            if !has_name {
+                let body = serde_json::to_string(&new_data)?;
+                return self
+                    .do_request::<ProfitSharingOrderResponse>(url, "POST", &body)
+                    .await;
+            }
+            let (serial_no, cert_file) = self.get_platform_cert().await?;
+            for receiver in new_data.receivers.iter_mut() {
+                if let Some(name) = &receiver.name {
+                    receiver.name = Some(rsa_encrypt_oaep(name, &cert_file)?);
+                }
+            }
+            let body = serde_json::to_string(&new_data)?;
+            let req_builder = self
+                .build_request_builder(url, "POST", &body)?
+                .header("Wechatpay-Serial", serial_no);
+            self.send_request::<ProfitSharingOrderResponse>(req_builder)
+                .await
+        })
+    }
+    fn query_profit_sharing(
+        &self,
+        transaction_id: &str,
+        out_order_no: &str,
+    ) -> BoxFuture<'_, ProfitSharingOrderResponse> {
+        let url = format!(
+            "/v3/profitsharing/orders/{}?transaction_id={}",
+            out_order_no, transaction_id
+        );
+        let url = self.get_uri(&url, self.is_sp(), false);
+        Box::pin(async move {
+            self.do_request::<ProfitSharingOrderResponse>(&url, "GET", "")
+                .await
+        })
+    }
+    fn profit_sharing_return(
+        &self,
+        data: ReqProfitSharingReturn,
+    ) -> BoxFuture<'_, ProfitSharingReturnResponse> {
+        let new_data = if self.is_sp() {
+            ReqProfitSharingReturn {
+                sub_mchid: Some(self.config.mchid.clone()),
+                ..data
+            }
+        } else {
+            data
+        };
+        Box::pin(async move {
+            let body = serde_json::to_string(&new_data)?;
+            self.do_request::<ProfitSharingReturnResponse>(
+                "/v3/profitsharing/return-orders",
+                "POST",
+                &body,
+            )
+            .await
+        })
+    }
+    fn query_profit_sharing_return(
+        &self,
+        out_order_no: &str,
+        out_return_no: &str,
+    ) -> BoxFuture<'_, ProfitSharingReturnResponse> {
+        let url = format!(
+            "/v3/profitsharing/return-orders/{}?out_order_no={}",
+            out_return_no, out_order_no
+        );
+        let url = self.get_uri(&url, self.is_sp(), false);
+        Box::pin(async move {
+            self.do_request::<ProfitSharingReturnResponse>(&url, "GET", "")
+                .await
+        })
+    }
+    fn profit_sharing_unfreeze(
+        &self,
+        data: ReqProfitSharingUnfreeze,
+    ) -> BoxFuture<'_, ProfitSharingOrderResponse> {
+        let new_data = if self.is_sp() {
+            ReqProfitSharingUnfreeze {
+                sub_mchid: Some(self.config.mchid.clone()),
+                ..data
+            }
+        } else {
+            data
+        };
+        Box::pin(async move {
+            let body = serde_json::to_string(&new_data)?;
+            self.do_request::<ProfitSharingOrderResponse>(
+                "/v3/profitsharing/orders/unfreeze",
+                "POST",
+                &body,
+            )
+            .await
+        })
+    }
+    fn query_profit_sharing_amount(
+        &self,
+        transaction_id: &str,
+    ) -> BoxFuture<'_, ProfitSharingAmountResponse> {
+        let url = format!("/v3/profitsharing/transactions/{}/amounts", transaction_id);
+        Box::pin(async move {
+            self.do_request::<ProfitSharingAmountResponse>(&url, "GET", "")
+                .await
+        })
+    }
+    fn add_receiver(&self, data: ReqAddReceiver) -> BoxFuture<'_, AddReceiverResponse> {
+        let mut new_data = if self.is_sp() {
+            ReqAddReceiver {
+                sub_mchid: Some(self.config.mchid.clone()),
+                appid: data.appid.clone().or(self.config.sp_appid.clone()),
+                ..data
+            }
+        } else {
+            ReqAddReceiver {
+                appid: data.appid.clone().or(Some(self.config.app_id.clone())),
+                ..data
+            }
+        };
+        Box::pin(async move {
+            let url = "/v3/profitsharing/receivers/add";
+            let name = match new_data.name.clone() {
+                Some(name) => name,
+                None => {
+                    let body = serde_json::to_string(&new_data)?;
+                    return self
+                        .do_request::<AddReceiverResponse>(url, "POST", &body)
+                        .await;
+                }
+            };
+            let (serial_no, cert_file) = self.get_platform_cert().await?;
+            new_data.name = Some(rsa_encrypt_oaep(&name, &cert_file)?);
+            let body = serde_json::to_string(&new_data)?;
+            let req_builder = self
+                .build_request_builder(url, "POST", &body)?
+                .header("Wechatpay-Serial", serial_no);
+            self.send_request::<AddReceiverResponse>(req_builder).await
+        })
+    }
+    fn delete_receiver(&self, data: ReqDeleteReceiver) -> BoxFuture<'_, DeleteReceiverResponse> {
+        let new_data = if self.is_sp() {
+            ReqDeleteReceiver {
+                sub_mchid: Some(self.config.mchid.clone()),
+                appid: data.appid.clone().or(self.config.sp_appid.clone()),
+                ..data
+            }
+        } else {
+            ReqDeleteReceiver {
+                appid: data.appid.clone().or(Some(self.config.app_id.clone())),
+                ..data
+            }
+        };
+        Box::pin(async move {
+            let body = serde_json::to_string(&new_data)?;
+            self.do_request::<DeleteReceiverResponse>(
+                "/v3/profitsharing/receivers/delete",
+                "POST",
+                &body,
+            )
+            .await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_profit_sharing_body() {
+        let data = ReqProfitSharingOrder {
+            transaction_id: "4208450740201411110007820472".to_string(),
+            out_order_no: "P20150806125346".to_string(),
+            receivers: vec![ReqProfitSharingReceiver {
+                r#type: ReceiverType::PersonalOpenid,
+                account: "oUpF8uMuAJO_M2pxb1Q9zNjWeS6o".to_string(),
+                amount: 10,
+                description: "分给商户A".to_string(),
+                ..Default::default()
+            }],
+            unfreeze_unsplit: true,
+            ..Default::default()
+        };
+        let body = serde_json::to_string(&data).unwrap();
+        assert_eq!(
+            body,
+            r#"{"transaction_id":"4208450740201411110007820472","out_order_no":"P20150806125346","receivers":[{"type":"PERSONAL_OPENID","account":"oUpF8uMuAJO_M2pxb1Q9zNjWeS6o","amount":10,"description":"分给商户A"}],"unfreeze_unsplit":true}"#
+        );
+    }
+    #[test]
+    fn test_profit_sharing_response() {
+        let body = r#"{"sub_mchid":"1900000109","transaction_id":"4208450740201411110007820472","out_order_no":"P20150806125346","order_id":"3008450740201411110007820472","state":"FINISHED","receivers":[{"amount":100,"description":"分给商户A","type":"MERCHANT_ID","account":"1900000109","result":"SUCCESS","fail_reason":null,"create_time":"2015-05-20T13:29:35.120+08:00","finish_time":"2015-05-20T13:29:35.120+08:00","detail_id":"36011111111111111111111"}]}"#;
+        let result = serde_json::from_str::<ProfitSharingOrderResponse>(body).unwrap();
+        assert_eq!(result.state, ProfitSharingState::FINISHED);
+        let receivers = result.receivers.unwrap();
+        assert_eq!(receivers[0].r#type, ReceiverType::MerchantId);
+        assert_eq!(receivers[0].result, ProfitSharingResult::SUCCESS);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::utils::*;
 use crate::wechat::prelude::*;
 use crate::BoxFuture;
 use crate::*;
@@ -6,6 +7,18 @@ pub trait RefundTrait {
     fn refund(&self, data: ReqRefundOrder) -> BoxFuture<RefundResponse>;
     /// 查询退款
     fn query_refund(&self, out_refund_no: &str) -> BoxFuture<RefundResponse>;
+    /// 发起异常退款,退款状态为 ABNORMAL 时使用
+    /// refund_id 为微信支付退款单号
+    fn apply_abnormal_refund(
+        &self,
+        refund_id: &str,
+        data: ReqAbnormalRefund,
+    ) -> BoxFuture<'_, RefundResponse>;
+    /// 查询一组退款单,返回其中需要人工跟进(状态为 ABNORMAL)的退款
+    fn query_abnormal_refunds(
+        &self,
+        out_refund_nos: Vec<String>,
+    ) -> BoxFuture<'_, Vec<RefundResponse>>;
 }
 impl RefundTrait for Payment<WechatConfig> {
     fn refund(&self, data: ReqRefundOrder) -> BoxFuture<RefundResponse> {
@@ -33,10 +46,69 @@ impl RefundTrait for Payment<WechatConfig> {
         let url = self.get_uri(&url, true, false);
         Box::pin(async move { self.do_request::<RefundResponse>(&url, "GET", "").await })
     }
+    fn apply_abnormal_refund(
+        &self,
+        refund_id: &str,
+        data: ReqAbnormalRefund,
+    ) -> BoxFuture<'_, RefundResponse> {
+        let url = format!(
+            "/v3/refund/domestic/refunds/{}/apply-abnormal-refund",
+            refund_id
+        );
+        let mut new_data = if self.is_sp() {
+            ReqAbnormalRefund {
+                sub_mchid: Some(self.config.mchid.clone()),
+                ..data
+            }
+        } else {
+            data
+        };
+        Box::pin(async move {
+            if new_data.bank_account.is_none() && new_data.real_name.is_none() {
+                let body = serde_json::to_string(&new_data)?;
+                return self.do_request::<RefundResponse>(&url, "POST", &body).await;
+            }
+            let (serial_no, cert_file) = self.get_platform_cert().await?;
+            if let Some(bank_account) = &new_data.bank_account {
+                new_data.bank_account = Some(rsa_encrypt_oaep(bank_account, &cert_file)?);
+            }
+            if let Some(real_name) = &new_data.real_name {
+                new_data.real_name = Some(rsa_encrypt_oaep(real_name, &cert_file)?);
+            }
+            let body = serde_json::to_string(&new_data)?;
+            let req_builder = self
+                .build_request_builder(&url, "POST", &body)?
+                .header("Wechatpay-Serial", serial_no);
+            self.send_request::<RefundResponse>(req_builder).await
+        })
+    }
+    fn query_abnormal_refunds(
+        &self,
+        out_refund_nos: Vec<String>,
+    ) -> BoxFuture<'_, Vec<RefundResponse>> {
+        Box::pin(async move {
+            let mut abnormal_refunds: Vec<RefundResponse> = vec![];
+            for out_refund_no in out_refund_nos.iter() {
+                let refund = self.query_refund(out_refund_no).await?;
+                if refund.need_follow_up() {
+                    abnormal_refunds.push(refund);
+                }
+            }
+            Ok(abnormal_refunds)
+        })
+    }
 }
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[test]
+    fn test_refund_status() {
+        let body = r#"{"refund_id":"50000000382019052709732678859","out_refund_no":"1217752501201407033233368018","transaction_id":"1217752501201407033233368018","out_trade_no":"1217752501201407033233368018","channel":"OTHER_BANKCARD","user_received_account":"招商银行信用卡0403","success_time":null,"create_time":"2020-12-01T16:18:12+08:00","status":"ABNORMAL","funds_account":"UNSETTLED","amount":{"total":100,"refund":100,"payer_total":90,"payer_refund":90,"settlement_refund":100,"settlement_total":100,"discount_refund":10,"currency":"CNY","refund_fee":0},"promotion_detail":[]}"#;
+        let result = serde_json::from_str::<RefundResponse>(body).unwrap();
+        assert_eq!(result.channel, RefundChannel::OtherBankcard);
+        assert_eq!(result.status, RefundStatus::ABNORMAL);
+        assert!(result.need_follow_up());
+    }
     //use crate::wechat::prelude::*;
     #[tokio::test]
     async fn test_refund() {
@@ -0,0 +1,387 @@
+use crate::utils::*;
+use crate::wechat::prelude::*;
+use crate::*;
+use openssl::sha::sha256;
+/// 转账金额达到此金额(单位为分)时必须传入收款用户姓名
+pub const TRANSFER_USER_NAME_THRESHOLD: i32 = 200_000;
+/// 商家转账,仅支持直连商户
+/// 收款用户姓名会自动使用平台证书加密
+/// 批次通知使用 BaseTrait::notify,U 为 ResourceTransferBatchBody
+/// 转账单据通知使用 BaseTrait::notify,U 为 TransferBillQueryResponse
+pub trait TransferTrait {
+    /// 发起商家转账批次
+    fn transfer_batch(&self, data: ReqTransferBatch) -> BoxFuture<'_, TransferBatchResponse>;
+    /// 通过微信批次单号查询批次单
+    fn query_transfer_batch(
+        &self,
+        batch_id: &str,
+        query: ReqTransferBatchQuery,
+    ) -> BoxFuture<'_, TransferBatchQueryResponse>;
+    /// 通过商家批次单号查询批次单
+    fn query_transfer_batch_by_out_no(
+        &self,
+        out_batch_no: &str,
+        query: ReqTransferBatchQuery,
+    ) -> BoxFuture<'_, TransferBatchQueryResponse>;
+    /// 通过微信明细单号查询明细单
+    fn query_transfer_detail(
+        &self,
+        batch_id: &str,
+        detail_id: &str,
+    ) -> BoxFuture<'_, TransferDetailResponse>;
+    /// 通过商家明细单号查询明细单
+    fn query_transfer_detail_by_out_no(
+        &self,
+        out_batch_no: &str,
+        out_detail_no: &str,
+    ) -> BoxFuture<'_, TransferDetailResponse>;
+    /// 转账账单电子回单申请受理
+    fn apply_transfer_receipt(&self, out_batch_no: &str) -> BoxFuture<'_, TransferReceiptResponse>;
+    /// 查询转账账单电子回单
+    fn query_transfer_receipt(&self, out_batch_no: &str) -> BoxFuture<'_, TransferReceiptResponse>;
+    /// 转账明细电子回单受理
+    fn apply_transfer_detail_receipt(
+        &self,
+        data: ReqTransferDetailReceipt,
+    ) -> BoxFuture<'_, TransferDetailReceiptResponse>;
+    /// 查询转账明细电子回单受理结果
+    fn query_transfer_detail_receipt(
+        &self,
+        data: ReqTransferDetailReceipt,
+    ) -> BoxFuture<'_, TransferDetailReceiptResponse>;
+    /// 发起转账单据
+    fn transfer_bill(&self, data: ReqTransferBill) -> BoxFuture<'_, TransferBillResponse>;
+    /// 通过商户单号查询转账单据
+    fn query_transfer_bill(&self, out_bill_no: &str) -> BoxFuture<'_, TransferBillQueryResponse>;
+    /// 通过微信转账单号查询转账单据
+    fn query_transfer_bill_by_no(
+        &self,
+        transfer_bill_no: &str,
+    ) -> BoxFuture<'_, TransferBillQueryResponse>;
+    /// 撤销转账单据
+    fn cancel_transfer_bill(&self, out_bill_no: &str) -> BoxFuture<'_, TransferBillCancelResponse>;
+    /// 转账单据电子回单申请受理
+    fn apply_transfer_bill_receipt(
+        &self,
+        out_bill_no: &str,
+    ) -> BoxFuture<'_, TransferBillReceiptResponse>;
+    /// 查询转账单据电子回单
+    fn query_transfer_bill_receipt(
+        &self,
+        out_bill_no: &str,
+    ) -> BoxFuture<'_, TransferBillReceiptResponse>;
+    /// 下载电子回单,hash_value 不为空时会校验文件的SHA256
+    fn download_receipt<'a>(
+        &'a self,
+        download_url: &'a str,
+        hash_value: Option<&'a str>,
+    ) -> BoxFuture<'a, Vec<u8>>;
+}
+impl TransferTrait for Payment<WechatConfig> {
+    fn transfer_batch(&self, data: ReqTransferBatch) -> BoxFuture<'_, TransferBatchResponse> {
+        let mut new_data = ReqTransferBatch {
+            appid: data.appid.clone().or(Some(self.config.app_id.clone())),
+            ..data
+        };
+        if new_data.total_num == 0 {
+            new_data.total_num = new_data.transfer_detail_list.len() as i32;
+        }
+        if new_data.total_amount == 0 {
+            new_data.total_amount = new_data
+                .transfer_detail_list
+                .iter()
+                .map(|item| item.transfer_amount as i64)
+                .sum();
+        }
+        Box::pin(async move {
+            let url = "/v3/transfer/batches";
+            for item in new_data.transfer_detail_list.iter() {
+                check_user_name(item.transfer_amount, &item.user_name)?;
+            }
+            let has_name = new_data
+                .transfer_detail_list
+                .iter()
+                .any(|item| item.user_name.is_some());
+            if !has_name {
+                let body = serde_json::to_string(&new_data)?;
+                return self
+                    .do_request::<TransferBatchResponse>(url, "POST", &body)
+                    .await;
+            }
+            let (serial_no, cert_file) = self.get_platform_cert().await?;
+            for item in new_data.transfer_detail_list.iter_mut() {
+                if let Some(user_name) = &item.user_name {
+                    item.user_name = Some(rsa_encrypt_oaep(user_name, &cert_file)?);
+                }
+            }
+            let body = serde_json::to_string(&new_data)?;
+            let req_builder = self
+                .build_request_builder(url, "POST", &body)?
+                .header("Wechatpay-Serial", serial_no);
+            self.send_request::<TransferBatchResponse>(req_builder)
+                .await
+        })
+    }
+    fn query_transfer_batch(
+        &self,
+        batch_id: &str,
+        query: ReqTransferBatchQuery,
+    ) -> BoxFuture<'_, TransferBatchQueryResponse> {
+        let url = format!(
+            "/v3/transfer/batches/batch-id/{}{}",
+            batch_id,
+            batch_query_string(&query)
+        );
+        Box::pin(async move {
+            self.do_request::<TransferBatchQueryResponse>(&url, "GET", "")
+                .await
+        })
+    }
+    fn query_transfer_batch_by_out_no(
+        &self,
+        out_batch_no: &str,
+        query: ReqTransferBatchQuery,
+    ) -> BoxFuture<'_, TransferBatchQueryResponse> {
+        let url = format!(
+            "/v3/transfer/batches/out-batch-no/{}{}",
+            out_batch_no,
+            batch_query_string(&query)
+        );
+        Box::pin(async move {
+            self.do_request::<TransferBatchQueryResponse>(&url, "GET", "")
+                .await
+        })
+    }
+    fn query_transfer_detail(
+        &self,
+        batch_id: &str,
+        detail_id: &str,
+    ) -> BoxFuture<'_, TransferDetailResponse> {
+        let url = format!(
+            "/v3/transfer/batches/batch-id/{}/details/detail-id/{}",
+            batch_id, detail_id
+        );
+        Box::pin(async move {
+            self.do_request::<TransferDetailResponse>(&url, "GET", "")
+                .await
+        })
+    }
+    fn query_transfer_detail_by_out_no(
+        &self,
+        out_batch_no: &str,
+        out_detail_no: &str,
+    ) -> BoxFuture<'_, TransferDetailResponse> {
+        let url = format!(
+            "/v3/transfer/batches/out-batch-no/{}/details/out-detail-no/{}",
+            out_batch_no, out_detail_no
+        );
+        Box::pin(async move {
+            self.do_request::<TransferDetailResponse>(&url, "GET", "")
+                .await
+        })
+    }
+    fn apply_transfer_receipt(&self, out_batch_no: &str) -> BoxFuture<'_, TransferReceiptResponse> {
+        let body = format!("{{\"out_batch_no\":\"{}\"}}", out_batch_no);
+        Box::pin(async move {
+            self.do_request::<TransferReceiptResponse>("/v3/transfer/bill-receipt", "POST", &body)
+                .await
+        })
+    }
+    fn query_transfer_receipt(&self, out_batch_no: &str) -> BoxFuture<'_, TransferReceiptResponse> {
+        let url = format!("/v3/transfer/bill-receipt/{}", out_batch_no);
+        Box::pin(async move {
+            self.do_request::<TransferReceiptResponse>(&url, "GET", "")
+                .await
+        })
+    }
+    fn apply_transfer_detail_receipt(
+        &self,
+        data: ReqTransferDetailReceipt,
+    ) -> BoxFuture<'_, TransferDetailReceiptResponse> {
+        Box::pin(async move {
+            let body = serde_json::to_string(&data)?;
+            self.do_request::<TransferDetailReceiptResponse>(
+                "/v3/transfer-detail/electronic-receipts",
+                "POST",
+                &body,
+            )
+            .await
+        })
+    }
+    fn query_transfer_detail_receipt(
+        &self,
+        data: ReqTransferDetailReceipt,
+    ) -> BoxFuture<'_, TransferDetailReceiptResponse> {
+        let mut url = format!(
+            "/v3/transfer-detail/electronic-receipts?accept_type={}&out_detail_no={}",
+            data.accept_type, data.out_detail_no
+        );
+        if let Some(out_batch_no) = data.out_batch_no {
+            url = format!("{}&out_batch_no={}", url, out_batch_no);
+        }
+        Box::pin(async move {
+            self.do_request::<TransferDetailReceiptResponse>(&url, "GET", "")
+                .await
+        })
+    }
+    fn transfer_bill(&self, data: ReqTransferBill) -> BoxFuture<'_, TransferBillResponse> {
+        let mut new_data = ReqTransferBill {
+            appid: data.appid.clone().or(Some(self.config.app_id.clone())),
+            ..data
+        };
+        if new_data.notify_url.is_none() && !self.config.notify_url.is_empty() {
+            new_data.notify_url = Some(self.config.notify_url.clone());
+        }
+        Box::pin(async move {
+            let url = "/v3/fund-app/mch-transfer/transfer-bills";
+            check_user_name(new_data.transfer_amount, &new_data.user_name)?;
+            let user_name = match new_data.user_name.clone() {
+                Some(user_name) => user_name,
+                None => {
+                    let body = serde_json::to_string(&new_data)?;
+                    return self
+                        .do_request::<TransferBillResponse>(url, "POST", &body)
+                        .await;
+                }
+            };
+            let (serial_no, cert_file) = self.get_platform_cert().await?;
+            new_data.user_name = Some(rsa_encrypt_oaep(&user_name, &cert_file)?);
+            let body = serde_json::to_string(&new_data)?;
+            let req_builder = self
+                .build_request_builder(url, "POST", &body)?
+                .header("Wechatpay-Serial", serial_no);
+            self.send_request::<TransferBillResponse>(req_builder).await
+        })
+    }
+    fn query_transfer_bill(&self, out_bill_no: &str) -> BoxFuture<'_, TransferBillQueryResponse> {
+        let url = format!(
+            "/v3/fund-app/mch-transfer/transfer-bills/out-bill-no/{}",
+            out_bill_no
+        );
+        Box::pin(async move {
+            self.do_request::<TransferBillQueryResponse>(&url, "GET", "")
+                .await
+        })
+    }
+    fn query_transfer_bill_by_no(
+        &self,
+        transfer_bill_no: &str,
+    ) -> BoxFuture<'_, TransferBillQueryResponse> {
+        let url = format!(
+            "/v3/fund-app/mch-transfer/transfer-bills/transfer-bill-no/{}",
+            transfer_bill_no
+        );
+        Box::pin(async move {
+            self.do_request::<TransferBillQueryResponse>(&url, "GET", "")
+                .await
+        })
+    }
+    fn cancel_transfer_bill(&self, out_bill_no: &str) -> BoxFuture<'_, TransferBillCancelResponse> {
+        let url = format!(
+            "/v3/fund-app/mch-transfer/transfer-bills/out-bill-no/{}/cancel",
+            out_bill_no
+        );
+        Box::pin(async move {
+            self.do_request::<TransferBillCancelResponse>(&url, "POST", "")
+                .await
+        })
+    }
+    fn apply_transfer_bill_receipt(
+        &self,
+        out_bill_no: &str,
+    ) -> BoxFuture<'_, TransferBillReceiptResponse> {
+        let body = format!("{{\"out_bill_no\":\"{}\"}}", out_bill_no);
+        Box::pin(async move {
+            self.do_request::<TransferBillReceiptResponse>(
+                "/v3/fund-app/mch-transfer/elecsign/out-bill-no",
+                "POST",
+                &body,
+            )
+            .await
+        })
+    }
+    fn query_transfer_bill_receipt(
+        &self,
+        out_bill_no: &str,
+    ) -> BoxFuture<'_, TransferBillReceiptResponse> {
+        let url = format!(
+            "/v3/fund-app/mch-transfer/elecsign/out-bill-no/{}",
+            out_bill_no
+        );
+        Box::pin(async move {
+            self.do_request::<TransferBillReceiptResponse>(&url, "GET", "")
+                .await
+        })
+    }
+    fn download_receipt<'a>(
+        &'a self,
+        download_url: &'a str,
+        hash_value: Option<&'a str>,
+    ) -> BoxFuture<'a, Vec<u8>> {
+        Box::pin(async move {
+            let resp = self.download(download_url).await?;
+            let content = resp.bytes().await?.to_vec();
+            if let Some(hash_value) = hash_value {
+                let digest = sha256(&content)
+                    .iter()
+                    .map(|byte| format!("{:02x}", byte))
+                    .collect::<String>();
+                if !digest.eq_ignore_ascii_case(hash_value) {
+                    return Err(e("receipt hash verify error"));
+                }
+            }
+            Ok(content)
+        })
+    }
+}
+// 转账金额达到阈值时必须传入收款用户姓名
+fn check_user_name(transfer_amount: i32, user_name: &Option<String>) -> WeaResult<()> {
+    if transfer_amount >= TRANSFER_USER_NAME_THRESHOLD && user_name.is_none() {
+        return Err(e("user_name is required when transfer_amount >= 2000 yuan"));
+    }
+    Ok(())
+}
+// 构建查询批次单的参数
+fn batch_query_string(query: &ReqTransferBatchQuery) -> String {
+    let mut query_str = format!("?need_query_detail={}", query.need_query_detail);
+    if let Some(offset) = query.offset {
+        query_str = format!("{}&offset={}", query_str, offset);
+    }
+    if let Some(limit) = query.limit {
+        query_str = format!("{}&limit={}", query_str, limit);
+    }
+    if let Some(detail_status) = &query.detail_status {
+        query_str = format!("{}&detail_status={}", query_str, detail_status);
+    }
+    query_str
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_check_user_name() {
+        assert!(check_user_name(199_999, &None).is_ok());
+        assert!(check_user_name(200_000, &None).is_err());
+        assert!(check_user_name(200_000, &Some("张三".to_string())).is_ok());
+    }
+    #[test]
+    fn test_batch_query_string() {
+        let query = ReqTransferBatchQuery {
+            need_query_detail: true,
+            offset: Some(0),
+            limit: Some(20),
+            detail_status: Some("FAIL".to_string()),
+        };
+        assert_eq!(
+            batch_query_string(&query),
+            "?need_query_detail=true&offset=0&limit=20&detail_status=FAIL"
+        );
+    }
+    #[test]
+    fn test_transfer_bill_response() {
+        let body = r#"{"out_bill_no":"plfk2020042013","transfer_bill_no":"1330000071100999991182020050700019480001","create_time":"2015-05-20T13:29:35.120+08:00","state":"WAIT_USER_CONFIRM","package_info":"affffddafdfafddffda=="}"#;
+        let result = serde_json::from_str::<TransferBillResponse>(body).unwrap();
+        assert_eq!(result.state, TransferBillState::WaitUserConfirm);
+    }
+}
@@ -0,0 +1,161 @@
+use crate::utils::*;
+use crate::wechat::prelude::*;
+use crate::wechat::v2::xml::*;
+use crate::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::BTreeMap;
+/// v2 接口公共能力
+/// 使用配置中的 api_v2_key(为空时使用 mch_key)签名,需要证书的接口使用 apiclient_cert/apiclient_key 双向认证
+pub trait V2Trait {
+    /// 生成签名,参数按参数名ASCII码排序,空值及 sign 不参与签名
+    fn v2_sign(
+        &self,
+        params: &BTreeMap<String, String>,
+        sign_type: &V2SignType,
+    ) -> WeaResult<String>;
+    /// 验证应答或通知的签名
+    fn v2_verify(
+        &self,
+        params: &BTreeMap<String, String>,
+        sign_type: &V2SignType,
+    ) -> WeaResult<bool>;
+    /// 构建请求 client,with_cert 为 true 时携带商户证书
+    fn v2_client(&self, with_cert: bool) -> WeaResult<reqwest::Client>;
+    /// 发起 v2 请求,自动补充 nonce_str 及签名,应答 return_code/result_code 失败时返回错误
+    /// result_code 为 FAIL 时错误的 kind 为 err_code,如 SYSTEMERROR 需要使用原单号重试或查询
+    fn v2_request<'a, T: Serialize + Sync, U: DeserializeOwned>(
+        &'a self,
+        url: &'a str,
+        data: &'a T,
+        sign_type: V2SignType,
+        with_cert: bool,
+    ) -> BoxFuture<'a, U>;
+}
+impl V2Trait for Payment<WechatConfig> {
+    fn v2_sign(
+        &self,
+        params: &BTreeMap<String, String>,
+        sign_type: &V2SignType,
+    ) -> WeaResult<String> {
+        let data = params
+            .iter()
+            .filter(|(key, value)| key.as_str() != "sign" && !value.is_empty())
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<String>>()
+            .join("&");
+        let key = self.config.v2_key();
+        let data = format!("{}&key={}", data, key);
+        match sign_type {
+            V2SignType::MD5 => Ok(hex_digest("MD5", data.as_bytes())?.to_uppercase()),
+            V2SignType::HmacSha256 => hmac_sha256(key, &data),
+        }
+    }
+    fn v2_verify(
+        &self,
+        params: &BTreeMap<String, String>,
+        sign_type: &V2SignType,
+    ) -> WeaResult<bool> {
+        let sign = match params.get("sign") {
+            Some(sign) => sign,
+            None => return Ok(false),
+        };
+        let sign_type = match params.get("sign_type").map(|v| v.as_str()) {
+            Some("HMAC-SHA256") => V2SignType::HmacSha256,
+            Some("MD5") => V2SignType::MD5,
+            _ => sign_type.clone(),
+        };
+        let expected = self.v2_sign(params, &sign_type)?;
+        Ok(openssl::memcmp::eq(expected.as_bytes(), sign.as_bytes()))
+    }
+    fn v2_client(&self, with_cert: bool) -> WeaResult<reqwest::Client> {
+        let builder = reqwest::Client::builder().user_agent(SDK_UA);
+        let builder = if with_cert {
+            let (apiclient_key, apiclient_cert) = self.merchant_cert();
+            let cert = std::fs::read(apiclient_cert)?;
+            let key = std::fs::read(apiclient_key)?;
+            builder.identity(reqwest::Identity::from_pkcs8_pem(&cert, &key)?)
+        } else {
+            builder
+        };
+        Ok(builder.build()?)
+    }
+    fn v2_request<'a, T: Serialize + Sync, U: DeserializeOwned>(
+        &'a self,
+        url: &'a str,
+        data: &'a T,
+        sign_type: V2SignType,
+        with_cert: bool,
+    ) -> BoxFuture<'a, U> {
+        Box::pin(async move {
+            let mut params = to_params(data)?;
+            params
+                .entry("nonce_str".to_string())
+                .or_insert_with(|| generate_random_string(32));
+            if sign_type != V2SignType::MD5 {
+                params.insert("sign_type".to_string(), sign_type.as_str().to_string());
+            }
+            let sign = self.v2_sign(&params, &sign_type)?;
+            params.insert("sign".to_string(), sign);
+            let full_url = format!("{}{}", self.base_url(), url);
+            let res = self
+                .v2_client(with_cert)?
+                .post(full_url)
+                .header("Content-Type", "text/xml; charset=utf-8")
+                .body(to_xml(&params))
+                .send()
+                .await?
+                .text()
+                .await?;
+            let value = from_xml(&res)?;
+            let res_params = flat_params(&value);
+            let field = |key: &str| res_params.get(key).cloned().unwrap_or_default();
+            if field("return_code") != "SUCCESS" {
+                return Err(e(&field("return_msg")));
+            }
+            if field("result_code") == "FAIL" {
+                return Err(WeaError::new(field("err_code"), field("err_code_des")));
+            }
+            // 红包等接口应答中没有 sign,有 sign 时需要验签
+            if res_params.contains_key("sign") && !self.v2_verify(&res_params, &sign_type)? {
+                return Err(e("signature verify error"));
+            }
+            Ok(serde_json::from_value::<U>(value)?)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    // 签名示例来自微信支付v2签名算法说明
+    #[test]
+    fn test_v2_sign() {
+        let config = WechatConfig {
+            api_v2_key: Some("192006250b4c09247ec02edce69f6a2d".to_string()),
+            ..Default::default()
+        };
+        let payment = Payment::new(config);
+        let params = [
+            ("appid", "wxd930ea5d5a258f4f"),
+            ("mch_id", "10000100"),
+            ("device_info", "1000"),
+            ("body", "test"),
+            ("nonce_str", "ibuaiVcKdpRxkhJA"),
+        ]
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect::<BTreeMap<String, String>>();
+        let sign = payment.v2_sign(&params, &V2SignType::MD5).unwrap();
+        assert_eq!(sign, "9A0A8659F005D6984697E2CA0A9CF3B7");
+        let sign = payment.v2_sign(&params, &V2SignType::HmacSha256).unwrap();
+        assert_eq!(
+            sign,
+            "6A9AE1657590FD6257D693A078E1C3E4BB6BA4DC30B23E0EE2496E54170DACD6"
+        );
+        let mut params = params;
+        params.insert("sign".to_string(), sign);
+        params.insert("sign_type".to_string(), "HMAC-SHA256".to_string());
+        assert!(!payment.v2_verify(&params, &V2SignType::MD5).unwrap());
+    }
+}
@@ -0,0 +1,179 @@
+use crate::wechat::v2::xml::*;
+use serde::{Deserialize, Serialize};
+/// v2 签名类型
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub enum V2SignType {
+    #[default]
+    MD5,
+    #[serde(rename = "HMAC-SHA256")]
+    HmacSha256,
+}
+impl V2SignType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            V2SignType::MD5 => "MD5",
+            V2SignType::HmacSha256 => "HMAC-SHA256",
+        }
+    }
+}
+/// 发放普通红包
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqSendRedPack {
+    //商户订单号,mch_id+yyyymmdd+10位一天内不能重复的数字
+    pub mch_billno: String,
+    //商户号,为空时使用配置中的 mchid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mch_id: Option<String>,
+    //公众账号appid,为空时使用配置中的 app_id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wxappid: Option<String>,
+    //商户名称
+    pub send_name: String,
+    //接收红包的用户openid
+    pub re_openid: String,
+    //付款金额,单位分
+    pub total_amount: i64,
+    //红包发放总人数,普通红包为1
+    pub total_num: i32,
+    //红包祝福语
+    pub wishing: String,
+    //调用接口的机器IP
+    pub client_ip: String,
+    //活动名称
+    pub act_name: String,
+    //备注
+    pub remark: String,
+    //场景id,金额大于200元或小于1元时必填
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scene_id: Option<String>,
+    //活动信息,urlencode 后的 posttime=xx&clientversion=xx 等
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub risk_info: Option<String>,
+}
+/// 发放裂变红包
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReqSendGroupRedPack {
+    //商户订单号
+    pub mch_billno: String,
+    //商户号,为空时使用配置中的 mchid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mch_id: Option<String>,
+    //公众账号appid,为空时使用配置中的 app_id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wxappid: Option<String>,
+    //商户名称
+    pub send_name: String,
+    //种子用户openid
+    pub re_openid: String,
+    //红包总金额,单位分
+    pub total_amount: i64,
+    //红包发放总人数,至少3人
+    pub total_num: i32,
+    //红包金额设置方式,ALL_RAND 为全部随机,为空时使用 ALL_RAND
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amt_type: Option<String>,
+    //红包祝福语
+    pub wishing: String,
+    //活动名称
+    pub act_name: String,
+    //备注
+    pub remark: String,
+    //场景id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scene_id: Option<String>,
+    //活动信息
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub risk_info: Option<String>,
+}
+/// 发放红包返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct SendRedPackResponse {
+    //商户订单号
+    pub mch_billno: String,
+    //商户号
+    pub mch_id: String,
+    //公众账号appid
+    pub wxappid: String,
+    //接收红包的用户openid
+    pub re_openid: String,
+    //付款金额
+    #[serde(deserialize_with = "deserialize_number")]
+    pub total_amount: i64,
+    //微信红包订单号
+    pub send_listid: Option<String>,
+}
+/// 红包状态
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RedPackStatus {
+    //发放中
+    #[default]
+    Sending,
+    //已发放待领取
+    Sent,
+    //发放失败
+    Failed,
+    //已领取
+    Received,
+    //退款中,微信返回值为 RFUND_ING
+    RfundIng,
+    //已退款
+    Refund,
+}
+/// 红包领取记录
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct RedPackReceiveInfo {
+    //领取红包的用户openid
+    pub openid: String,
+    //领取金额
+    #[serde(deserialize_with = "deserialize_number")]
+    pub amount: i64,
+    //领取时间
+    pub rcv_time: String,
+}
+/// 红包领取列表
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct RedPackReceiveList {
+    //领取记录
+    #[serde(deserialize_with = "deserialize_one_or_many")]
+    pub hbinfo: Vec<RedPackReceiveInfo>,
+}
+/// 查询红包记录返回
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct RedPackQueryResponse {
+    //商户订单号
+    pub mch_billno: String,
+    //商户号
+    pub mch_id: String,
+    //红包单号
+    pub detail_id: String,
+    //红包状态
+    pub status: RedPackStatus,
+    //发放类型 API/UPLOAD/ACTIVITY
+    pub send_type: String,
+    //红包类型 GROUP/NORMAL
+    pub hb_type: String,
+    //红包个数
+    #[serde(deserialize_with = "deserialize_number")]
+    pub total_num: i32,
+    //红包总金额
+    #[serde(deserialize_with = "deserialize_number")]
+    pub total_amount: i64,
+    //发送失败原因
+    pub reason: Option<String>,
+    //红包发送时间
+    pub send_time: String,
+    //红包退款时间
+    pub refund_time: Option<String>,
+    //红包退款金额
+    #[serde(default, deserialize_with = "deserialize_option_number")]
+    pub refund_amount: Option<i64>,
+    //祝福语
+    pub wishing: Option<String>,
+    //活动描述
+    pub remark: Option<String>,
+    //活动名称
+    pub act_name: Option<String>,
+    //领取列表
+    pub hblist: Option<RedPackReceiveList>,
+}
@@ -0,0 +1,6 @@
+//! 微信支付 v2 接口
+//! 仅用于 v3 尚未提供的能力(如现金红包),请求及应答为 XML,使用 APIv2 密钥签名
+pub mod common;
+pub mod dict;
+pub mod redpack;
+pub mod xml;
@@ -0,0 +1,94 @@
+use crate::wechat::prelude::*;
+use crate::*;
+use serde::Serialize;
+/// 现金红包,仅 v2 接口提供,需要商户证书
+pub trait RedPackTrait {
+    /// 发放普通红包
+    fn send_redpack(&self, data: ReqSendRedPack) -> BoxFuture<'_, SendRedPackResponse>;
+    /// 发放裂变红包
+    fn send_group_redpack(&self, data: ReqSendGroupRedPack) -> BoxFuture<'_, SendRedPackResponse>;
+    /// 查询红包记录
+    /// mch_billno: 商户发放红包的商户订单号
+    fn query_redpack(&self, mch_billno: String) -> BoxFuture<'_, RedPackQueryResponse>;
+}
+impl RedPackTrait for Payment<WechatConfig> {
+    fn send_redpack(&self, data: ReqSendRedPack) -> BoxFuture<'_, SendRedPackResponse> {
+        let url = "/mmpaymkttransfers/sendredpack";
+        Box::pin(async move {
+            let data = ReqSendRedPack {
+                mch_id: data.mch_id.clone().or(Some(self.config.mchid.clone())),
+                wxappid: data.wxappid.clone().or(Some(self.config.app_id.clone())),
+                ..data
+            };
+            self.v2_request(url, &data, V2SignType::MD5, true).await
+        })
+    }
+    fn send_group_redpack(&self, data: ReqSendGroupRedPack) -> BoxFuture<'_, SendRedPackResponse> {
+        let url = "/mmpaymkttransfers/sendgroupredpack";
+        Box::pin(async move {
+            let data = ReqSendGroupRedPack {
+                mch_id: data.mch_id.clone().or(Some(self.config.mchid.clone())),
+                wxappid: data.wxappid.clone().or(Some(self.config.app_id.clone())),
+                amt_type: data.amt_type.clone().or(Some("ALL_RAND".to_string())),
+                ..data
+            };
+            self.v2_request(url, &data, V2SignType::MD5, true).await
+        })
+    }
+    fn query_redpack(&self, mch_billno: String) -> BoxFuture<'_, RedPackQueryResponse> {
+        let url = "/mmpaymkttransfers/gethbinfo";
+        Box::pin(async move {
+            let data = ReqQueryRedPack {
+                mch_billno,
+                mch_id: self.config.mchid.clone(),
+                appid: self.config.app_id.clone(),
+                bill_type: "MCHT",
+            };
+            self.v2_request(url, &data, V2SignType::MD5, true).await
+        })
+    }
+}
+#[derive(Serialize)]
+struct ReqQueryRedPack {
+    mch_billno: String,
+    mch_id: String,
+    appid: String,
+    bill_type: &'static str,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wechat::v2::xml::from_xml_str;
+    #[test]
+    fn test_parse_redpack_query() {
+        let xml = r#"<xml>
+<return_code><![CDATA[SUCCESS]]></return_code>
+<return_msg><![CDATA[OK]]></return_msg>
+<result_code><![CDATA[SUCCESS]]></result_code>
+<mch_billno><![CDATA[9010080799701411170000046603]]></mch_billno>
+<mch_id><![CDATA[11475856]]></mch_id>
+<detail_id><![CDATA[10000417012016080830956240040]]></detail_id>
+<status><![CDATA[RECEIVED]]></status>
+<send_type><![CDATA[ACTIVITY]]></send_type>
+<hb_type><![CDATA[NORMAL]]></hb_type>
+<total_num>1</total_num>
+<total_amount>100</total_amount>
+<send_time><![CDATA[2016-08-08 21:49:22]]></send_time>
+<hblist>
+<hbinfo>
+<openid><![CDATA[oHkLxtzmyHXX6FW_cAWo_orTSRXs]]></openid>
+<amount>100</amount>
+<rcv_time><![CDATA[2016-08-08 21:49:46]]></rcv_time>
+</hbinfo>
+</hblist>
+</xml>"#;
+        let res: RedPackQueryResponse = from_xml_str(xml).unwrap();
+        assert_eq!(res.status, RedPackStatus::Received);
+        assert_eq!(res.total_amount, 100);
+        assert_eq!(res.refund_amount, None);
+        let list = res.hblist.unwrap().hbinfo;
+        assert_eq!(list.len(), 1);
+        assert_eq!(list[0].amount, 100);
+    }
+}
@@ -0,0 +1,260 @@
+//! v2 接口的 XML 报文转换
+//! 请求报文为扁平的 `<xml><key><![CDATA[value]]></key></xml>`,应答报文可能含有嵌套节点
+//! 应答先转换为 serde_json::Value 再反序列化,叶子节点均为字符串
+use crate::utils::*;
+use crate::WeaResult;
+use serde::de::{DeserializeOwned, Deserializer};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// 将请求结构转换为参数表,None 字段不输出,数字及布尔转换为字符串
+pub fn to_params<T: Serialize>(data: &T) -> WeaResult<BTreeMap<String, String>> {
+    let value = serde_json::to_value(data)?;
+    let object = match value {
+        Value::Object(object) => object,
+        _ => return Err(e("v2 request must be a struct")),
+    };
+    let mut params = BTreeMap::new();
+    for (key, value) in object {
+        let value = match value {
+            Value::Null => continue,
+            Value::String(value) => value,
+            Value::Number(value) => value.to_string(),
+            Value::Bool(value) => value.to_string(),
+            _ => return Err(e(&format!("v2 field {} must be a scalar", key))),
+        };
+        params.insert(key, value);
+    }
+    Ok(params)
+}
+/// 参数表转换为 XML 报文
+pub fn to_xml(params: &BTreeMap<String, String>) -> String {
+    let mut xml = String::from("<xml>");
+    for (key, value) in params {
+        // CDATA 中不能出现 ]]>,需要拆分为两段
+        let value = value.replace("]]>", "]]]]><![CDATA[>");
+        xml.push_str(&format!("<{0}><![CDATA[{1}]]></{0}>", key, value));
+    }
+    xml.push_str("</xml>");
+    xml
+}
+/// 解析 XML 报文,返回根节点的内容
+/// 子节点转换为对象,同名子节点转换为数组,叶子节点为字符串
+pub fn from_xml(xml: &str) -> WeaResult<Value> {
+    let mut parser = XmlParser { xml, pos: 0 };
+    parser.skip_prolog();
+    let (_, value) = parser.parse_element()?;
+    match value {
+        Value::String(text) if text.trim().is_empty() => Ok(Value::Object(Map::new())),
+        value => Ok(value),
+    }
+}
+/// 解析 XML 报文并转换为对应的结构
+pub fn from_xml_str<U: DeserializeOwned>(xml: &str) -> WeaResult<U> {
+    Ok(serde_json::from_value(from_xml(xml)?)?)
+}
+/// 取出根节点下的字符串字段,用于验签
+pub fn flat_params(value: &Value) -> BTreeMap<String, String> {
+    value
+        .as_object()
+        .map(|object| {
+            object
+                .iter()
+                .filter_map(|(key, value)| value.as_str().map(|v| (key.clone(), v.to_string())))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+struct XmlParser<'a> {
+    xml: &'a str,
+    pos: usize,
+}
+impl<'a> XmlParser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.xml[self.pos..]
+    }
+    /// 跳过 XML 声明、注释及空白
+    fn skip_prolog(&mut self) {
+        loop {
+            let trimmed = self.rest().trim_start();
+            self.pos = self.xml.len() - trimmed.len();
+            if trimmed.starts_with("<?") {
+                self.skip_past("?>");
+            } else if trimmed.starts_with("<!--") {
+                self.skip_past("-->");
+            } else {
+                break;
+            }
+        }
+    }
+    fn skip_past(&mut self, end: &str) -> &str {
+        match self.rest().find(end) {
+            Some(index) => {
+                let skipped = &self.xml[self.pos..self.pos + index];
+                self.pos += index + end.len();
+                skipped
+            }
+            None => {
+                let skipped = &self.xml[self.pos..];
+                self.pos = self.xml.len();
+                skipped
+            }
+        }
+    }
+    fn parse_element(&mut self) -> WeaResult<(String, Value)> {
+        if !self.rest().starts_with('<') {
+            return Err(e("xml element expected"));
+        }
+        let tag = self.skip_past(">");
+        let tag = tag.trim_start_matches('<');
+        let self_closing = tag.ends_with('/');
+        let name = tag
+            .trim_end_matches('/')
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        if name.is_empty() {
+            return Err(e("xml element name is empty"));
+        }
+        if self_closing {
+            return Ok((name, Value::String(String::new())));
+        }
+        let mut text = String::new();
+        let mut children = Map::new();
+        loop {
+            let rest = self.rest();
+            if rest.is_empty() {
+                return Err(e(&format!("xml element {} not closed", name)));
+            } else if rest.starts_with("<![CDATA[") {
+                self.pos += "<![CDATA[".len();
+                text.push_str(self.skip_past("]]>"));
+            } else if rest.starts_with("<!--") {
+                self.skip_past("-->");
+            } else if rest.starts_with("</") {
+                self.skip_past(">");
+                break;
+            } else if rest.starts_with('<') {
+                let (child_name, child) = self.parse_element()?;
+                match children.get_mut(&child_name) {
+                    Some(Value::Array(items)) => items.push(child),
+                    Some(exists) => {
+                        let first = exists.take();
+                        *exists = Value::Array(vec![first, child]);
+                    }
+                    None => {
+                        children.insert(child_name, child);
+                    }
+                }
+            } else {
+                let end = rest.find('<').unwrap_or(rest.len());
+                text.push_str(&unescape(&rest[..end]));
+                self.pos += end;
+            }
+        }
+        if children.is_empty() {
+            Ok((name, Value::String(text)))
+        } else {
+            Ok((name, Value::Object(children)))
+        }
+    }
+}
+/// 还原 XML 转义字符
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+/// XML 中的数字均为字符串,反序列化时转换为数字
+pub(crate) fn deserialize_number<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    let value = String::deserialize(deserializer)?;
+    value.trim().parse::<T>().map_err(serde::de::Error::custom)
+}
+/// 可选的数字字段
+pub(crate) fn deserialize_option_number<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    match Option::<String>::deserialize(deserializer)? {
+        Some(value) if !value.trim().is_empty() => value
+            .trim()
+            .parse::<T>()
+            .map(Some)
+            .map_err(serde::de::Error::custom),
+        _ => Ok(None),
+    }
+}
+/// 只有一个子节点时不是数组,统一转换为数组
+pub(crate) fn deserialize_one_or_many<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany<T> {
+        Many(Vec<T>),
+        One(T),
+    }
+    match OneOrMany::<T>::deserialize(deserializer)? {
+        OneOrMany::Many(items) => Ok(items),
+        OneOrMany::One(item) => Ok(vec![item]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_to_xml() {
+        #[derive(Serialize)]
+        struct Req {
+            mch_billno: String,
+            total_amount: i64,
+            remark: Option<String>,
+        }
+        let params = to_params(&Req {
+            mch_billno: "0010010404201411170000046545".to_string(),
+            total_amount: 100,
+            remark: None,
+        })
+        .unwrap();
+        assert_eq!(
+            to_xml(&params),
+            "<xml><mch_billno><![CDATA[0010010404201411170000046545]]></mch_billno><total_amount><![CDATA[100]]></total_amount></xml>"
+        );
+    }
+    #[test]
+    fn test_from_xml() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<xml>
+<return_code><![CDATA[SUCCESS]]></return_code>
+<return_msg>OK &amp; done</return_msg>
+<hblist>
+<hbinfo><openid>a</openid><amount>100</amount></hbinfo>
+<hbinfo><openid>b</openid><amount>200</amount></hbinfo>
+</hblist>
+<empty/>
+</xml>"#;
+        let value = from_xml(xml).unwrap();
+        assert_eq!(value["return_code"], "SUCCESS");
+        assert_eq!(value["return_msg"], "OK & done");
+        assert_eq!(value["hblist"]["hbinfo"][1]["amount"], "200");
+        assert_eq!(value["empty"], "");
+        let params = flat_params(&value);
+        assert_eq!(params.len(), 3);
+        assert!(from_xml("<xml><a>1</a>").is_err());
+    }
+}